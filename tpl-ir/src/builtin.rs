@@ -1,13 +1,10 @@
-use inkwell::{
-    values::{BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue},
-    AddressSpace,
+use inkwell::values::{
+    BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, PointerValue,
 };
 
 use crate::{
-    error::{ErrorType, GenError},
-    get_int_order,
-    libc::Libc,
-    Compiler,
+    error::ErrorType, get_int_order, libc::Libc, list::List, map::Map, runtime::Runtime,
+    strings::Strings, CompileResult, Compiler,
 };
 
 use tpl_parser::{expressions::Expressions, value::Value};
@@ -19,13 +16,20 @@ pub trait BuiltIn<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    );
+    ) -> CompileResult<()>;
+    fn build_array_format(
+        &mut self,
+        array_type: &str,
+        array_pointer: PointerValue<'ctx>,
+        values: &mut Vec<BasicMetadataValueEnum<'ctx>>,
+        line: usize,
+    ) -> CompileResult<String>;
     fn build_input_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
 
     // helpful functions
     fn build_type_call(
@@ -33,25 +37,25 @@ pub trait BuiltIn<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
     fn build_len_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
     fn build_size_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
     fn build_concat_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
 
     // conversions
     fn build_to_str_call(
@@ -59,31 +63,43 @@ pub trait BuiltIn<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
     fn build_to_int8_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
     fn build_to_int16_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
     fn build_to_int32_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
     fn build_to_int64_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_to_float32_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_to_float64_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
 
     // allocation
     fn build_malloc_call(
@@ -91,21 +107,39 @@ pub trait BuiltIn<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
 
     fn build_realloc_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
 
     fn build_free_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+
+    // collections
+    fn build_collection_call(
+        &mut self,
+        function_name: String,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+
+    // strings
+    fn build_string_call(
+        &mut self,
+        function_name: String,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
 
     // files
     fn build_file_call(
@@ -113,21 +147,98 @@ pub trait BuiltIn<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
 
     fn build_close_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
 
     fn build_write_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>);
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+
+    fn build_file_argument(
+        &mut self,
+        function_name: &str,
+        arguments: &[Expressions],
+        arguments_count: usize,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn build_path_argument(
+        &mut self,
+        function_name: &str,
+        arguments: &[Expressions],
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<PointerValue<'ctx>>;
+
+    fn build_read_char_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_read_line_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_read_all_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_eof_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_seek_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_tell_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_exists_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+
+    // process
+    fn build_args_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_arg_count_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_exit_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
 }
 
 impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
@@ -136,78 +247,30 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() != 2 {
-            GenError::throw(
+            return Err(self.error(
                 "`concat` function takes 2 arguments!",
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let left_arg = self.compile_expression(
-            arguments[0].clone(),
-            line,
-            function,
-            self.current_expectation_value.clone(),
-        );
-        let right_arg = self.compile_expression(
-            arguments[1].clone(),
-            line,
-            function,
-            self.current_expectation_value.clone(),
-        );
-
-        let strcat_fn = self.__c_strcat();
+        let left_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
+        let right_arg = self.compile_expression(arguments[1].clone(), line, function, None)?;
 
         if !Compiler::validate_types(&[left_arg.0, right_arg.0], "str".to_string()) {
-            GenError::throw(
+            return Err(self.error(
                 "`concat` function takes only string types!",
                 ErrorType::TypeError,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
+            ));
         }
 
-        let val: BasicValueEnum<'ctx> = self
-            .builder
-            .build_call(
-                strcat_fn,
-                &[left_arg.1.into(), right_arg.1.into()],
-                "concat",
-            )
-            .unwrap_or_else(|_| {
-                GenError::throw(
-                    "An error occured while calling `concat` function!",
-                    ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
-            })
-            .try_as_basic_value()
-            .left()
-            .unwrap_or_else(|| {
-                GenError::throw(
-                    "Unable to get basic value from `concat` function!",
-                    ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
-            });
-
-        (String::from("str"), val)
+        // result is a new heap string, so both arguments stay untouched
+        let val = self.build_str_concat(left_arg.1, right_arg.1, line)?;
 
-        // `strcat` provides concatenating left and right values to left variable, which
-        // means that call `concat(a, b)` will insert result into variable 'a'.
-        // To fix it we need to copy both of values and use it in function (and try to free memory)
+        Ok((String::from("str"), val.into()))
     }
 
     fn build_print_call(
@@ -215,7 +278,7 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) {
+    ) -> CompileResult<()> {
         let mut fmts: Vec<String> = Vec::new();
         let mut values: Vec<BasicMetadataValueEnum<'ctx>> = Vec::new();
         let printf_fn = self.__c_printf();
@@ -226,95 +289,31 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                 line,
                 function,
                 self.current_expectation_value.clone(),
-            );
+            )?;
             let mut basic_value = compiled_arg.1;
 
             match compiled_arg.0.as_str() {
                 "void" => continue,
-                _ if compiled_arg.0.contains("[") => {
-                    // array
-                    let array_value = basic_value.into_vector_value();
-                    let array_type = compiled_arg.0.split("[").collect::<Vec<&str>>()[0];
-
-                    let array_len = {
-                        let left_parts = compiled_arg.0.split("[").collect::<Vec<&str>>();
-
-                        let right_parts = left_parts[1].split("]").collect::<Vec<&str>>();
-
-                        right_parts[0].parse::<u32>().unwrap_or_else(|_| {
-                            GenError::throw(
-                                "Unable to get array length!",
-                                ErrorType::BuildError,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
-                                line,
-                            );
-                            std::process::exit(1);
-                        })
-                    };
-
-                    let mut new_fmts: Vec<&str> = Vec::new();
-
-                    for array_index in 0..array_len {
-                        let mut element = array_value.const_extract_element(
-                            self.context.i32_type().const_int(array_index as u64, false),
-                        );
-
-                        let format_string = match array_type {
-                            "int8" => "%d",
-                            "int16" => "%hd",
-                            "int32" => "%d",
-                            "int64" => "%lld",
-                            "bool" => {
-                                let (_true, _false) = self.__boolean_strings();
-
-                                if let BasicValueEnum::IntValue(int) = element {
-                                    element = self
-                                        .builder
-                                        .build_select(int, _true, _false, "bool_fmt_str")
-                                        .unwrap();
-                                }
-
-                                "%s"
-                            }
-                            "str" => "\"%s\"",
-                            "char" => "'%c'",
-                            _ => {
-                                GenError::throw(
-                                    format!(
-                                        "Type `{}` is not supported for 'print' function!",
-                                        array_type
-                                    ),
-                                    ErrorType::NotSupported,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
-                                    line,
-                                );
-                                std::process::exit(1);
-                            }
-                        };
-
-                        new_fmts.push(format_string);
-                        values.push(element.into());
-                    }
-
-                    for (index, fmt) in new_fmts.iter().enumerate() {
-                        let mut output_string = format!("{},", fmt);
-
-                        if index == 0 {
-                            output_string = format!("[{},", fmt)
-                        } else if index == new_fmts.len() - 1 {
-                            output_string = format!("{}]", fmt);
-                        }
-
-                        fmts.push(output_string);
+                ctype if Compiler::__is_float_type(ctype) => {
+                    // varargs floats are always passed as `double`
+                    fmts.push(Compiler::__type_fmt(ctype));
+                    values.push(
+                        self.build_float_cast(compiled_arg.clone(), "float64", line)?
+                            .into(),
+                    );
 
-                        // i know that this code is piece of shit, but i wanna sleep ._.
-                        // i'll figure it out tomorrow
-                        //
-                        // nah i didn't figured it out
-                    }
+                    continue;
+                }
+                ctype if Compiler::__is_arr_type(ctype) => {
+                    // array elements are printed as `[1, 2, 3]`
+                    let array_fmt = self.build_array_format(
+                        ctype,
+                        basic_value.into_pointer_value(),
+                        &mut values,
+                        line,
+                    )?;
 
+                    fmts.push(array_fmt);
                     continue;
                 }
                 _ => {}
@@ -340,17 +339,14 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                 "str" => "%s",
                 "char" => "%c",
                 _ => {
-                    GenError::throw(
+                    return Err(self.error(
                         format!(
                             "Type `{}` is not supported for 'print' function!",
                             compiled_arg.0
                         ),
                         ErrorType::NotSupported,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
+                    ));
                 }
             }
             .to_string();
@@ -362,22 +358,94 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         let complete_fmt_string = self
             .builder
             .build_global_string_ptr(format!("{}\n", fmts.join(" ")).as_str(), "printf_fmt")
-            .unwrap_or_else(|_| {
-                GenError::throw(
+            .map_err(|_| {
+                self.error(
                     "Unable to create format string for C function!",
                     ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     line,
-                );
-                std::process::exit(1);
-            })
+                )
+            })?
             .as_pointer_value();
 
         let mut printf_arguments = vec![complete_fmt_string.into()];
         printf_arguments.append(&mut values);
 
         let _ = self.builder.build_call(printf_fn, &printf_arguments, "");
+
+        Ok(())
+    }
+
+    fn build_array_format(
+        &mut self,
+        array_type: &str,
+        array_pointer: PointerValue<'ctx>,
+        values: &mut Vec<BasicMetadataValueEnum<'ctx>>,
+        line: usize,
+    ) -> CompileResult<String> {
+        let element_type = Compiler::clean_array_datatype(array_type);
+        let array_len = Compiler::get_array_datatype_len(array_type);
+        let mut fmts = Vec::new();
+
+        for array_index in 0..array_len {
+            let index = self.context.i64_type().const_int(array_index, false);
+            let (_, element_pointer) = self.array_element_pointer(
+                (array_type.to_string(), array_pointer.into()),
+                index,
+                line,
+            )?;
+
+            // nested arrays are formatted recursively
+            if Compiler::__is_arr_type(&element_type) {
+                fmts.push(self.build_array_format(&element_type, element_pointer, values, line)?);
+                continue;
+            }
+
+            let mut element = self.build_value_load(&element_type, element_pointer, line)?;
+
+            let format_string = match element_type.as_str() {
+                "int8" => "%d",
+                "int16" => "%hd",
+                "int32" => "%d",
+                "int64" => "%lld",
+                "float32" | "float64" => {
+                    // varargs floats are always passed as `double`
+                    element = self
+                        .build_float_cast((element_type.clone(), element), "float64", line)?
+                        .into();
+
+                    "%g"
+                }
+                "bool" => {
+                    let (_true, _false) = self.__boolean_strings();
+
+                    if let BasicValueEnum::IntValue(int) = element {
+                        element = self
+                            .builder
+                            .build_select(int, _true, _false, "bool_fmt_str")
+                            .unwrap();
+                    }
+
+                    "%s"
+                }
+                "str" => "\"%s\"",
+                "char" => "'%c'",
+                _ => {
+                    return Err(self.error(
+                        format!(
+                            "Type `{}` is not supported for 'print' function!",
+                            element_type
+                        ),
+                        ErrorType::NotSupported,
+                        line,
+                    ));
+                }
+            };
+
+            fmts.push(format_string.to_string());
+            values.push(element.into());
+        }
+
+        Ok(format!("[{}]", fmts.join(", ")))
     }
 
     fn build_input_call(
@@ -385,31 +453,26 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() > 1 {
-            GenError::throw(
+            return Err(self.error(
                 "Function `input()` takes only 0 or 1 arguments! Example: input(\"Type here: \")",
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
         if let Some(argument) = arguments.first() {
-            let compiled_argument = self.compile_expression(argument.clone(), line, function, None);
+            let compiled_argument =
+                self.compile_expression(argument.clone(), line, function, None)?;
             let printf_fn = self.__c_printf();
 
             if compiled_argument.0 != "str" {
-                GenError::throw(
+                return Err(self.error(
                     "Function `input()` takes only string as argument!",
                     ErrorType::NotExpected,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     line,
-                );
-                std::process::exit(1);
+                ));
             }
 
             let _ = self
@@ -417,24 +480,10 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                 .build_call(printf_fn, &[compiled_argument.1.into()], "");
         }
 
-        let scanf_fn = self.__c_scanf();
-        let format_string = self
-            .builder
-            .build_global_string_ptr("%s", "")
-            .unwrap()
-            .as_basic_value_enum();
-
-        let result_alloca = self
-            .builder
-            .build_alloca(self.context.ptr_type(AddressSpace::default()), "")
-            .unwrap();
-
-        let _ = self
-            .builder
-            .build_call(scanf_fn, &[format_string.into(), result_alloca.into()], "")
-            .unwrap();
+        let stdin = self.build_stdin(line)?;
+        let result = self.build_str_read_line(stdin, line)?;
 
-        ("str".to_string(), result_alloca.into())
+        Ok(("str".to_string(), result.into()))
     }
 
     fn build_type_call(
@@ -442,38 +491,22 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `type()` requires only 1 argument, but {} found!",
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None);
-        let arg_type_string = self
-            .builder
-            .build_global_string_ptr(compiled_arg.0.as_str(), "_type")
-            .unwrap_or_else(|_| {
-                GenError::throw(
-                    "Unable to allocate memory for type fmt!",
-                    ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
-            })
-            .as_pointer_value();
+        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
+        let arg_type_string = self.build_str_literal(compiled_arg.0.as_str(), "_type");
 
-        (String::from("str"), arg_type_string.into())
+        Ok((String::from("str"), arg_type_string.into()))
     }
 
     fn build_len_call(
@@ -481,22 +514,19 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `len()` requires only 1 argument, but {} found!",
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None);
+        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
 
         match compiled_arg.0.as_str() {
             argtype if Compiler::__is_arr_type(argtype) => {
@@ -507,33 +537,39 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     .const_int(length, false)
                     .as_basic_value_enum();
 
-                (String::from("int64"), basic_value)
+                Ok((String::from("int64"), basic_value))
             }
-            "str" => {
-                let strlen_fn = self.__c_strlen();
-                let value = self
+            argtype if Compiler::__is_slice_type(argtype) => {
+                let length = self
                     .builder
-                    .build_call(strlen_fn, &[compiled_arg.1.into()], "")
-                    .unwrap()
-                    .try_as_basic_value()
-                    .left()
-                    .unwrap();
+                    .build_extract_value(compiled_arg.1.into_struct_value(), 1, "")
+                    .map_err(|_| {
+                        self.error("Unable to get slice length!", ErrorType::BuildError, line)
+                    })?;
 
-                (String::from("int64"), value)
+                Ok((String::from("int64"), length))
             }
-            _ => {
-                GenError::throw(
-                    format!(
-                        "Type `{}` is not supported for `len()` function!",
-                        &compiled_arg.0
-                    ),
-                    ErrorType::NotSupported,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
+            argtype if Compiler::__is_list_type(argtype) => {
+                let length = self.build_list_len(compiled_arg.1.into_pointer_value(), line)?;
+                Ok((String::from("int64"), length.into()))
+            }
+            argtype if Compiler::__is_map_type(argtype) => {
+                let length = self.build_map_len(compiled_arg.1.into_pointer_value(), line)?;
+                Ok((String::from("int64"), length.into()))
+            }
+            "str" => {
+                // length is stored in string header
+                let length = self.__str_len(compiled_arg.1.into_pointer_value());
+                Ok((String::from("int64"), length.into()))
             }
+            _ => Err(self.error(
+                format!(
+                    "Type `{}` is not supported for `len()` function!",
+                    &compiled_arg.0
+                ),
+                ErrorType::NotSupported,
+                line,
+            )),
         }
     }
 
@@ -542,25 +578,27 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `len()` requires only 1 argument, but {} found!",
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
         let compiled_type = match arguments[0].clone() {
-            Expressions::Value(Value::Keyword(arg_type)) => arg_type,
+            Expressions::Value(Value::Keyword(arg_type), _) => arg_type,
+            Expressions::Value(Value::Identifier(arg_type), _)
+                if self.get_user_type(&arg_type).is_some() =>
+            {
+                arg_type
+            }
             _ => {
-                self.compile_expression(arguments[0].clone(), line, function, None)
+                self.compile_expression(arguments[0].clone(), line, function, None)?
                     .0
             }
         };
@@ -587,23 +625,39 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             };
         }
 
-        let size = crate::TYPE_SIZES
-            .get(&raw_type.as_str())
-            .unwrap_or_else(|| {
-                GenError::throw(
-                    format!("Unsupported for size type found: `{}`", raw_type),
-                    ErrorType::NotSupported,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
+        let aggregate_type = match self.get_user_type(&raw_type) {
+            Some(user_type) => Some(user_type),
+            None if Compiler::__is_slice_type(&raw_type) => {
+                Some(self.get_basic_type(&raw_type, line)?.into_struct_type())
+            }
+            None => None,
+        };
+
+        if let Some(user_type) = aggregate_type {
+            // structure layout (with paddings) is known only to target
+            let size = user_type.size_of().ok_or_else(|| {
+                self.error(
+                    format!("Unable to get size of `{}` type!", raw_type),
+                    ErrorType::BuildError,
                     line,
-                );
-                std::process::exit(1);
-            })
-            * type_multiplier;
+                )
+            })?;
+
+            let multiplier = self.context.i64_type().const_int(type_multiplier, false);
+            return Ok((String::from("int64"), size.const_mul(multiplier).into()));
+        }
+
+        let size = crate::TYPE_SIZES.get(&raw_type.as_str()).ok_or_else(|| {
+            self.error(
+                format!("Unsupported for size type found: `{}`", raw_type),
+                ErrorType::NotSupported,
+                line,
+            )
+        })? * type_multiplier;
 
         let constant = self.context.i64_type().const_int(size, false);
 
-        (String::from("int64"), constant.into())
+        Ok((String::from("int64"), constant.into()))
     }
 
     // conversion
@@ -614,31 +668,28 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         #[allow(non_snake_case)]
         let (TARGET_TYPE, TARGET_BASIC_TYPE, TARGET_TYPE_FORMAT) =
             ("int8", self.context.i8_type(), "%d");
 
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `to_{}()` requires only 1 argument, but {} found!",
                     TARGET_TYPE,
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None);
+        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
 
         // checks
         match compiled_arg.0.as_str() {
-            ctype if ctype == TARGET_TYPE => return compiled_arg,
+            ctype if ctype == TARGET_TYPE => return Ok(compiled_arg),
             "str" => {
                 let sscanf_fn = self.__c_sscanf();
                 let format_string = self
@@ -664,17 +715,32 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     .build_load(TARGET_BASIC_TYPE, result_alloca, "")
                     .unwrap();
 
-                return (TARGET_TYPE.to_string(), result_value);
+                return Ok((TARGET_TYPE.to_string(), result_value));
+            }
+            "float32" | "float64" => {
+                let converted_value = self
+                    .builder
+                    .build_float_to_signed_int(
+                        compiled_arg.1.into_float_value(),
+                        TARGET_BASIC_TYPE,
+                        format!("to_{}_fptosi", TARGET_TYPE).as_str(),
+                    )
+                    .map_err(|_| {
+                        self.error(
+                            "Unable to convert float value!",
+                            ErrorType::BuildError,
+                            line,
+                        )
+                    })?;
+
+                return Ok((TARGET_TYPE.to_string(), converted_value.into()));
             }
             _ if !compiled_arg.0.contains("int") => {
-                GenError::throw(
+                return Err(self.error(
                     format!("Unable to convert non-int type to `{}`", TARGET_TYPE),
                     ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     line,
-                );
-                std::process::exit(1);
+                ));
             }
             _ => {}
         }
@@ -691,16 +757,13 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     TARGET_BASIC_TYPE,
                     format!("to_{}_trunc", TARGET_TYPE).as_str(),
                 )
-                .unwrap_or_else(|_| {
-                    GenError::throw(
+                .map_err(|_| {
+                    self.error(
                         "Unable to truncate integer value!",
                         ErrorType::BuildError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
-                });
+                    )
+                })?;
 
             truncated
         } else {
@@ -712,21 +775,18 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     TARGET_BASIC_TYPE,
                     format!("to_{}_sext", TARGET_TYPE).as_str(),
                 )
-                .unwrap_or_else(|_| {
-                    GenError::throw(
+                .map_err(|_| {
+                    self.error(
                         "Unable to extend integer value!",
                         ErrorType::BuildError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
-                });
+                    )
+                })?;
 
             extended
         };
 
-        (String::from(TARGET_TYPE), converted_value.into())
+        Ok((String::from(TARGET_TYPE), converted_value.into()))
     }
 
     fn build_to_int16_call(
@@ -734,31 +794,28 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         #[allow(non_snake_case)]
         let (TARGET_TYPE, TARGET_BASIC_TYPE, TARGET_TYPE_FORMAT) =
             ("int16", self.context.i16_type(), "%d");
 
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `to_{}()` requires only 1 argument, but {} found!",
                     TARGET_TYPE,
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None);
+        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
 
         // checks
         match compiled_arg.0.as_str() {
-            ctype if ctype == TARGET_TYPE => return compiled_arg,
+            ctype if ctype == TARGET_TYPE => return Ok(compiled_arg),
             "str" => {
                 let sscanf_fn = self.__c_sscanf();
                 let format_string = self
@@ -784,18 +841,33 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     .build_load(TARGET_BASIC_TYPE, result_alloca, "")
                     .unwrap();
 
-                return (TARGET_TYPE.to_string(), result_value);
+                return Ok((TARGET_TYPE.to_string(), result_value));
             }
 
+            "float32" | "float64" => {
+                let converted_value = self
+                    .builder
+                    .build_float_to_signed_int(
+                        compiled_arg.1.into_float_value(),
+                        TARGET_BASIC_TYPE,
+                        format!("to_{}_fptosi", TARGET_TYPE).as_str(),
+                    )
+                    .map_err(|_| {
+                        self.error(
+                            "Unable to convert float value!",
+                            ErrorType::BuildError,
+                            line,
+                        )
+                    })?;
+
+                return Ok((TARGET_TYPE.to_string(), converted_value.into()));
+            }
             _ if !compiled_arg.0.contains("int") => {
-                GenError::throw(
+                return Err(self.error(
                     format!("Unable to convert non-int type to `{}`", TARGET_TYPE),
                     ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     line,
-                );
-                std::process::exit(1);
+                ));
             }
             _ => {}
         }
@@ -812,16 +884,13 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     TARGET_BASIC_TYPE,
                     format!("to_{}_trunc", TARGET_TYPE).as_str(),
                 )
-                .unwrap_or_else(|_| {
-                    GenError::throw(
+                .map_err(|_| {
+                    self.error(
                         "Unable to truncate integer value!",
                         ErrorType::BuildError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
-                });
+                    )
+                })?;
 
             truncated
         } else {
@@ -833,21 +902,18 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     TARGET_BASIC_TYPE,
                     format!("to_{}_sext", TARGET_TYPE).as_str(),
                 )
-                .unwrap_or_else(|_| {
-                    GenError::throw(
+                .map_err(|_| {
+                    self.error(
                         "Unable to extend integer value!",
                         ErrorType::BuildError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
-                });
+                    )
+                })?;
 
             extended
         };
 
-        (String::from(TARGET_TYPE), converted_value.into())
+        Ok((String::from(TARGET_TYPE), converted_value.into()))
     }
 
     fn build_to_int32_call(
@@ -855,31 +921,28 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         #[allow(non_snake_case)]
         let (TARGET_TYPE, TARGET_BASIC_TYPE, TARGET_TYPE_FORMAT) =
             ("int32", self.context.i32_type(), "%d");
 
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `to_{}()` requires only 1 argument, but {} found!",
                     TARGET_TYPE,
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None);
+        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
 
         // checks
         match compiled_arg.0.as_str() {
-            ctype if ctype == TARGET_TYPE => return compiled_arg,
+            ctype if ctype == TARGET_TYPE => return Ok(compiled_arg),
             "str" => {
                 let sscanf_fn = self.__c_sscanf();
                 let format_string = self
@@ -905,18 +968,33 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     .build_load(TARGET_BASIC_TYPE, result_alloca, "")
                     .unwrap();
 
-                return (TARGET_TYPE.to_string(), result_value);
+                return Ok((TARGET_TYPE.to_string(), result_value));
             }
 
+            "float32" | "float64" => {
+                let converted_value = self
+                    .builder
+                    .build_float_to_signed_int(
+                        compiled_arg.1.into_float_value(),
+                        TARGET_BASIC_TYPE,
+                        format!("to_{}_fptosi", TARGET_TYPE).as_str(),
+                    )
+                    .map_err(|_| {
+                        self.error(
+                            "Unable to convert float value!",
+                            ErrorType::BuildError,
+                            line,
+                        )
+                    })?;
+
+                return Ok((TARGET_TYPE.to_string(), converted_value.into()));
+            }
             _ if !compiled_arg.0.contains("int") => {
-                GenError::throw(
+                return Err(self.error(
                     format!("Unable to convert non-int type to `{}`", TARGET_TYPE),
                     ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     line,
-                );
-                std::process::exit(1);
+                ));
             }
             _ => {}
         }
@@ -933,16 +1011,13 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     TARGET_BASIC_TYPE,
                     format!("to_{}_trunc", TARGET_TYPE).as_str(),
                 )
-                .unwrap_or_else(|_| {
-                    GenError::throw(
+                .map_err(|_| {
+                    self.error(
                         "Unable to truncate integer value!",
                         ErrorType::BuildError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
-                });
+                    )
+                })?;
 
             truncated
         } else {
@@ -954,21 +1029,18 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     TARGET_BASIC_TYPE,
                     format!("to_{}_sext", TARGET_TYPE).as_str(),
                 )
-                .unwrap_or_else(|_| {
-                    GenError::throw(
+                .map_err(|_| {
+                    self.error(
                         "Unable to extend integer value!",
                         ErrorType::BuildError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
-                });
+                    )
+                })?;
 
             extended
         };
 
-        (String::from(TARGET_TYPE), converted_value.into())
+        Ok((String::from(TARGET_TYPE), converted_value.into()))
     }
 
     fn build_to_int64_call(
@@ -976,31 +1048,28 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         #[allow(non_snake_case)]
         let (TARGET_TYPE, TARGET_BASIC_TYPE, TARGET_TYPE_FORMAT) =
             ("int64", self.context.i64_type(), "%ld");
 
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `to_{}()` requires only 1 argument, but {} found!",
                     TARGET_TYPE,
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None);
+        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
 
         // checks
         match compiled_arg.0.as_str() {
-            ctype if ctype == TARGET_TYPE => return compiled_arg,
+            ctype if ctype == TARGET_TYPE => return Ok(compiled_arg),
             "str" => {
                 let sscanf_fn = self.__c_sscanf();
                 let format_string = self
@@ -1026,18 +1095,33 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     .build_load(TARGET_BASIC_TYPE, result_alloca, "")
                     .unwrap();
 
-                return (TARGET_TYPE.to_string(), result_value);
+                return Ok((TARGET_TYPE.to_string(), result_value));
             }
 
+            "float32" | "float64" => {
+                let converted_value = self
+                    .builder
+                    .build_float_to_signed_int(
+                        compiled_arg.1.into_float_value(),
+                        TARGET_BASIC_TYPE,
+                        format!("to_{}_fptosi", TARGET_TYPE).as_str(),
+                    )
+                    .map_err(|_| {
+                        self.error(
+                            "Unable to convert float value!",
+                            ErrorType::BuildError,
+                            line,
+                        )
+                    })?;
+
+                return Ok((TARGET_TYPE.to_string(), converted_value.into()));
+            }
             _ if !compiled_arg.0.contains("int") => {
-                GenError::throw(
+                return Err(self.error(
                     format!("Unable to convert non-int type to `{}`", TARGET_TYPE),
                     ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     line,
-                );
-                std::process::exit(1);
+                ));
             }
             _ => {}
         }
@@ -1054,16 +1138,13 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     TARGET_BASIC_TYPE,
                     format!("to_{}_trunc", TARGET_TYPE).as_str(),
                 )
-                .unwrap_or_else(|_| {
-                    GenError::throw(
+                .map_err(|_| {
+                    self.error(
                         "Unable to truncate integer value!",
                         ErrorType::BuildError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
-                });
+                    )
+                })?;
 
             truncated
         } else {
@@ -1075,122 +1156,210 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
                     TARGET_BASIC_TYPE,
                     format!("to_{}_sext", TARGET_TYPE).as_str(),
                 )
-                .unwrap_or_else(|_| {
-                    GenError::throw(
+                .map_err(|_| {
+                    self.error(
                         "Unable to extend integer value!",
                         ErrorType::BuildError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
-                });
+                    )
+                })?;
 
             extended
         };
 
-        (String::from(TARGET_TYPE), converted_value.into())
+        Ok((String::from(TARGET_TYPE), converted_value.into()))
     }
 
-    // str
+    // float
 
-    fn build_to_str_call(
+    fn build_to_float32_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        #[allow(non_snake_case)]
+        let (TARGET_TYPE, TARGET_BASIC_TYPE, TARGET_TYPE_FORMAT) =
+            ("float32", self.context.f32_type(), "%f");
+
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
-                    "Function `to_str()` requires only 1 argument, but {} found!",
+                    "Function `to_{}()` requires only 1 argument, but {} found!",
+                    TARGET_TYPE,
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None);
-        let arg_fmt = Compiler::__type_fmt(&compiled_arg.0);
-        let arg_fmt_ptr = self
-            .builder
-            .build_global_string_ptr(&arg_fmt, "_to_str_fmt")
-            .unwrap_or_else(|_| {
-                GenError::throw(
-                    "Unable to allocate format pointer!",
-                    ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
-            })
-            .as_basic_value_enum();
+        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
 
-        let data_ptr_size = self.context.i8_type().const_int(10, false);
-        let data_ptr = self
-            .builder
-            .build_array_alloca(
-                self.context.ptr_type(AddressSpace::default()),
-                data_ptr_size,
-                "_to_str_alloca",
-            )
-            .unwrap_or_else(|_| {
-                GenError::throw(
-                    "Unable to create array alloca!",
-                    ErrorType::MemoryError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
-            });
+        // checks
+        match compiled_arg.0.as_str() {
+            ctype if ctype == TARGET_TYPE => return Ok(compiled_arg),
+            "str" => {
+                let sscanf_fn = self.__c_sscanf();
+                let format_string = self
+                    .builder
+                    .build_global_string_ptr(TARGET_TYPE_FORMAT, TARGET_TYPE)
+                    .unwrap()
+                    .as_basic_value_enum();
 
-        let sprintf_fn = self.__c_sprintf();
+                let result_alloca = self.builder.build_alloca(TARGET_BASIC_TYPE, "").unwrap();
 
-        let _ = self
-            .builder
-            .build_call(
-                sprintf_fn,
-                &[data_ptr.into(), arg_fmt_ptr.into(), compiled_arg.1.into()],
-                "_to_string_call",
-            )
-            .unwrap_or_else(|_| {
-                GenError::throw(
-                    "Call `to_str()` failed!",
-                    ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
+                let _ = self.builder.build_call(
+                    sscanf_fn,
+                    &[
+                        compiled_arg.1.into(),
+                        format_string.into(),
+                        result_alloca.into(),
+                    ],
+                    "",
                 );
-                std::process::exit(1);
-            });
 
-        ("str".to_string(), data_ptr.into())
+                let result_value = self
+                    .builder
+                    .build_load(TARGET_BASIC_TYPE, result_alloca, "")
+                    .unwrap();
+
+                return Ok((TARGET_TYPE.to_string(), result_value));
+            }
+            _ => {}
+        }
+
+        // ints are converted with `sitofp`, floats are extended/truncated
+        let converted_value = self.build_float_cast(compiled_arg, TARGET_TYPE, line)?;
+
+        Ok((String::from(TARGET_TYPE), converted_value.into()))
     }
 
-    fn build_malloc_call(
+    fn build_to_float64_call(
         &mut self,
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        #[allow(non_snake_case)]
+        let (TARGET_TYPE, TARGET_BASIC_TYPE, TARGET_TYPE_FORMAT) =
+            ("float64", self.context.f64_type(), "%lf");
+
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
-                    "Function `malloc` requires 1 argument, but {} found!",
+                    "Function `to_{}()` requires only 1 argument, but {} found!",
+                    TARGET_TYPE,
+                    arguments.len()
+                ),
+                ErrorType::NotExpected,
+                line,
+            ));
+        }
+
+        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
+
+        // checks
+        match compiled_arg.0.as_str() {
+            ctype if ctype == TARGET_TYPE => return Ok(compiled_arg),
+            "str" => {
+                let sscanf_fn = self.__c_sscanf();
+                let format_string = self
+                    .builder
+                    .build_global_string_ptr(TARGET_TYPE_FORMAT, TARGET_TYPE)
+                    .unwrap()
+                    .as_basic_value_enum();
+
+                let result_alloca = self.builder.build_alloca(TARGET_BASIC_TYPE, "").unwrap();
+
+                let _ = self.builder.build_call(
+                    sscanf_fn,
+                    &[
+                        compiled_arg.1.into(),
+                        format_string.into(),
+                        result_alloca.into(),
+                    ],
+                    "",
+                );
+
+                let result_value = self
+                    .builder
+                    .build_load(TARGET_BASIC_TYPE, result_alloca, "")
+                    .unwrap();
+
+                return Ok((TARGET_TYPE.to_string(), result_value));
+            }
+            _ => {}
+        }
+
+        // ints are converted with `sitofp`, floats are extended/truncated
+        let converted_value = self.build_float_cast(compiled_arg, TARGET_TYPE, line)?;
+
+        Ok((String::from(TARGET_TYPE), converted_value.into()))
+    }
+
+    // str
+
+    fn build_to_str_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        if arguments.len() != 1 {
+            return Err(self.error(
+                format!(
+                    "Function `to_str()` requires only 1 argument, but {} found!",
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
+        }
+
+        let mut compiled_arg =
+            self.compile_expression(arguments[0].clone(), line, function, None)?;
+        let arg_fmt = Compiler::__type_fmt(&compiled_arg.0);
+
+        if compiled_arg.0 == "float32" {
+            // varargs floats are always passed as `double`
+            compiled_arg.1 = self
+                .build_float_cast(compiled_arg.clone(), "float64", line)?
+                .into();
+        }
+        let arg_fmt_ptr = self
+            .builder
+            .build_global_string_ptr(&arg_fmt, "_to_str_fmt")
+            .map_err(|_| {
+                self.error(
+                    "Unable to allocate format pointer!",
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?
+            .as_basic_value_enum();
+
+        let data_ptr =
+            self.build_str_format(arg_fmt_ptr.into_pointer_value(), compiled_arg.1, line)?;
+
+        Ok(("str".to_string(), data_ptr.into()))
+    }
+
+    fn build_malloc_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        if arguments.len() != 1 {
+            return Err(self.error(
+                format!(
+                    "Function `malloc` requires 1 argument, but {} found!",
+                    arguments.len()
+                ),
+                ErrorType::NotExpected,
+                line,
+            ));
         }
 
         let compiled_size = self.compile_expression(
@@ -1198,18 +1367,15 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             line,
             function,
             Some(String::from("int64")),
-        );
+        )?;
 
         if !compiled_size.0.starts_with("int") {
             dbg!(arguments);
-            GenError::throw(
+            return Err(self.error(
                 "Non-integer size for allocation found!",
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
         let malloc_fn = self.__c_malloc();
@@ -1228,20 +1394,17 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             .unwrap_or(String::from("void*"));
 
         if !Compiler::__is_ptr_type(&output_type) {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Non-pointer type `{}` requested for `malloc()`",
                     output_type
                 ),
                 ErrorType::TypeError,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        (output_type, result)
+        Ok((output_type, result))
     }
 
     fn build_free_call(
@@ -1249,32 +1412,50 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `free` requires 1 arguments, but {} found!",
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None);
+        let compiled_arg = self.compile_expression(arguments[0].clone(), line, function, None)?;
+
+        // list and map free their elements and header
+        if Compiler::__is_list_type(&compiled_arg.0) || Compiler::__is_map_type(&compiled_arg.0) {
+            if Compiler::__is_map_type(&compiled_arg.0) {
+                self.build_map_free(compiled_arg.1.into_pointer_value(), line)?;
+            } else {
+                self.build_list_free(compiled_arg.1.into_pointer_value(), line)?;
+            }
+
+            return Ok((
+                String::from("void"),
+                self.context.bool_type().const_zero().into(),
+            ));
+        }
+
+        // strings release their heap buffer, literals are skipped
+        if compiled_arg.0 == "str" {
+            self.build_str_free(compiled_arg.1.into_pointer_value(), line)?;
+
+            return Ok((
+                String::from("void"),
+                self.context.bool_type().const_zero().into(),
+            ));
+        }
 
         if !Compiler::__is_ptr_type(&compiled_arg.0) {
-            GenError::throw(
+            return Err(self.error(
                 "Function `free` requires pointer as an argument!",
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
         let free_fn = self.__c_free();
@@ -1283,10 +1464,190 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             .build_call(free_fn, &[compiled_arg.1.into()], "")
             .unwrap();
 
-        (
+        Ok((
             String::from("void"),
             self.context.bool_type().const_zero().into(),
-        )
+        ))
+    }
+
+    fn build_collection_call(
+        &mut self,
+        function_name: String,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        if arguments.is_empty() {
+            return Err(self.error(
+                format!(
+                    "Function `{}()` requires list or map as the first argument!",
+                    function_name
+                ),
+                ErrorType::NotExpected,
+                line,
+            ));
+        }
+
+        let collection = self.compile_expression(arguments[0].clone(), line, function, None)?;
+        let arguments = arguments[1..].to_vec();
+
+        match collection.0.as_str() {
+            list_type if Compiler::__is_list_type(list_type) => {
+                self.build_list_call(function_name, collection, arguments, line, function)
+            }
+            map_type if Compiler::__is_map_type(map_type) => {
+                self.build_map_call(function_name, collection, arguments, line, function)
+            }
+            _ => Err(self.error(
+                format!(
+                    "Function `{}()` requires list or map as the first argument, but found `{}`!",
+                    function_name, collection.0
+                ),
+                ErrorType::NotExpected,
+                line,
+            )),
+        }
+    }
+
+    fn build_string_call(
+        &mut self,
+        function_name: String,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        // `int` means any integer type, it's casted to `int64`
+        let (arguments_types, return_type): (&[&str], &str) = match function_name.as_str() {
+            "substr" => (&["str", "int", "int"], "str"),
+            "find" => (&["str", "str"], "int64"),
+            "contains" | "starts_with" | "ends_with" => (&["str", "str"], "bool"),
+            "split" => (&["str", "str"], "list<str>"),
+            "replace" => (&["str", "str", "str"], "str"),
+            "trim" | "to_upper" | "to_lower" => (&["str"], "str"),
+            "repeat" => (&["str", "int"], "str"),
+            "join" => (&["list<str>", "str"], "str"),
+            _ => {
+                return Err(self.error(
+                    format!("Function `{}()` is not defined!", function_name),
+                    ErrorType::NotDefined,
+                    line,
+                ));
+            }
+        };
+
+        if arguments.len() != arguments_types.len() {
+            return Err(self.arguments_count_error(
+                &function_name,
+                arguments_types.len(),
+                arguments.len(),
+                line,
+            ));
+        }
+
+        let mut values = Vec::new();
+
+        for (index, (argument, expected_type)) in
+            arguments.into_iter().zip(arguments_types).enumerate()
+        {
+            let value = if *expected_type == "int" {
+                self.compile_expression(argument, line, function, None)?
+            } else {
+                let value = self.compile_expression(
+                    argument,
+                    line,
+                    function,
+                    Some(expected_type.to_string()),
+                )?;
+                self.coerce_value(value, expected_type, line)?
+            };
+
+            let is_valid = match *expected_type {
+                "int" => value.0.starts_with("int"),
+                _ => value.0 == *expected_type,
+            };
+
+            if !is_valid {
+                return Err(self.error(
+                    format!(
+                        "Function `{}()` expected `{}` as argument {}, but found `{}`!",
+                        function_name,
+                        expected_type,
+                        index + 1,
+                        value.0
+                    ),
+                    ErrorType::TypeError,
+                    line,
+                ));
+            }
+
+            if *expected_type == "int" {
+                let casted_value = self
+                    .builder
+                    .build_int_cast_sign_flag(
+                        value.1.into_int_value(),
+                        self.context.i64_type(),
+                        true,
+                        "",
+                    )
+                    .map_err(|_| {
+                        self.error("Unable to cast integer!", ErrorType::BuildError, line)
+                    })?;
+
+                values.push(casted_value.into());
+            } else {
+                values.push(value.1);
+            }
+        }
+
+        let string = values[0].into_pointer_value();
+        let value: BasicValueEnum<'ctx> = match function_name.as_str() {
+            "substr" => self
+                .build_str_substr(
+                    string,
+                    values[1].into_int_value(),
+                    values[2].into_int_value(),
+                    line,
+                )?
+                .into(),
+            "find" => self
+                .build_str_find(string, values[1].into_pointer_value(), line)?
+                .into(),
+            "contains" => self
+                .build_str_contains(string, values[1].into_pointer_value(), line)?
+                .into(),
+            "starts_with" => self
+                .build_str_starts_with(string, values[1].into_pointer_value(), line)?
+                .into(),
+            "ends_with" => self
+                .build_str_ends_with(string, values[1].into_pointer_value(), line)?
+                .into(),
+            "split" => return self.build_str_split(string, values[1].into_pointer_value(), line),
+            "replace" => {
+                let replace_fn = self.__runtime_str_replace();
+                let arguments: Vec<BasicMetadataValueEnum> =
+                    values.iter().map(|value| (*value).into()).collect();
+
+                self.build_str_runtime_call(replace_fn, &arguments, line)?
+            }
+            "trim" => {
+                let trim_fn = self.__runtime_str_trim();
+                self.build_str_runtime_call(trim_fn, &[string.into()], line)?
+            }
+            "to_upper" | "to_lower" => {
+                let case_fn = self.__runtime_str_case(function_name == "to_upper");
+                self.build_str_runtime_call(case_fn, &[string.into()], line)?
+            }
+            "repeat" => {
+                let repeat_fn = self.__runtime_str_repeat();
+                self.build_str_runtime_call(repeat_fn, &[string.into(), values[1].into()], line)?
+            }
+            "join" => self
+                .build_str_join(string, values[1].into_pointer_value(), line)?
+                .into(),
+            _ => unreachable!(),
+        };
+
+        Ok((return_type.to_string(), value))
     }
 
     fn build_realloc_call(
@@ -1294,46 +1655,37 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() != 2 {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `realloc` requires 2 arguments, but {} found!",
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let argument_ptr = self.compile_expression(arguments[0].clone(), line, function, None);
+        let argument_ptr = self.compile_expression(arguments[0].clone(), line, function, None)?;
 
         if !Compiler::__is_ptr_type(&argument_ptr.0) {
-            GenError::throw(
+            return Err(self.error(
                 "Function `realloc` requires pointer as first argument!",
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
-        let compiled_size = self.compile_expression(arguments[1].clone(), line, function, None);
+        let compiled_size = self.compile_expression(arguments[1].clone(), line, function, None)?;
 
         if !compiled_size.0.starts_with("int") {
             dbg!(arguments);
-            GenError::throw(
+            return Err(self.error(
                 "Non-integer size for allocation found!",
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
         let realloc_fn = self.__c_realloc();
@@ -1349,7 +1701,7 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             .left()
             .unwrap();
 
-        (argument_ptr.0, result_ptr)
+        Ok((argument_ptr.0, result_ptr))
     }
 
     fn build_file_call(
@@ -1357,31 +1709,25 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             arguments: Vec<Expressions>,
             line: usize,
             function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() != 2 {
-            GenError::throw(
+            return Err(self.error(
                 format!("Function `file` requires 2 arguments, but {} found", arguments.len()),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
-                line
-            );
-            std::process::exit(1);
+                line,
+            ));
         }
 
-        let path_to_file = self.compile_expression(arguments[0].clone(), line, function, None);
-        let open_mode = self.compile_expression(arguments[1].clone(), line, function, None);
+        let path_to_file = self.compile_expression(arguments[0].clone(), line, function, None)?;
+        let open_mode = self.compile_expression(arguments[1].clone(), line, function, None)?;
 
         if path_to_file.0 != String::from("str")
-        && open_mode.0 != String::from("str") {
-            GenError::throw(
+        || open_mode.0 != String::from("str") {
+            return Err(self.error(
                 "Wrong arguments found! Function `file` takes next arguments: file(str path, str mode)",
                 ErrorType::TypeError,
-                self.module_name.clone(),
-                self.module_source.clone(),
-                line
-            );
-            std::process::exit(1);
+                line,
+            ));
         }
 
         let fopen_fn = self.__c_fopen();
@@ -1400,7 +1746,7 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             .left()
             .unwrap();
 
-        (String::from("FILE*"), call_result)
+        Ok((String::from("FILE*"), call_result))
     }
 
     fn build_close_call(
@@ -1408,29 +1754,23 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             arguments: Vec<Expressions>,
             line: usize,
             function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() != 1 {
-            GenError::throw(
+            return Err(self.error(
                 format!("Function `close` requires 1 argument, but {} found", arguments.len()),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
-                line
-            );
-            std::process::exit(1);
+                line,
+            ));
         }
 
-        let file_ptr = self.compile_expression(arguments[0].clone(), line, function, None);
+        let file_ptr = self.compile_expression(arguments[0].clone(), line, function, None)?;
 
         if file_ptr.0 != String::from("FILE*") {
-            GenError::throw(
+            return Err(self.error(
                 "Function `close` requires file pointer as an argument!",
                 ErrorType::TypeError,
-                self.module_name.clone(),
-                self.module_source.clone(),
-                line
-            );
-            std::process::exit(1);
+                line,
+            ));
         }
 
         let fclose_fn = self.__c_fclose();
@@ -1445,7 +1785,10 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             )
             .unwrap();
 
-        (String::from("void"), self.context.bool_type().const_zero().into())
+        Ok((
+            String::from("void"),
+            self.context.bool_type().const_zero().into(),
+        ))
     }
 
     fn build_write_call(
@@ -1453,41 +1796,32 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             arguments: Vec<Expressions>,
             line: usize,
             function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
         if arguments.len() != 2 {
-            GenError::throw(
+            return Err(self.error(
                 format!("Function `write` requires 2 arguments, but {} found", arguments.len()),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
-                line
-            );
-            std::process::exit(1);
+                line,
+            ));
         }
 
-        let file_ptr = self.compile_expression(arguments[0].clone(), line, function, None);
-        let string = self.compile_expression(arguments[1].clone(), line, function, None);
+        let file_ptr = self.compile_expression(arguments[0].clone(), line, function, None)?;
+        let string = self.compile_expression(arguments[1].clone(), line, function, None)?;
 
         if file_ptr.0 != String::from("FILE*") {
-            GenError::throw(
+            return Err(self.error(
                 "Function `write` requires file pointer!",
                 ErrorType::TypeError,
-                self.module_name.clone(),
-                self.module_source.clone(),
-                line
-            );
-            std::process::exit(1);
+                line,
+            ));
         }
 
         if string.0 != String::from("str") {
-            GenError::throw(
+            return Err(self.error(
                 format!("Type `str` expected, but found `{}`", file_ptr.1),
                 ErrorType::TypeError,
-                self.module_name.clone(),
-                self.module_source.clone(),
-                line
-            );
-            std::process::exit(1);
+                line,
+            ));
         }
 
         let fprintf_fn = self.__c_fprintf();
@@ -1504,6 +1838,395 @@ impl<'ctx> BuiltIn<'ctx> for Compiler<'ctx> {
             )
             .unwrap();
 
-        ("void".into(), self.context.bool_type().const_zero().into())
+        Ok(("void".into(), self.context.bool_type().const_zero().into()))
+    }
+
+    fn build_file_argument(
+        &mut self,
+        function_name: &str,
+        arguments: &[Expressions],
+        arguments_count: usize,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        if arguments.len() != arguments_count {
+            return Err(self.arguments_count_error(
+                function_name,
+                arguments_count,
+                arguments.len(),
+                line,
+            ));
+        }
+
+        let file_ptr = self.compile_expression(arguments[0].clone(), line, function, None)?;
+
+        if file_ptr.0 != "FILE*" {
+            return Err(self.error(
+                format!(
+                    "Function `{}` requires file pointer as the first argument!",
+                    function_name
+                ),
+                ErrorType::TypeError,
+                line,
+            ));
+        }
+
+        Ok(file_ptr.1.into_pointer_value())
+    }
+
+    fn build_path_argument(
+        &mut self,
+        function_name: &str,
+        arguments: &[Expressions],
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        if arguments.len() != 1 {
+            return Err(self.error(
+                format!(
+                    "Function `{}` requires 1 argument, but {} found",
+                    function_name,
+                    arguments.len()
+                ),
+                ErrorType::NotExpected,
+                line,
+            ));
+        }
+
+        let path = self.compile_expression(arguments[0].clone(), line, function, None)?;
+
+        if path.0 != "str" {
+            return Err(self.error(
+                format!(
+                    "Function `{}` requires path string, but found `{}`!",
+                    function_name, path.0
+                ),
+                ErrorType::TypeError,
+                line,
+            ));
+        }
+
+        Ok(path.1.into_pointer_value())
+    }
+
+    fn build_read_char_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let file_ptr = self.build_file_argument("read_char", &arguments, 1, line, function)?;
+        let fgetc_fn = self.__c_fgetc();
+
+        let character = self
+            .builder
+            .build_call(fgetc_fn, &[file_ptr.into()], "")
+            .map_err(|_| self.error("Call `read_char()` failed!", ErrorType::BuildError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+
+        // end of file is returned as `\0`, use `eof()` to tell it apart
+        let is_eof = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::SLT,
+                character,
+                character.get_type().const_zero(),
+                "",
+            )
+            .unwrap();
+        let character = self
+            .builder
+            .build_int_truncate(character, self.context.i8_type(), "")
+            .unwrap();
+        let character = self
+            .builder
+            .build_select(
+                is_eof,
+                self.context.i8_type().const_zero(),
+                character,
+                "read_char",
+            )
+            .unwrap();
+
+        Ok(("char".to_string(), character))
+    }
+
+    fn build_read_line_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let file_ptr = self.build_file_argument("read_line", &arguments, 1, line, function)?;
+        let result = self.build_str_read_line(file_ptr, line)?;
+
+        Ok(("str".to_string(), result.into()))
+    }
+
+    fn build_read_all_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let path = self.build_path_argument("read_all", &arguments, line, function)?;
+        let result = self.build_str_read_file(path, line)?;
+
+        Ok(("str".to_string(), result.into()))
+    }
+
+    fn build_eof_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let file_ptr = self.build_file_argument("eof", &arguments, 1, line, function)?;
+        let fgetc_fn = self.__c_fgetc();
+        let ungetc_fn = self.__c_ungetc();
+
+        // `feof` is set only after failed read, so next character is peeked instead
+        // (returning EOF back with `ungetc` does nothing)
+        let character = self
+            .builder
+            .build_call(fgetc_fn, &[file_ptr.into()], "")
+            .map_err(|_| self.error("Call `eof()` failed!", ErrorType::BuildError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+
+        let _ = self
+            .builder
+            .build_call(ungetc_fn, &[character.into(), file_ptr.into()], "")
+            .map_err(|_| self.error("Call `eof()` failed!", ErrorType::BuildError, line))?;
+
+        let is_eof = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::SLT,
+                character,
+                character.get_type().const_zero(),
+                "eof",
+            )
+            .unwrap();
+
+        Ok(("bool".to_string(), is_eof.into()))
+    }
+
+    fn build_seek_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let file_ptr = self.build_file_argument("seek", &arguments, 2, line, function)?;
+        let position = self.compile_expression(arguments[1].clone(), line, function, None)?;
+
+        if !position.0.starts_with("int") {
+            return Err(self.error(
+                format!(
+                    "Function `seek` requires integer position, but found `{}`!",
+                    position.0
+                ),
+                ErrorType::TypeError,
+                line,
+            ));
+        }
+
+        let position = self
+            .builder
+            .build_int_cast_sign_flag(
+                position.1.into_int_value(),
+                self.context.i64_type(),
+                true,
+                "",
+            )
+            .unwrap();
+
+        // position is counted from the start of file (`SEEK_SET`)
+        let fseek_fn = self.__c_fseek();
+        let seek_result = self
+            .builder
+            .build_call(
+                fseek_fn,
+                &[
+                    file_ptr.into(),
+                    position.into(),
+                    self.context.i32_type().const_zero().into(),
+                ],
+                "",
+            )
+            .map_err(|_| self.error("Call `seek()` failed!", ErrorType::BuildError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+
+        let is_success = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                seek_result,
+                self.context.i32_type().const_zero(),
+                "seek",
+            )
+            .unwrap();
+
+        Ok(("bool".to_string(), is_success.into()))
+    }
+
+    fn build_tell_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let file_ptr = self.build_file_argument("tell", &arguments, 1, line, function)?;
+        let ftell_fn = self.__c_ftell();
+
+        let position = self
+            .builder
+            .build_call(ftell_fn, &[file_ptr.into()], "tell")
+            .map_err(|_| self.error("Call `tell()` failed!", ErrorType::BuildError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+
+        Ok(("int64".to_string(), position))
+    }
+
+    fn build_exists_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let path = self.build_path_argument("exists", &arguments, line, function)?;
+        let access_fn = self.__c_access();
+
+        // access(path, F_OK)
+        let access_result = self
+            .builder
+            .build_call(
+                access_fn,
+                &[path.into(), self.context.i32_type().const_zero().into()],
+                "",
+            )
+            .map_err(|_| self.error("Call `exists()` failed!", ErrorType::BuildError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+
+        let is_existing = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                access_result,
+                self.context.i32_type().const_zero(),
+                "exists",
+            )
+            .unwrap();
+
+        Ok(("bool".to_string(), is_existing.into()))
+    }
+
+    fn build_args_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        if !arguments.is_empty() {
+            return Err(self.arguments_count_error("args", 0, arguments.len(), line));
+        }
+
+        // `argv` is viewed as `str[]` slice, program name is the first element
+        let (argc, argv) = self.build_arguments_load(line)?;
+        let argc = self
+            .builder
+            .build_int_cast_sign_flag(argc, self.context.i64_type(), true, "")
+            .unwrap();
+
+        let slice_type = self.get_basic_type("str[]", line)?.into_struct_type();
+        let slice = self
+            .builder
+            .build_insert_value(slice_type.get_undef(), argv, 0, "")
+            .and_then(|slice| self.builder.build_insert_value(slice, argc, 1, "args"))
+            .map_err(|_| {
+                self.error(
+                    "Unable to create `args` slice!",
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?;
+
+        Ok(("str[]".to_string(), slice.as_basic_value_enum()))
+    }
+
+    fn build_arg_count_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        if !arguments.is_empty() {
+            return Err(self.arguments_count_error("arg_count", 0, arguments.len(), line));
+        }
+
+        let (argc, _) = self.build_arguments_load(line)?;
+        Ok(("int32".to_string(), argc.into()))
+    }
+
+    fn build_exit_call(
+        &mut self,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        if arguments.len() != 1 {
+            return Err(self.arguments_count_error("exit", 1, arguments.len(), line));
+        }
+
+        let exit_code = self.compile_expression(
+            arguments[0].clone(),
+            line,
+            function,
+            Some("int32".to_string()),
+        )?;
+
+        if !exit_code.0.starts_with("int") {
+            return Err(self.error(
+                format!(
+                    "Function `exit` requires integer exit code, but found `{}`!",
+                    exit_code.0
+                ),
+                ErrorType::TypeError,
+                line,
+            ));
+        }
+
+        let exit_code = self
+            .builder
+            .build_int_cast_sign_flag(
+                exit_code.1.into_int_value(),
+                self.context.i32_type(),
+                true,
+                "",
+            )
+            .unwrap();
+
+        let exit_fn = self.__c_exit();
+        let _ = self
+            .builder
+            .build_call(exit_fn, &[exit_code.into()], "")
+            .map_err(|_| self.error("Call `exit()` failed!", ErrorType::BuildError, line))?;
+
+        Ok((
+            String::from("void"),
+            self.context.bool_type().const_zero().into(),
+        ))
     }
 }
@@ -0,0 +1,556 @@
+// Toy Programming Language | by mealet
+// https://github.com/mealet/tpl-lang
+// =========================================
+// Project licensed under the BSD-3 LICENSE.
+// Check the `LICENSE` file to more info.
+
+use inkwell::{
+    types::{BasicMetadataTypeEnum, BasicTypeEnum, StructType},
+    values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, PointerValue},
+    AddressSpace,
+};
+use tpl_parser::{expressions::Expressions, statements::Statements, value::Value};
+
+use crate::{
+    error::ErrorType, libc::Libc, variable::Variable, CompileResult, Compiler, LAMBDA_NAME,
+};
+
+// NOTE: `fn<int32(int32)>` value is a fat pointer { ptr function, ptr environment }.
+// Environment is a heap struct with copies of captured variables (null if nothing is captured),
+// lambda takes it as the first hidden parameter and uses its fields as variables.
+// Captured copies are not shared with enclosing scope, but they live between closure calls.
+
+const FUNCTION_FIELD: u32 = 0;
+const ENVIRONMENT_FIELD: u32 = 1;
+
+pub trait Closure<'ctx> {
+    fn __closure_type(&self) -> StructType<'ctx>;
+    fn __environment_type(&self, captures: &[(String, Variable<'ctx>)]) -> StructType<'ctx>;
+
+    fn build_closure(
+        &mut self,
+        arguments: Vec<(String, String)>,
+        statements: Vec<Statements>,
+        ftype: String,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_environment(
+        &mut self,
+        captures: &[(String, Variable<'ctx>)],
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn bind_environment(
+        &mut self,
+        environment: PointerValue<'ctx>,
+        captures: &[(String, Variable<'ctx>)],
+        line: usize,
+    ) -> CompileResult<()>;
+    fn build_closure_call(
+        &mut self,
+        variable_name: String,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+}
+
+impl<'ctx> Closure<'ctx> for Compiler<'ctx> {
+    fn __closure_type(&self) -> StructType<'ctx> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        self.context
+            .struct_type(&[ptr_type.into(), ptr_type.into()], false)
+    }
+
+    fn __environment_type(&self, captures: &[(String, Variable<'ctx>)]) -> StructType<'ctx> {
+        let fields: Vec<BasicTypeEnum> = captures
+            .iter()
+            .map(|(_, variable)| variable.basic_type)
+            .collect();
+
+        self.context.struct_type(&fields, false)
+    }
+
+    fn build_closure(
+        &mut self,
+        arguments: Vec<(String, String)>,
+        statements: Vec<Statements>,
+        ftype: String,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        // only variables with value can be captured
+        let captures: Vec<(String, Variable<'ctx>)> = captured_identifiers(&arguments, &statements)
+            .into_iter()
+            .filter_map(|name| {
+                self.variables
+                    .get(&name)
+                    .filter(|variable| variable.assigned)
+                    .map(|variable| (name, variable.clone()))
+            })
+            .collect();
+
+        let closure_datatype = format!(
+            "fn<{}({})>",
+            ftype,
+            arguments
+                .iter()
+                .map(|(_, datatype)| datatype.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        );
+
+        // lambda body sees only its parameters and captured variables
+        let outer_variables = std::mem::take(&mut self.variables);
+        let defined_function = self.define_user_function(
+            LAMBDA_NAME.to_string(),
+            ftype.clone(),
+            arguments,
+            statements,
+            line,
+            Some(&captures),
+        );
+        self.variables = outer_variables;
+
+        let func = defined_function?;
+        let environment = self.build_environment(&captures, line)?;
+
+        let closure_type = self.__closure_type();
+        let closure = self
+            .builder
+            .build_insert_value(
+                closure_type.get_undef(),
+                func.function_value.as_global_value().as_pointer_value(),
+                FUNCTION_FIELD,
+                "",
+            )
+            .and_then(|closure| {
+                self.builder
+                    .build_insert_value(closure, environment, ENVIRONMENT_FIELD, "closure")
+            })
+            .map_err(|_| self.error("Unable to build closure!", ErrorType::BuildError, line))?
+            .into_struct_value();
+
+        self.current_assign_function = Some(func);
+
+        Ok((closure_datatype, closure.into()))
+    }
+
+    fn build_environment(
+        &mut self,
+        captures: &[(String, Variable<'ctx>)],
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        if captures.is_empty() {
+            return Ok(self.context.ptr_type(AddressSpace::default()).const_null());
+        }
+
+        let environment_type = self.__environment_type(captures);
+        let environment_size = environment_type.size_of().ok_or_else(|| {
+            self.error(
+                "Unable to get size of closure environment!",
+                ErrorType::BuildError,
+                line,
+            )
+        })?;
+
+        let malloc_fn = self.__c_malloc();
+        let environment = self
+            .builder
+            .build_call(malloc_fn, &[environment_size.into()], "environment")
+            .map_err(|_| {
+                self.error(
+                    "Unable to allocate closure environment!",
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        // captured values are copied at the moment of closure creation
+        for (index, (name, variable)) in captures.iter().enumerate() {
+            let value = self
+                .builder
+                .build_load(variable.basic_type, variable.pointer, "")
+                .map_err(|_| {
+                    self.error(
+                        format!("Unable to capture `{}` variable!", name),
+                        ErrorType::MemoryError,
+                        line,
+                    )
+                })?;
+            let field_ptr = self
+                .builder
+                .build_struct_gep(environment_type, environment, index as u32, "")
+                .map_err(|_| {
+                    self.error(
+                        format!("Unable to capture `{}` variable!", name),
+                        ErrorType::BuildError,
+                        line,
+                    )
+                })?;
+
+            let _ = self.builder.build_store(field_ptr, value);
+        }
+
+        Ok(environment)
+    }
+
+    fn bind_environment(
+        &mut self,
+        environment: PointerValue<'ctx>,
+        captures: &[(String, Variable<'ctx>)],
+        line: usize,
+    ) -> CompileResult<()> {
+        let environment_type = self.__environment_type(captures);
+
+        for (index, (name, variable)) in captures.iter().enumerate() {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(environment_type, environment, index as u32, name)
+                .map_err(|_| {
+                    self.error(
+                        format!("Unable to bind captured `{}` variable!", name),
+                        ErrorType::BuildError,
+                        line,
+                    )
+                })?;
+
+            self.variables.insert(
+                name.clone(),
+                Variable::new(
+                    variable.str_type.clone(),
+                    true,
+                    variable.basic_type,
+                    field_ptr,
+                    variable.assigned_function.clone(),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn build_closure_call(
+        &mut self,
+        variable_name: String,
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let variable = self.variables.get(&variable_name).cloned().unwrap();
+
+        if !variable.assigned {
+            return Err(self.error(
+                format!("No value assigned to `{}` variable!", variable_name),
+                ErrorType::NoValue,
+                line,
+            ));
+        }
+
+        // arguments are checked by signature: `fn<int32(int32)>`, erased `fn<int32>` value
+        // can hold any lambda, so calling it without signature is rejected
+        let (return_type, signature_arguments) = Compiler::__fn_signature(&variable.str_type);
+        let expected_arguments = signature_arguments.ok_or_else(|| {
+            self.error(
+                format!(
+                    "Function `{}` can't be called through `{}` type without signature, use `fn<{}(...)>` instead!",
+                    variable_name, variable.str_type, return_type
+                ),
+                ErrorType::TypeError,
+                line,
+            )
+        })?;
+
+        if arguments.len() != expected_arguments.len() {
+            return Err(self.error(
+                format!(
+                    "Function `{}` has {} arguments, but {} found!",
+                    variable_name,
+                    expected_arguments.len(),
+                    arguments.len()
+                ),
+                ErrorType::NotExpected,
+                line,
+            ));
+        }
+
+        let mut arguments_error = false;
+        let mut arguments_types = Vec::new();
+        let mut parameters: Vec<BasicMetadataTypeEnum> =
+            vec![self.context.ptr_type(AddressSpace::default()).into()];
+        let mut values: Vec<BasicMetadataValueEnum> = Vec::new();
+
+        for (argument, expected_type) in arguments.into_iter().zip(&expected_arguments) {
+            let compiled_arg =
+                self.compile_expression(argument, line, function, Some(expected_type.clone()))?;
+            let compiled_arg = self.coerce_value(compiled_arg, expected_type, line)?;
+
+            if *expected_type != compiled_arg.0 {
+                arguments_error = true;
+            } else {
+                parameters.push(self.get_storage_type(&compiled_arg.0, line)?.into());
+                values.push(self.array_aggregate(&compiled_arg, line)?.into());
+            }
+
+            arguments_types.push(compiled_arg.0);
+        }
+
+        if arguments_error {
+            return Err(self.error(
+                format!(
+                    "Lambda function expected arguments types [{}], but found [{}]!",
+                    expected_arguments.join(", "),
+                    arguments_types.join(", "),
+                ),
+                ErrorType::TypeError,
+                line,
+            ));
+        }
+
+        // unpacking fat pointer
+        let closure = self
+            .builder
+            .build_load(variable.basic_type, variable.pointer, "")
+            .map_err(|_| {
+                self.error(
+                    format!("Error with loading `{}` variable", variable_name),
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })?
+            .into_struct_value();
+
+        let function_ptr = self
+            .builder
+            .build_extract_value(closure, FUNCTION_FIELD, "")
+            .unwrap()
+            .into_pointer_value();
+        let environment = self
+            .builder
+            .build_extract_value(closure, ENVIRONMENT_FIELD, "")
+            .unwrap();
+
+        values.insert(0, environment.into());
+
+        let fn_type = self.get_fn_type(&return_type, &parameters, false, line)?;
+
+        let call_result = self
+            .builder
+            .build_indirect_call(fn_type, function_ptr, &values, "")
+            .map_err(|_| {
+                self.error(
+                    format!(
+                        "An error occured while calling `{}` closure!",
+                        variable_name
+                    ),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?
+            .try_as_basic_value()
+            .left()
+            .unwrap_or_else(|| self.context.i8_type().const_zero().into());
+
+        self.array_from_aggregate((return_type, call_result), line)
+    }
+}
+
+// capture analysis: every identifier used in lambda body is a candidate,
+// only candidates defined in enclosing scopes are captured
+
+fn captured_identifiers(arguments: &[(String, String)], statements: &[Statements]) -> Vec<String> {
+    let mut identifiers = Vec::new();
+
+    for statement in statements {
+        collect_statement(statement, &mut identifiers);
+    }
+
+    identifiers.retain(|name| !arguments.iter().any(|(argument, _)| argument == name));
+    identifiers
+}
+
+fn collect_name(name: &str, identifiers: &mut Vec<String>) {
+    if !identifiers.iter().any(|identifier| identifier == name) {
+        identifiers.push(name.to_string());
+    }
+}
+
+fn collect_block(block: &[Statements], identifiers: &mut Vec<String>) {
+    for statement in block {
+        collect_statement(statement, identifiers);
+    }
+}
+
+fn collect_statement(statement: &Statements, identifiers: &mut Vec<String>) {
+    match statement {
+        Statements::AssignStatement {
+            identifier, value, ..
+        }
+        | Statements::BinaryAssignStatement {
+            identifier, value, ..
+        }
+        | Statements::DerefAssignStatement {
+            identifier, value, ..
+        } => {
+            collect_name(identifier, identifiers);
+            collect_expression(value, identifiers);
+        }
+        Statements::SliceAssignStatement {
+            object,
+            index,
+            value,
+            ..
+        } => {
+            collect_expression(object, identifiers);
+            collect_expression(index, identifiers);
+            collect_expression(value, identifiers);
+        }
+        Statements::FieldAssignStatement { target, value, .. } => {
+            collect_expression(target, identifiers);
+            collect_expression(value, identifiers);
+        }
+        Statements::AnnotationStatement { value, .. } => {
+            if let Some(value) = value {
+                collect_expression(value, identifiers);
+            }
+        }
+        Statements::FunctionCallStatement {
+            function_name,
+            arguments,
+            ..
+        } => {
+            collect_name(function_name, identifiers);
+            arguments
+                .iter()
+                .for_each(|argument| collect_expression(argument, identifiers));
+        }
+        Statements::IfStatement {
+            condition,
+            then_block,
+            else_block,
+            ..
+        } => {
+            collect_expression(condition, identifiers);
+            collect_block(then_block, identifiers);
+
+            if let Some(else_block) = else_block {
+                collect_block(else_block, identifiers);
+            }
+        }
+        Statements::WhileStatement {
+            condition, block, ..
+        } => {
+            collect_expression(condition, identifiers);
+            collect_block(block, identifiers);
+        }
+        Statements::ForStatement {
+            initializer,
+            condition,
+            iterator,
+            block,
+            ..
+        } => {
+            collect_statement(initializer, identifiers);
+            collect_expression(condition, identifiers);
+            collect_statement(iterator, identifiers);
+            collect_block(block, identifiers);
+        }
+        Statements::ForInStatement {
+            iterable, block, ..
+        } => {
+            collect_expression(iterable, identifiers);
+            collect_block(block, identifiers);
+        }
+        Statements::SwitchStatement {
+            value,
+            cases,
+            default_block,
+            ..
+        } => {
+            collect_expression(value, identifiers);
+
+            for (case_values, block) in cases {
+                case_values
+                    .iter()
+                    .for_each(|case_value| collect_expression(case_value, identifiers));
+                collect_block(block, identifiers);
+            }
+
+            if let Some(default_block) = default_block {
+                collect_block(default_block, identifiers);
+            }
+        }
+        Statements::MatchStatement { value, arms, .. } => {
+            collect_expression(value, identifiers);
+            arms.iter()
+                .for_each(|(_, _, block)| collect_block(block, identifiers));
+        }
+        Statements::ReturnStatement { value, .. } => collect_expression(value, identifiers),
+        Statements::Expression(expression) => collect_expression(expression, identifiers),
+
+        // named functions and types cannot capture anything
+        Statements::FunctionDefineStatement { .. }
+        | Statements::StructDefineStatement { .. }
+        | Statements::EnumDefineStatement { .. }
+        | Statements::ImportStatement { .. }
+        | Statements::BreakStatement { .. }
+        | Statements::ContinueStatement { .. }
+        | Statements::None
+        | Statements::End => {}
+    }
+}
+
+fn collect_expression(expression: &Expressions, identifiers: &mut Vec<String>) {
+    match expression {
+        Expressions::Binary { lhs, rhs, .. }
+        | Expressions::Boolean { lhs, rhs, .. }
+        | Expressions::Bitwise { lhs, rhs, .. } => {
+            collect_expression(lhs, identifiers);
+            collect_expression(rhs, identifiers);
+        }
+        Expressions::Unary { object, .. }
+        | Expressions::Reference { object, .. }
+        | Expressions::Dereference { object, .. } => collect_expression(object, identifiers),
+        Expressions::SubElement { parent, child, .. } => {
+            collect_expression(parent, identifiers);
+
+            // child is a field name or a method, only method arguments are used
+            if let Expressions::Call { arguments, .. } = child.as_ref() {
+                arguments
+                    .iter()
+                    .for_each(|argument| collect_expression(argument, identifiers));
+            }
+        }
+        Expressions::Call {
+            function_name,
+            arguments,
+            ..
+        } => {
+            collect_name(function_name, identifiers);
+            arguments
+                .iter()
+                .for_each(|argument| collect_expression(argument, identifiers));
+        }
+        Expressions::Lambda { statements, .. } => collect_block(statements, identifiers),
+        Expressions::Array { values, .. } => values
+            .iter()
+            .for_each(|value| collect_expression(value, identifiers)),
+        Expressions::Slice { object, index, .. } => {
+            collect_expression(object, identifiers);
+            collect_expression(index, identifiers);
+        }
+        Expressions::Range { start, end, .. } => {
+            collect_expression(start, identifiers);
+            collect_expression(end, identifiers);
+        }
+        Expressions::Struct { fields, .. } => fields
+            .iter()
+            .for_each(|(_, value)| collect_expression(value, identifiers)),
+        Expressions::Value(Value::Identifier(identifier), _) => {
+            collect_name(identifier, identifiers)
+        }
+        Expressions::Value(..) | Expressions::Argument { .. } | Expressions::None => {}
+    }
+}
@@ -0,0 +1,23 @@
+// Toy Programming Language | by mealet
+// https://github.com/mealet/tpl-lang
+// =========================================
+// Project licensed under the BSD-3 LICENSE.
+// Check the `LICENSE` file to more info.
+
+#[derive(Debug, Clone)]
+#[allow(unused)]
+pub struct Enumeration<'ctx> {
+    pub name: String,
+    pub variants: Vec<(String, Vec<String>)>, // ("Rect", ["int32", "int32"])
+    pub enum_type: inkwell::types::StructType<'ctx>,
+}
+
+impl Enumeration<'_> {
+    // tag and payload types of the variant
+    pub fn variant(&self, name: &str) -> Option<(u32, Vec<String>)> {
+        self.variants
+            .iter()
+            .position(|(variant, _)| variant == name)
+            .map(|index| (index as u32, self.variants[index].1.clone()))
+    }
+}
@@ -5,13 +5,12 @@
 // Check the `LICENSE` file to more info.
 
 use colored::Colorize;
+use tpl_lexer::span::Span;
 
 // IR Error
 
-pub struct GenError;
-
 #[allow(unused)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ErrorType {
     NotDefined,
     NotSupported,
@@ -26,50 +25,80 @@ pub enum ErrorType {
     BuildError,
 }
 
-impl GenError {
-    pub fn throw<T: std::fmt::Display>(
-        description: T,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodegenDiagnostic {
+    pub error_type: ErrorType,
+    pub message: String,
+    pub file: String,
+    pub line: usize,
+
+    source_line: String,
+    underline: String,
+}
+
+#[allow(unused)]
+impl CodegenDiagnostic {
+    pub fn new<T: std::fmt::Display>(
+        message: T,
         error_type: ErrorType,
-        module_name: String,
-        source: String,
+        file: String,
+        source: &str,
         line: usize,
-    ) {
-        let stringified_type = format!("{:?}", error_type);
-        let fmt = Self::format(description, stringified_type, module_name, source, line);
+    ) -> Self {
+        let source_line = source.lines().nth(line).unwrap_or_default().to_string();
+
+        Self {
+            error_type,
+            message: message.to_string(),
+            file,
+            line,
+            source_line,
+            underline: String::new(),
+        }
+    }
+
+    pub fn with_span(mut self, span: Span, source: &str) -> Self {
+        // span is marked only if it starts on the reported line
+        if span.location(source).0 == self.line {
+            self.underline = span.underline(source);
+        }
 
-        eprintln!("{}", fmt);
+        self
     }
 
-    pub fn format<T: std::fmt::Display>(
-        description: T,
-        error_type: String,
-        module_name: String,
-        source: String,
-        line: usize,
-    ) -> String {
-        let line_number_len = line.to_string().len();
-        let fetched_line = source.lines().collect::<Vec<&str>>()[line];
+    pub fn format_error(&self) -> String {
+        let line_number_len = (self.line + 1).to_string().len();
 
-        let red_side_fmt = format!("[CodeGen][{}][{}]:", error_type, module_name).red();
+        let red_side_fmt = format!("[CodeGen][{:?}][{}]:", self.error_type, self.file).red();
         let line_fmt = format!(
-            "{}{}\n {} {} {}\n{}{}",
+            "{}{}\n {} {} {}\n{}{} {}",
             " ".repeat(line_number_len + 2),
             "|".cyan(),
-            line + 1,
+            self.line + 1,
             "|".cyan(),
-            fetched_line,
+            self.source_line,
             " ".repeat(line_number_len + 2),
-            "|".cyan()
+            "|".cyan(),
+            self.underline.red(),
         );
 
-        format!("{} {}\n{}", red_side_fmt, description, line_fmt)
+        format!("{} {}\n{}", red_side_fmt, self.message, line_fmt)
+    }
+}
+
+impl std::fmt::Display for CodegenDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format_error())
     }
 }
 
 // Import Error
 
 #[derive(Debug, Clone)]
-pub struct ImportError;
+pub struct ImportError {
+    pub description: String,
+    pub error_type: ImportErrorType,
+}
 
 #[derive(Debug, Clone)]
 pub enum ImportErrorType {
@@ -79,18 +108,16 @@ pub enum ImportErrorType {
 }
 
 impl ImportError {
-    pub fn throw<T: std::fmt::Display>(description: T, error_type: ImportErrorType) {
-        let stringified_type = format!("{:?}", error_type);
-        let fmt = Self::format(description, stringified_type);
-
-        eprintln!("{}", fmt);
+    pub fn new<T: std::fmt::Display>(description: T, error_type: ImportErrorType) -> Self {
+        Self {
+            description: description.to_string(),
+            error_type,
+        }
     }
+}
 
-    pub fn format<T: std::fmt::Display>(description: T, error_type: String) -> String {
-        format!(
-            "{} {}",
-            format!("[ImportError][{}]:", error_type).red(),
-            description
-        )
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{:?}] {}", self.error_type, self.description)
     }
 }
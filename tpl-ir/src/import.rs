@@ -5,11 +5,9 @@
 // Check the `LICENSE` file to more info.
 
 use crate::error::{ImportError, ImportErrorType};
-use std::convert::From;
+use std::convert::TryFrom;
 use std::path::PathBuf;
 
-const COMMENTS_START: &str = "//";
-
 #[allow(unused)]
 #[derive(Debug, Clone)]
 pub struct ImportObject {
@@ -25,67 +23,45 @@ impl ImportObject {
     }
 }
 
-impl From<String> for ImportObject {
-    fn from(value: String) -> Self {
+impl TryFrom<String> for ImportObject {
+    type Error = ImportError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
         // init variables
 
         let path = PathBuf::from(value);
         let path_clone = path.clone();
         let name = path_clone
             .file_name()
-            .unwrap_or_else(|| {
-                ImportError::throw(
-                    format!("Error with formatting path: {:?}", path),
-                    ImportErrorType::FormatError,
-                );
-                std::process::exit(1);
-            })
-            .to_str()
-            .unwrap_or_else(|| {
-                ImportError::throw(
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                ImportError::new(
                     format!("Error with formatting path: {:?}", path),
                     ImportErrorType::FormatError,
-                );
-                std::process::exit(1);
-            });
+                )
+            })?;
 
         // test if path is really exists
 
         if !path.exists() {
-            ImportError::throw(
+            return Err(ImportError::new(
                 format!("Module `{}` does not exists!", name),
                 ImportErrorType::PathError,
-            );
-            std::process::exit(1);
+            ));
         }
 
         // reading source code
-        let source = std::fs::read_to_string(path.clone()).unwrap_or_else(|_| {
-            ImportError::throw(
+        let source = std::fs::read_to_string(path.clone()).map_err(|_| {
+            ImportError::new(
                 format!("Cannot read `{}` module!", name),
                 ImportErrorType::ReadFailure,
-            );
-            std::process::exit(1);
-        });
+            )
+        })?;
 
-        // deleting comments
-
-        let formatted_source = source
-            .lines()
-            .map(|line| {
-                if let Some(index) = line.find(COMMENTS_START) {
-                    &line[..index]
-                } else {
-                    line
-                }
-            })
-            .collect::<Vec<&str>>()
-            .join("\n");
-
-        Self {
+        Ok(Self {
             path,
             name: name.to_string(),
-            source: formatted_source,
-        }
+            source,
+        })
     }
 }
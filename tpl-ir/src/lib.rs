@@ -5,10 +5,18 @@
 // Check the `LICENSE` file to more info.
 
 mod builtin;
+mod closure;
+mod enumeration;
 mod error;
 mod function;
 mod import;
 mod libc;
+mod list;
+mod map;
+mod runtime;
+mod scope;
+mod strings;
+mod structure;
 mod variable;
 
 use inkwell::{
@@ -16,24 +24,36 @@ use inkwell::{
     builder::Builder,
     context::Context,
     module::Module,
-    types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType},
+    types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType, StructType},
     values::{
-        BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, IntValue, PointerValue,
+        BasicMetadataValueEnum, BasicValue, BasicValueEnum, FloatValue, FunctionValue, IntValue,
+        PointerValue,
     },
     AddressSpace,
 };
 
 use builtin::BuiltIn;
-use libc::Libc;
+use closure::Closure;
+use list::List;
+use map::Map;
+use runtime::Runtime;
+use strings::Strings;
 use std::{collections::HashMap, sync::LazyLock};
 
-use error::{ErrorType, GenError};
+use enumeration::Enumeration;
 use function::Function;
 use import::ImportObject;
+use scope::ScopeStack;
+use structure::Structure;
 use variable::Variable;
 
+use tpl_lexer::span::Span;
 use tpl_parser::{expressions::Expressions, statements::Statements, value::Value};
 
+pub use error::{CodegenDiagnostic, ErrorType};
+
+type CompileResult<T> = Result<T, CodegenDiagnostic>;
+
 static LAMBDA_NAME: &str = "i_need_newer_inkwell_version"; // :D
 static INT_TYPES_ORDER: LazyLock<HashMap<&str, u8>> =
     LazyLock::new(|| HashMap::from([("int8", 0), ("int16", 1), ("int32", 2), ("int64", 3)]));
@@ -44,6 +64,8 @@ static TYPE_SIZES: LazyLock<HashMap<&str, u64>> = LazyLock::new(|| {
         ("int16", 2),
         ("int32", 4),
         ("int64", 8),
+        ("float32", 4),
+        ("float64", 8),
         ("bool", 1),
         ("char", 1),
         ("str", 8),
@@ -71,17 +93,28 @@ pub struct Compiler<'ctx> {
     // function and block
     main_function: FunctionValue<'ctx>,
     current_block: BasicBlock<'ctx>,
+    loop_blocks: Vec<(BasicBlock<'ctx>, BasicBlock<'ctx>)>, // (continue, break) targets
 
     // hashmaps
-    variables: HashMap<String, Variable<'ctx>>,
+    variables: ScopeStack<'ctx>,
     functions: HashMap<String, Function<'ctx>>,
+    structures: HashMap<String, Structure<'ctx>>,
+    enums: HashMap<String, Enumeration<'ctx>>,
     imports: HashMap<String, ImportObject>,
 
+    // diagnostics
+    diagnostics: Vec<CodegenDiagnostic>,
+    current_span: Option<Span>,
+    next_file_id: usize, // main module is `0`, imported modules are numbered from `1`
+
     // tech
     built_functions: HashMap<String, FunctionValue<'ctx>>,
     current_expectation_value: Option<String>,
     current_assign_function: Option<Function<'ctx>>,
     boolean_strings_ptr: Option<(PointerValue<'ctx>, PointerValue<'ctx>)>,
+
+    // options
+    bounds_checks: bool,
 }
 
 impl<'ctx> Compiler<'ctx> {
@@ -94,9 +127,15 @@ impl<'ctx> Compiler<'ctx> {
         let module = context.create_module(module_name);
         let builder = context.create_builder();
 
-        // main function creation
+        // main function creation: `int32 main(int32 argc, str* argv)`
         let main_fn_type = context.i32_type();
-        let fn_type = main_fn_type.fn_type(&[], false);
+        let fn_type = main_fn_type.fn_type(
+            &[
+                context.i32_type().into(),
+                context.ptr_type(AddressSpace::default()).into(),
+            ],
+            false,
+        );
         let function = module.add_function("main", fn_type, None);
         let basic_block = context.append_basic_block(function, "entry");
 
@@ -111,31 +150,77 @@ impl<'ctx> Compiler<'ctx> {
             builder,
             module,
 
-            variables: HashMap::new(),
+            variables: ScopeStack::new(),
             functions: HashMap::new(),
+            structures: HashMap::new(),
+            enums: HashMap::new(),
             imports: HashMap::new(),
 
             current_block: basic_block,
             main_function: function,
+            loop_blocks: Vec::new(),
+
+            diagnostics: Vec::new(),
+            current_span: None,
+            next_file_id: 1,
 
             built_functions,
             current_expectation_value: None,
             current_assign_function: None,
             boolean_strings_ptr: None,
+
+            bounds_checks: true,
         }
     }
 
-    pub fn generate(&mut self, statements: Vec<Statements>) {
+    // runtime bounds checks can be disabled for release builds
+    pub fn with_bounds_checks(mut self, enabled: bool) -> Self {
+        self.bounds_checks = enabled;
+        self
+    }
+
+    pub fn generate(&mut self, statements: Vec<Statements>) -> Result<(), Vec<CodegenDiagnostic>> {
         self.builder.position_at_end(self.current_block);
+        self.build_arguments_store();
 
-        for statement in statements {
-            self.compile_statement(statement, self.main_function);
+        self.compile_block(statements, self.main_function);
+
+        // returning 0 if program wasn't finished by top-level `return`
+        if !self.is_block_terminated() {
+            let _ = self
+                .builder
+                .build_return(Some(&self.context.i32_type().const_int(0, false)));
         }
 
-        // returning 0
-        let _ = self
-            .builder
-            .build_return(Some(&self.context.i32_type().const_int(0, false)));
+        if self.diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(std::mem::take(&mut self.diagnostics))
+        }
+    }
+
+    fn error<T: std::fmt::Display>(
+        &self,
+        description: T,
+        error_type: ErrorType,
+        line: usize,
+    ) -> CodegenDiagnostic {
+        let diagnostic = CodegenDiagnostic::new(
+            description,
+            error_type,
+            self.module_name.clone(),
+            &self.module_source,
+            line,
+        );
+
+        match self.current_span {
+            Some(span) => diagnostic.with_span(span, &self.module_source),
+            None => diagnostic,
+        }
+    }
+
+    fn report(&mut self, diagnostic: CodegenDiagnostic) {
+        self.diagnostics.push(diagnostic);
     }
 
     fn switch_block(&mut self, dest: BasicBlock<'ctx>) {
@@ -143,7 +228,92 @@ impl<'ctx> Compiler<'ctx> {
         self.builder.position_at_end(dest);
     }
 
-    fn compile_statement(&mut self, statement: Statements, function: FunctionValue<'ctx>) {
+    fn is_block_terminated(&self) -> bool {
+        self.builder
+            .get_insert_block()
+            .and_then(|block| block.get_terminator())
+            .is_some()
+    }
+
+    fn compile_block(&mut self, block: Vec<Statements>, function: FunctionValue<'ctx>) {
+        for stmt in block {
+            // compiler state to roll back to if statement fails
+            let scopes_depth = self.variables.depth();
+            let loops_depth = self.loop_blocks.len();
+            let insert_block = self.builder.get_insert_block();
+            let expectation_value = self.current_expectation_value.clone();
+
+            // span of the statement is used to mark errors in source
+            let outer_span = std::mem::replace(&mut self.current_span, stmt.span());
+            let result = self.compile_statement(stmt, function);
+
+            self.current_span = outer_span;
+
+            if let Err(diagnostic) = result {
+                self.report(diagnostic);
+
+                self.variables.truncate(scopes_depth);
+                self.loop_blocks.truncate(loops_depth);
+                self.current_expectation_value = expectation_value;
+
+                // failed statement could leave builder inside of another function
+                let current_function = self
+                    .builder
+                    .get_insert_block()
+                    .and_then(|block| block.get_parent());
+
+                if current_function != Some(function) {
+                    if let Some(block) = insert_block {
+                        self.switch_block(block);
+                    }
+                }
+            }
+        }
+    }
+
+    fn compile_scoped_block(&mut self, block: Vec<Statements>, function: FunctionValue<'ctx>) {
+        self.variables.enter();
+        self.compile_block(block, function);
+        self.variables.exit();
+    }
+
+    fn undefined_variable_message(&self, identifier: &str) -> String {
+        if self.variables.is_expired(identifier) {
+            format!("Variable `{}` is used outside of its scope!", identifier)
+        } else {
+            format!("Variable `{}` is not defined!", identifier)
+        }
+    }
+
+    fn arguments_count_error(
+        &self,
+        function_name: &str,
+        expected: usize,
+        found: usize,
+        line: usize,
+    ) -> CodegenDiagnostic {
+        self.error(
+            format!(
+                "Function `{}()` requires {} arguments, but {} found!",
+                function_name, expected, found
+            ),
+            ErrorType::NotExpected,
+            line,
+        )
+    }
+
+    // function values in scope shadow functions with the same name: `fn<str(str)> trim`
+    fn is_callable_variable(&self, identifier: &str) -> bool {
+        self.variables
+            .get(identifier)
+            .is_some_and(|variable| Compiler::__is_fn_type(&variable.str_type))
+    }
+
+    fn compile_statement(
+        &mut self,
+        statement: Statements,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<()> {
         match statement {
             // NOTE: Annotation
             Statements::AnnotationStatement {
@@ -151,79 +321,95 @@ impl<'ctx> Compiler<'ctx> {
                 datatype,
                 value,
                 line,
+                ..
             } => {
                 if datatype == *"auto" {
-                    let initial_value = value.unwrap_or_else(|| {
-                        GenError::throw(
+                    let initial_value = value.ok_or_else(|| {
+                        self.error(
                             "Variable with `auto` type cannot be empty!",
                             ErrorType::TypeError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
-                    });
+                        )
+                    })?;
 
                     let compiled_expression = self.compile_expression(
                         *initial_value,
                         line,
                         function,
                         self.current_expectation_value.clone(),
-                    );
-                    let var_type = self.get_basic_type(compiled_expression.0.as_str(), line);
+                    )?;
+                    let var_type = self.get_basic_type(compiled_expression.0.as_str(), line)?;
                     let alloca = self
                         .builder
                         .build_alloca(var_type, &identifier)
-                        .unwrap_or_else(|_| {
-                            GenError::throw(
+                        .map_err(|_| {
+                            self.error(
                                 "Unable to create 'automated' type alloca!",
                                 ErrorType::BuildError,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
                                 line,
-                            );
-                            std::process::exit(1);
-                        });
+                            )
+                        })?;
+
+                    self.build_value_store(alloca, &compiled_expression, line)?;
 
                     self.variables.insert(
                         identifier.clone(),
                         Variable::new(compiled_expression.0, true, var_type, alloca, None),
                     );
-
-                    let _ = self.builder.build_store(alloca, compiled_expression.1);
                 } else {
                     let var_type = if Compiler::__is_ptr_type(&datatype) {
                         self.context
                             .ptr_type(AddressSpace::default())
                             .as_basic_type_enum()
                     } else {
-                        self.get_basic_type(&datatype, line)
+                        self.get_basic_type(&datatype, line)?
                     };
 
                     let alloca = self
                         .builder
                         .build_alloca(var_type, &identifier)
-                        .unwrap_or_else(|_| {
-                            GenError::throw(
+                        .map_err(|_| {
+                            self.error(
                                 format!(
                                     "Error with creating allocation with identifier `{}`",
                                     &identifier
                                 ),
                                 ErrorType::MemoryError,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
                                 line,
-                            );
-                            std::process::exit(1);
-                        });
+                            )
+                        })?;
 
                     let assigned_function = self.current_assign_function.clone();
 
+                    // structures and arrays without value are zeroed,
+                    // so fields and elements can be assigned one by one
+                    let is_zeroed = value.is_none()
+                        && (self.structures.contains_key(&datatype)
+                            || Compiler::__is_arr_type(&datatype));
+
+                    if is_zeroed {
+                        let _ = self.builder.build_store(alloca, var_type.const_zero());
+                    }
+
+                    // lists and maps without value are created empty
+                    let is_empty_collection = value.is_none()
+                        && (Compiler::__is_list_type(&datatype)
+                            || Compiler::__is_map_type(&datatype));
+
+                    if is_empty_collection {
+                        let empty_collection = if Compiler::__is_map_type(&datatype) {
+                            self.build_map_new(&datatype, line)?
+                        } else {
+                            self.build_list_new(&datatype, None, line)?
+                        };
+                        let _ = self.builder.build_store(alloca, empty_collection.1);
+                    }
+
                     self.variables.insert(
                         identifier.clone(),
                         Variable::new(
                             datatype.clone(),
-                            false,
+                            is_zeroed || is_empty_collection,
                             var_type,
                             alloca,
                             assigned_function.clone(),
@@ -231,24 +417,28 @@ impl<'ctx> Compiler<'ctx> {
                     );
 
                     if let Some(intial_value) = value {
-                        let expected_type = match datatype.clone().as_str() {
-                            _ if datatype.contains("[") => {
-                                Some(Compiler::clean_array_datatype(&datatype))
-                            }
-                            _ => Some(datatype.clone()),
-                        };
+                        let expected_type = Some(datatype.clone());
 
                         let old_expectation_value = self.current_expectation_value.clone();
                         self.current_expectation_value = expected_type.clone();
 
+                        // only lambda in this value can assign a function to the variable
+                        self.current_assign_function = None;
+
                         let compiled_expression =
-                            self.compile_expression(*intial_value, line, function, expected_type);
+                            self.compile_expression(*intial_value, line, function, expected_type)?;
 
                         // matching datatypes
 
-                        if compiled_expression.0 == String::from("null") { return };
+                        if compiled_expression.0 == String::from("null") {
+                            return Ok(());
+                        };
+
+                        let compiled_expression =
+                            self.coerce_value(compiled_expression, &datatype, line)?;
+
                         if compiled_expression.0 != datatype {
-                            GenError::throw(
+                            return Err(self.error(
                                 format!(
                                     "Type `{}` expected for '{}' variable, but found `{}`!",
                                     datatype,
@@ -256,11 +446,8 @@ impl<'ctx> Compiler<'ctx> {
                                     compiled_expression.0
                                 ),
                                 ErrorType::TypeError,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
                                 line,
-                            );
-                            std::process::exit(1);
+                            ));
                         }
 
                         if Compiler::__is_ptr_type(&datatype) {
@@ -287,7 +474,7 @@ impl<'ctx> Compiler<'ctx> {
                                     assigned_function.clone(),
                                 ),
                             );
-                            let _ = self.builder.build_store(alloca, compiled_expression.1);
+                            self.build_value_store(alloca, &compiled_expression, line)?;
                         }
 
                         // rewriting variable for assigning function
@@ -316,261 +503,176 @@ impl<'ctx> Compiler<'ctx> {
                 identifier,
                 value,
                 line,
+                ..
             } => {
-                if let Some(var_ptr) = self.variables.clone().get(&identifier) {
+                if let Some(var_ptr) = self.variables.get(&identifier).cloned() {
                     let expr_value = self.compile_expression(
                         *value,
                         line,
                         function,
                         Some(var_ptr.str_type.clone()),
-                    );
+                    )?;
+                    let expr_value = self.coerce_value(expr_value, &var_ptr.str_type, line)?;
 
                     // matching datatypes
 
                     if expr_value.0 != var_ptr.str_type {
-                        GenError::throw(
+                        return Err(self.error(
                             format!(
                                 "Expected type `{}`, but found `{}`!",
                                 var_ptr.str_type, expr_value.0
                             ),
                             ErrorType::TypeError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
+                        ));
                     }
 
                     // storing value
 
-                    let _ = self.builder.build_store(var_ptr.pointer, expr_value.1);
+                    self.build_value_store(var_ptr.pointer, &expr_value, line)?;
                 } else {
-                    GenError::throw(
-                        format!("Variable `{}` is not defined!", identifier),
+                    return Err(self.error(
+                        self.undefined_variable_message(&identifier),
                         ErrorType::NotDefined,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
+                    ));
                 }
             }
             Statements::SliceAssignStatement {
-                identifier,
+                object,
                 index,
                 value,
                 line,
+                ..
             } => {
-                if let Some(var_ptr) = self.variables.clone().get(&identifier) {
-                    let expr_value = self.compile_expression(
-                        *value,
-                        line,
-                        function,
-                        Some(Compiler::clean_array_datatype(&var_ptr.str_type)),
-                    );
-                    let index_value = self.compile_expression(*index, line, function, None);
-
-                    if !index_value.0.starts_with("int") {
-                        GenError::throw(
-                            "Non-integer index found!",
-                            ErrorType::NotExpected,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
-                            line,
-                        );
-                        std::process::exit(1);
-                    }
-
-                    match var_ptr.str_type.as_str() {
-                        vartype if Compiler::__is_ptr_type(vartype) => {
-                            let raw_type = Compiler::__unwrap_ptr_type(&var_ptr.str_type);
-                            let raw_basic_type = self.get_basic_type(&raw_type, line);
-
-                            if expr_value.0 != raw_type {
-                                GenError::throw(
-                                    format!(
-                                        "Expected type `{}`, but found `{}`!",
-                                        var_ptr.str_type, expr_value.0
-                                    ),
-                                    ErrorType::TypeError,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
-                                    line,
-                                );
-                                std::process::exit(1);
-                            }
+                let compiled_object = self.compile_expression(*object, line, function, None)?;
 
-                            let var_value = self
-                                .builder
-                                .build_load(
-                                    self.context.ptr_type(AddressSpace::default()),
-                                    var_ptr.pointer,
-                                    "",
-                                )
-                                .unwrap();
-
-                            let ptr = unsafe {
-                                self.builder
-                                    .build_in_bounds_gep(
-                                        raw_basic_type,
-                                        var_value.into_pointer_value(),
-                                        &[index_value.1.into_int_value()],
-                                        "",
-                                    )
-                                    .unwrap()
-                            };
-
-                            let _ = self.builder.build_store(ptr, expr_value.1).unwrap();
-                        }
-                        vartype if Compiler::__is_arr_type(vartype) => {
-                            if expr_value.0 != Compiler::clean_array_datatype(&var_ptr.str_type) {
-                                GenError::throw(
-                                    format!(
-                                        "Expected type `{}`, but found `{}`!",
-                                        var_ptr.str_type, expr_value.0
-                                    ),
-                                    ErrorType::TypeError,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
-                                    line,
-                                );
-                                std::process::exit(1);
-                            }
+                // string literals are placed into read-only memory
+                if compiled_object.0 == "str" {
+                    return Err(self.error(
+                        "Unsupported for slicing type found: `str`",
+                        ErrorType::NotSupported,
+                        line,
+                    ));
+                }
 
-                            // loading array from pointer
+                let index_value = self.compile_expression(*index, line, function, None)?;
 
-                            let array = self
-                                .builder
-                                .build_load(var_ptr.basic_type, var_ptr.pointer, "")
-                                .unwrap_or_else(|_| {
-                                    GenError::throw(
-                                        "Unable to load pointer value!",
-                                        ErrorType::BuildError,
-                                        self.module_name.clone(),
-                                        self.module_source.clone(),
-                                        line,
-                                    );
-                                    std::process::exit(1);
-                                })
-                                .into_vector_value();
+                if !index_value.0.starts_with("int") {
+                    return Err(self.error(
+                        "Non-integer index found!",
+                        ErrorType::NotExpected,
+                        line,
+                    ));
+                }
 
-                            let new_vector = self
-                                .builder
-                                .build_insert_element(
-                                    array,
-                                    expr_value.1,
-                                    index_value.1.into_int_value(),
-                                    "",
-                                )
-                                .unwrap_or_else(|_| {
-                                    GenError::throw(
-                                        "Unable to insert element into vector!",
-                                        ErrorType::NotExpected,
-                                        self.module_name.clone(),
-                                        self.module_source.clone(),
-                                        line,
-                                    );
-                                    std::process::exit(1);
-                                });
+                let (element_type, element_pointer) = self.array_element_pointer(
+                    compiled_object,
+                    index_value.1.into_int_value(),
+                    line,
+                )?;
 
-                            // storing new vector into pointer
+                let expr_value =
+                    self.compile_expression(*value, line, function, Some(element_type.clone()))?;
+                let expr_value = self.coerce_value(expr_value, &element_type, line)?;
 
-                            let _ = self.builder.build_store(var_ptr.pointer, new_vector);
-                        }
-                        _ => {
-                            GenError::throw(
-                                format!(
-                                    "Unsupported for slicing type found: `{}`",
-                                    var_ptr.str_type
-                                ),
-                                ErrorType::NotSupported,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
-                                line,
-                            );
-                            std::process::exit(1);
-                        }
-                    }
-                } else {
-                    GenError::throw(
-                        format!("Variable `{}` is not defined!", identifier),
-                        ErrorType::NotDefined,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
+                if expr_value.0 != element_type {
+                    return Err(self.error(
+                        format!(
+                            "Expected type `{}`, but found `{}`!",
+                            element_type, expr_value.0
+                        ),
+                        ErrorType::TypeError,
                         line,
-                    );
-                    std::process::exit(1);
+                    ));
                 }
+
+                self.build_value_store(element_pointer, &expr_value, line)?;
             }
             Statements::BinaryAssignStatement {
                 identifier,
                 operand,
                 value,
                 line,
+                span,
             } => {
-                if let Some(var_ptr) = self.variables.clone().get(&identifier) {
-                    // building new binary expression
-                    let new_expression = Expressions::Binary {
-                        operand,
-                        lhs: Box::new(Expressions::Value(Value::Identifier(identifier))),
-                        rhs: value.clone(),
-                        line,
+                if let Some(var_ptr) = self.variables.get(&identifier).cloned() {
+                    // building new binary (or bitwise) expression
+                    let lhs = Box::new(Expressions::Value(Value::Identifier(identifier), span));
+                    let new_expression = if matches!(operand.as_str(), "<<" | ">>" | "&" | "|" | "^") {
+                        Expressions::Bitwise {
+                            operand,
+                            lhs,
+                            rhs: value.clone(),
+                            line,
+                            span,
+                        }
+                    } else {
+                        Expressions::Binary {
+                            operand,
+                            lhs,
+                            rhs: value.clone(),
+                            line,
+                            span,
+                        }
                     };
 
                     let expr_value = self.compile_expression(
                         new_expression,
                         line,
                         function,
-                        self.current_expectation_value.clone(),
-                    );
+                        Some(var_ptr.str_type.clone()),
+                    )?;
 
                     // matching types
                     if expr_value.0 != var_ptr.str_type {
-                        GenError::throw(
+                        return Err(self.error(
                             format!(
                                 "Expected type `{}`, but found `{}`!",
                                 var_ptr.str_type, expr_value.0
                             ),
                             ErrorType::TypeError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
+                        ));
                     }
 
                     // storing value
 
                     let _ = self.builder.build_store(var_ptr.pointer, expr_value.1);
+                } else {
+                    return Err(self.error(
+                        self.undefined_variable_message(&identifier),
+                        ErrorType::NotDefined,
+                        line,
+                    ));
                 }
             }
             Statements::DerefAssignStatement {
                 identifier,
                 value,
                 line,
+                ..
             } => {
-                if let Some(var_ptr) = self.variables.clone().get(&identifier) {
+                if let Some(var_ptr) = self.variables.get(&identifier).cloned() {
                     let expr_value = self.compile_expression(
                         *value,
                         line,
                         function,
                         Some(var_ptr.str_type.clone()),
-                    );
+                    )?;
 
                     // matching datatypes
 
                     let raw_type = Compiler::__unwrap_ptr_type(&var_ptr.str_type);
                     if expr_value.0 != raw_type {
-                        GenError::throw(
+                        return Err(self.error(
                             format!(
                                 "Expected type `{}`, but found `{}`!",
                                 var_ptr.str_type, expr_value.0
                             ),
                             ErrorType::TypeError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
+                        ));
                     }
 
                     // loading pointer from a pointer
@@ -582,16 +684,9 @@ impl<'ctx> Compiler<'ctx> {
                     let raw_ptr = self
                         .builder
                         .build_load(ptr_type, var_ptr.pointer, "")
-                        .unwrap_or_else(|_| {
-                            GenError::throw(
-                                "Unable to load a pointer!",
-                                ErrorType::BuildError,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
-                                line,
-                            );
-                            std::process::exit(1);
-                        });
+                        .map_err(|_| {
+                            self.error("Unable to load a pointer!", ErrorType::BuildError, line)
+                        })?;
 
                     // storing value
 
@@ -599,14 +694,194 @@ impl<'ctx> Compiler<'ctx> {
                         .builder
                         .build_store(raw_ptr.into_pointer_value(), expr_value.1);
                 } else {
-                    GenError::throw(
-                        format!("Variable `{}` is not defined!", identifier),
+                    return Err(self.error(
+                        self.undefined_variable_message(&identifier),
                         ErrorType::NotDefined,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
+                    ));
+                }
+            }
+
+            Statements::FieldAssignStatement {
+                target,
+                value,
+                line,
+                ..
+            } => {
+                let (field_type, field_ptr) = self.struct_field_pointer(*target, line, function)?;
+                let expr_value =
+                    self.compile_expression(*value, line, function, Some(field_type.clone()))?;
+                let expr_value = self.coerce_value(expr_value, &field_type, line)?;
+
+                // matching datatypes
+
+                if expr_value.0 != field_type {
+                    return Err(self.error(
+                        format!(
+                            "Expected type `{}`, but found `{}`!",
+                            field_type, expr_value.0
+                        ),
+                        ErrorType::TypeError,
+                        line,
+                    ));
+                }
+
+                // storing value
+
+                self.build_value_store(field_ptr, &expr_value, line)?;
+            }
+
+            // NOTE: Structures
+            Statements::StructDefineStatement {
+                struct_name,
+                fields,
+                line,
+                ..
+            } => {
+                if self.get_user_type(&struct_name).is_some() {
+                    return Err(self.error(
+                        format!("Type `{}` is already defined!", struct_name),
+                        ErrorType::NotExpected,
+                        line,
+                    ));
+                }
+
+                for (index, (field, datatype)) in fields.iter().enumerate() {
+                    if fields[..index].iter().any(|(name, _)| name == field) {
+                        return Err(self.error(
+                            format!(
+                                "Field `{}` is defined twice in `{}` structure!",
+                                field, struct_name
+                            ),
+                            ErrorType::NotExpected,
+                            line,
+                        ));
+                    }
+
+                    if *datatype == struct_name {
+                        return Err(self.error(
+                            format!(
+                                "Structure `{}` cannot contain itself! Use pointer instead: `{}*`",
+                                struct_name, struct_name
+                            ),
+                            ErrorType::TypeError,
+                            line,
+                        ));
+                    }
+                }
+
+                // declaring opaque type first, so fields can point to the structure itself
+                let struct_type = self.context.opaque_struct_type(&struct_name);
+
+                self.structures.insert(
+                    struct_name.clone(),
+                    Structure {
+                        name: struct_name.clone(),
+                        fields: fields.clone(),
+                        struct_type,
+                    },
+                );
+
+                let field_types: CompileResult<Vec<BasicTypeEnum<'ctx>>> = fields
+                    .iter()
+                    .map(|(_, datatype)| self.get_storage_type(datatype, line))
+                    .collect();
+
+                match field_types {
+                    Ok(field_types) => {
+                        struct_type.set_body(&field_types, false);
+                    }
+                    Err(diagnostic) => {
+                        self.structures.remove(&struct_name);
+                        return Err(diagnostic);
+                    }
+                }
+            }
+
+            Statements::EnumDefineStatement {
+                enum_name,
+                variants,
+                line,
+                ..
+            } => {
+                if self.get_user_type(&enum_name).is_some() {
+                    return Err(self.error(
+                        format!("Type `{}` is already defined!", enum_name),
+                        ErrorType::NotExpected,
+                        line,
+                    ));
+                }
+
+                if variants.is_empty() {
+                    return Err(self.error(
+                        format!(
+                            "Enumeration `{}` must have at least one variant!",
+                            enum_name
+                        ),
+                        ErrorType::NotExpected,
+                        line,
+                    ));
+                }
+
+                for (index, (variant, payload)) in variants.iter().enumerate() {
+                    if variants[..index].iter().any(|(name, _)| name == variant) {
+                        return Err(self.error(
+                            format!(
+                                "Variant `{}` is defined twice in `{}` enumeration!",
+                                variant, enum_name
+                            ),
+                            ErrorType::NotExpected,
+                            line,
+                        ));
+                    }
+
+                    if payload.contains(&enum_name) {
+                        return Err(self.error(
+                            format!(
+                                "Enumeration `{}` cannot contain itself! Use pointer instead: `{}*`",
+                                enum_name, enum_name
+                            ),
+                            ErrorType::TypeError,
+                            line,
+                        ));
+                    }
+                }
+
+                // NOTE: enumeration is lowered to `{ i32 tag, { payload 0 }, { payload 1 }, ... }`,
+                // payloads don't share memory because types sizes aren't known before target is chosen
+
+                let enum_type = self.context.opaque_struct_type(&enum_name);
+
+                self.enums.insert(
+                    enum_name.clone(),
+                    Enumeration {
+                        name: enum_name.clone(),
+                        variants: variants.clone(),
+                        enum_type,
+                    },
+                );
+
+                let payload_types: CompileResult<Vec<BasicTypeEnum<'ctx>>> = variants
+                    .iter()
+                    .map(|(_, payload)| {
+                        let field_types = payload
+                            .iter()
+                            .map(|datatype| self.get_storage_type(datatype, line))
+                            .collect::<CompileResult<Vec<BasicTypeEnum<'ctx>>>>()?;
+
+                        Ok(self.context.struct_type(&field_types, false).into())
+                    })
+                    .collect();
+
+                match payload_types {
+                    Ok(payload_types) => {
+                        let tag_type = self.context.i32_type().as_basic_type_enum();
+                        enum_type.set_body(&[vec![tag_type], payload_types].concat(), false);
+                    }
+                    Err(diagnostic) => {
+                        self.enums.remove(&enum_name);
+                        return Err(diagnostic);
+                    }
                 }
             }
 
@@ -617,48 +892,119 @@ impl<'ctx> Compiler<'ctx> {
                 arguments,
                 block,
                 line,
+                ..
             } => {
-                self.define_user_function(function_name, function_type, arguments, block, line);
+                self.define_user_function(
+                    function_name,
+                    function_type,
+                    arguments,
+                    block,
+                    line,
+                    None,
+                )?;
             }
 
             Statements::FunctionCallStatement {
                 function_name,
                 arguments,
                 line,
+                ..
             } => {
                 match function_name.as_str() {
-                    "print" => {
-                        self.build_print_call(arguments, line, function);
+                    "print" if !self.is_callable_variable(&function_name) => {
+                        self.build_print_call(arguments, line, function)?;
                     }
-                    "concat" => {
-                        self.build_concat_call(arguments, line, function);
+                    "concat" if !self.is_callable_variable(&function_name) => {
+                        self.build_concat_call(arguments, line, function)?;
                     }
                     _ => {
                         // user defined function
-                        self.fn_call(function_name, arguments, line, function);
+                        self.fn_call(function_name, arguments, line, function)?;
                     }
                 }
             }
 
-            Statements::ReturnStatement { value, line } => {
-                let compiled_value = self.compile_expression(
-                    value,
-                    line,
-                    function,
-                    self.current_expectation_value.clone(),
-                );
-                let _ = self.builder.build_return(Some(&compiled_value.1));
-            }
+            Statements::ReturnStatement { value, line, .. } => {
+                // top-level `return` finishes program with exit code
+                if function == self.main_function {
+                    let exit_code =
+                        self.compile_expression(value, line, function, Some("int32".to_string()))?;
 
-            // NOTE: Constructions
-            Statements::IfStatement {
+                    if !exit_code.0.starts_with("int") {
+                        return Err(self.error(
+                            format!(
+                                "Top-level `return` requires integer exit code, but found `{}`!",
+                                exit_code.0
+                            ),
+                            ErrorType::TypeError,
+                            line,
+                        ));
+                    }
+
+                    let exit_code = self
+                        .builder
+                        .build_int_cast_sign_flag(
+                            exit_code.1.into_int_value(),
+                            self.context.i32_type(),
+                            true,
+                            "exit_code",
+                        )
+                        .unwrap();
+
+                    let _ = self.builder.build_return(Some(&exit_code));
+
+                    // statements after `return` are unreachable, so they get their own block
+                    let unreachable_block =
+                        self.context.append_basic_block(function, "return_after");
+                    self.switch_block(unreachable_block);
+
+                    return Ok(());
+                }
+
+                let compiled_value = self.compile_expression(
+                    value,
+                    line,
+                    function,
+                    self.current_expectation_value.clone(),
+                )?;
+
+                // returned functions must match the signature, because all closures
+                // have the same representation and verification cannot catch it
+                if let Some(return_type) = self
+                    .current_expectation_value
+                    .clone()
+                    .filter(|return_type| Compiler::__is_fn_type(return_type))
+                {
+                    let compiled_value =
+                        self.coerce_value(compiled_value.clone(), &return_type, line)?;
+
+                    if compiled_value.0 != return_type {
+                        return Err(self.error(
+                            format!(
+                                "Function must return `{}`, but found `{}`!",
+                                return_type, compiled_value.0
+                            ),
+                            ErrorType::TypeError,
+                            line,
+                        ));
+                    }
+                }
+
+                let returned_value = self.array_aggregate(&compiled_value, line)?;
+
+                let _ = self.builder.build_return(Some(&returned_value));
+            }
+
+            // NOTE: Constructions
+            Statements::IfStatement {
                 condition,
                 then_block,
                 else_block,
                 line,
+                ..
             } => {
                 // compiling condition
-                let compiled_condition = self.compile_condition(condition, line, function);
+                let compiled_condition = self.compile_condition(condition, line, function)?;
 
                 // checking for else block
                 if let Some(else_matched_block) = else_block {
@@ -677,30 +1023,22 @@ impl<'ctx> Compiler<'ctx> {
                     // building `then` block
                     self.switch_block(then_basic_block);
 
-                    for stmt in then_block {
-                        self.compile_statement(stmt, function);
-                    }
+                    self.compile_scoped_block(then_block, function);
 
                     // building branch to merge point
-                    if then_basic_block.get_terminator().is_none() {
+                    if !self.is_block_terminated() {
                         let _ = self.builder.build_unconditional_branch(merge_basic_block);
                     }
 
                     // filling `else` block
                     self.switch_block(else_basic_block);
 
-                    for stmt in else_matched_block {
-                        self.compile_statement(stmt, function);
-                    }
+                    self.compile_scoped_block(else_matched_block, function);
 
                     // branch to merge block
 
-                    if let Some(last_instruction) = else_basic_block.get_last_instruction() {
-                        if last_instruction.get_opcode()
-                            != inkwell::values::InstructionOpcode::Return
-                        {
-                            let _ = self.builder.build_unconditional_branch(merge_basic_block);
-                        }
+                    if !self.is_block_terminated() {
+                        let _ = self.builder.build_unconditional_branch(merge_basic_block);
                     }
 
                     // and changing current builder position
@@ -721,17 +1059,11 @@ impl<'ctx> Compiler<'ctx> {
                     // building `then` block
                     self.switch_block(then_basic_block);
 
-                    for stmt in then_block {
-                        self.compile_statement(stmt, function);
-                    }
+                    self.compile_scoped_block(then_block, function);
 
                     // building branch to merge point
-                    if let Some(last_instruction) = then_basic_block.get_last_instruction() {
-                        if last_instruction.get_opcode()
-                            != inkwell::values::InstructionOpcode::Return
-                        {
-                            let _ = self.builder.build_unconditional_branch(merge_basic_block);
-                        }
+                    if !self.is_block_terminated() {
+                        let _ = self.builder.build_unconditional_branch(merge_basic_block);
                     }
 
                     // and changing current builder position
@@ -739,11 +1071,339 @@ impl<'ctx> Compiler<'ctx> {
                 }
             }
 
+            Statements::SwitchStatement {
+                value,
+                cases,
+                default_block,
+                line,
+                ..
+            } => {
+                let switch_value = self.compile_expression(value, line, function, None)?;
+
+                if !matches!(
+                    switch_value.0.as_str(),
+                    "int8" | "int16" | "int32" | "int64" | "char"
+                ) {
+                    return Err(self.error(
+                        format!(
+                            "Only integers and chars can be switched, but found `{}`!",
+                            switch_value.0
+                        ),
+                        ErrorType::TypeError,
+                        line,
+                    ));
+                }
+
+                // compiling cases values (they must be known on build)
+                let mut cases_values: Vec<(IntValue<'ctx>, BasicBlock<'ctx>)> = Vec::new();
+                let mut cases_blocks = Vec::new();
+
+                for (values, block) in cases {
+                    let case_basic_block = self.context.append_basic_block(function, "switch_case");
+
+                    for case_value in values {
+                        let compiled_value = self.compile_expression(
+                            case_value,
+                            line,
+                            function,
+                            Some(switch_value.0.clone()),
+                        )?;
+
+                        if compiled_value.0 != switch_value.0 {
+                            return Err(self.error(
+                                format!(
+                                    "Case value must be `{}` type, but found `{}`!",
+                                    switch_value.0, compiled_value.0
+                                ),
+                                ErrorType::TypeError,
+                                line,
+                            ));
+                        }
+
+                        let int_value = compiled_value.1.into_int_value();
+                        let constant = int_value.get_sign_extended_constant().ok_or_else(|| {
+                            self.error(
+                                "Case value must be a constant!",
+                                ErrorType::NotExpected,
+                                line,
+                            )
+                        })?;
+
+                        if cases_values
+                            .iter()
+                            .any(|(other, _)| other.get_sign_extended_constant() == Some(constant))
+                        {
+                            return Err(self.error(
+                                format!("Duplicate case value `{}` found in `switch`!", constant),
+                                ErrorType::NotExpected,
+                                line,
+                            ));
+                        }
+
+                        cases_values.push((int_value, case_basic_block));
+                    }
+
+                    cases_blocks.push((case_basic_block, block));
+                }
+
+                // creating blocks
+                let merge_basic_block = self.context.append_basic_block(function, "switch_merge");
+                let default_basic_block = match default_block {
+                    Some(_) => self
+                        .context
+                        .prepend_basic_block(merge_basic_block, "switch_default"),
+                    None => merge_basic_block,
+                };
+
+                // building switch instruction (LLVM turns dense cases into jump table)
+                let _ = self.builder.build_switch(
+                    switch_value.1.into_int_value(),
+                    default_basic_block,
+                    &cases_values,
+                );
+
+                for (case_basic_block, block) in cases_blocks {
+                    self.switch_block(case_basic_block);
+                    self.compile_scoped_block(block, function);
+
+                    // cases don't fall through
+                    if !self.is_block_terminated() {
+                        let _ = self.builder.build_unconditional_branch(merge_basic_block);
+                    }
+                }
+
+                if let Some(block) = default_block {
+                    self.switch_block(default_basic_block);
+                    self.compile_scoped_block(block, function);
+
+                    if !self.is_block_terminated() {
+                        let _ = self.builder.build_unconditional_branch(merge_basic_block);
+                    }
+                }
+
+                self.switch_block(merge_basic_block);
+            }
+            Statements::MatchStatement {
+                value, arms, line, ..
+            } => {
+                let matched_value = self.compile_expression(value, line, function, None)?;
+                let enumeration = self.enums.get(&matched_value.0).cloned().ok_or_else(|| {
+                    self.error(
+                        format!(
+                            "Only enumerations can be matched, but found `{}`!",
+                            matched_value.0
+                        ),
+                        ErrorType::TypeError,
+                        line,
+                    )
+                })?;
+
+                // validating arms
+                let mut covered_variants: Vec<&String> = Vec::new();
+                let mut has_default_arm = false;
+
+                for (variant, bindings, _) in arms.iter() {
+                    if has_default_arm {
+                        return Err(self.error(
+                            format!("Arm `{}` is unreachable after `_` arm!", variant),
+                            ErrorType::NotExpected,
+                            line,
+                        ));
+                    }
+
+                    if variant == "_" {
+                        if !bindings.is_empty() {
+                            return Err(self.error(
+                                "Default arm `_` cannot bind values!",
+                                ErrorType::NotExpected,
+                                line,
+                            ));
+                        }
+
+                        has_default_arm = true;
+                        continue;
+                    }
+
+                    let (_, payload) = enumeration.variant(variant).ok_or_else(|| {
+                        self.error(
+                            format!(
+                                "Enumeration `{}` has no variant `{}`!",
+                                enumeration.name, variant
+                            ),
+                            ErrorType::NotDefined,
+                            line,
+                        )
+                    })?;
+
+                    if covered_variants.contains(&variant) {
+                        return Err(self.error(
+                            format!("Variant `{}` is matched twice!", variant),
+                            ErrorType::NotExpected,
+                            line,
+                        ));
+                    }
+
+                    if bindings.len() != payload.len() {
+                        return Err(self.error(
+                            format!(
+                                "Variant `{}.{}` has {} values, but {} bindings found!",
+                                enumeration.name,
+                                variant,
+                                payload.len(),
+                                bindings.len()
+                            ),
+                            ErrorType::NotExpected,
+                            line,
+                        ));
+                    }
+
+                    covered_variants.push(variant);
+                }
+
+                if !has_default_arm {
+                    let missing_variants = enumeration
+                        .variants
+                        .iter()
+                        .filter(|(variant, _)| !covered_variants.contains(&variant))
+                        .map(|(variant, _)| format!("`{}`", variant))
+                        .collect::<Vec<String>>();
+
+                    if !missing_variants.is_empty() {
+                        return Err(self.error(
+                            format!(
+                                "Non-exhaustive match! Variants {} are not covered, add them or `_` arm",
+                                missing_variants.join(", ")
+                            ),
+                            ErrorType::NotExpected,
+                            line,
+                        ));
+                    }
+                }
+
+                // creating blocks
+                let enum_value = matched_value.1.into_struct_value();
+                let tag = self
+                    .builder
+                    .build_extract_value(enum_value, 0, "tag")
+                    .map_err(|_| {
+                        self.error(
+                            "Unable to get enumeration tag!",
+                            ErrorType::BuildError,
+                            line,
+                        )
+                    })?
+                    .into_int_value();
+
+                let default_basic_block =
+                    self.context.append_basic_block(function, "match_default");
+                let merge_basic_block = self.context.append_basic_block(function, "match_merge");
+
+                let mut cases = Vec::new();
+                let mut arms_blocks = Vec::new();
+
+                for (variant, bindings, block) in arms {
+                    if variant == "_" {
+                        arms_blocks.push((default_basic_block, variant, bindings, block));
+                        continue;
+                    }
+
+                    let (variant_tag, _) = enumeration.variant(&variant).unwrap_or_default();
+                    let arm_basic_block = self.context.append_basic_block(function, "match_arm");
+
+                    cases.push((
+                        self.context.i32_type().const_int(variant_tag as u64, false),
+                        arm_basic_block,
+                    ));
+                    arms_blocks.push((arm_basic_block, variant, bindings, block));
+                }
+
+                // building switch by tag
+                let _ = self.builder.build_switch(tag, default_basic_block, &cases);
+
+                if !has_default_arm {
+                    // all variants are covered
+                    self.switch_block(default_basic_block);
+                    let _ = self.builder.build_unreachable();
+                }
+
+                for (arm_basic_block, variant, bindings, block) in arms_blocks {
+                    self.switch_block(arm_basic_block);
+                    self.variables.enter();
+
+                    // binding payload values
+                    if !bindings.is_empty() {
+                        let (variant_tag, payload) =
+                            enumeration.variant(&variant).unwrap_or_default();
+                        let payload_value = self
+                            .builder
+                            .build_extract_value(enum_value, variant_tag + 1, &variant)
+                            .map_err(|_| {
+                                self.error(
+                                    format!("Unable to get `{}` variant payload!", variant),
+                                    ErrorType::BuildError,
+                                    line,
+                                )
+                            })?
+                            .into_struct_value();
+
+                        for (index, (binding, datatype)) in bindings.iter().zip(payload).enumerate()
+                        {
+                            if binding == "_" {
+                                continue;
+                            }
+
+                            let binding_type = self.get_storage_type(&datatype, line)?;
+                            let binding_value = self
+                                .builder
+                                .build_extract_value(payload_value, index as u32, binding)
+                                .map_err(|_| {
+                                    self.error(
+                                        format!("Unable to bind `{}` value!", binding),
+                                        ErrorType::BuildError,
+                                        line,
+                                    )
+                                })?;
+                            let alloca =
+                                self.builder
+                                    .build_alloca(binding_type, binding)
+                                    .map_err(|_| {
+                                        self.error(
+                                            format!(
+                                                "Unable to create alloca for `{}` binding!",
+                                                binding
+                                            ),
+                                            ErrorType::MemoryError,
+                                            line,
+                                        )
+                                    })?;
+
+                            let _ = self.builder.build_store(alloca, binding_value);
+
+                            self.variables.insert(
+                                binding.clone(),
+                                Variable::new(datatype, true, binding_type, alloca, None),
+                            );
+                        }
+                    }
+
+                    self.compile_block(block, function);
+                    self.variables.exit();
+
+                    // building branch to merge point
+                    if !self.is_block_terminated() {
+                        let _ = self.builder.build_unconditional_branch(merge_basic_block);
+                    }
+                }
+
+                self.switch_block(merge_basic_block);
+            }
+
             // NOTE: Cycles
             Statements::WhileStatement {
                 condition,
                 block,
                 line,
+                ..
             } => {
                 // creating basic blocks
                 let before_basic_block = self.context.append_basic_block(function, "while_before");
@@ -752,16 +1412,14 @@ impl<'ctx> Compiler<'ctx> {
 
                 // setting current position to block `before`
 
-                if let Some(last_instruction) = self.current_block.get_last_instruction() {
-                    if last_instruction.get_opcode() != inkwell::values::InstructionOpcode::Return {
-                        let _ = self.builder.build_unconditional_branch(before_basic_block);
-                    }
+                if !self.is_block_terminated() {
+                    let _ = self.builder.build_unconditional_branch(before_basic_block);
                 }
 
                 self.switch_block(before_basic_block);
 
                 // compiling condition
-                let compiled_condition = self.compile_condition(condition, line, function);
+                let compiled_condition = self.compile_condition(condition, line, function)?;
 
                 // building conditional branch to blocks
                 let _ = self.builder.build_conditional_branch(
@@ -772,16 +1430,15 @@ impl<'ctx> Compiler<'ctx> {
 
                 // building `then` block
                 self.switch_block(then_basic_block);
+                self.loop_blocks.push((before_basic_block, after_basic_block));
 
-                for stmt in block {
-                    self.compile_statement(stmt, function);
-                }
+                self.compile_scoped_block(block, function);
+
+                self.loop_blocks.pop();
 
                 // returning to block `before` for comparing condition
-                if let Some(last_instruction) = then_basic_block.get_last_instruction() {
-                    if last_instruction.get_opcode() != inkwell::values::InstructionOpcode::Return {
-                        let _ = self.builder.build_unconditional_branch(before_basic_block);
-                    }
+                if !self.is_block_terminated() {
+                    let _ = self.builder.build_unconditional_branch(before_basic_block);
                 }
 
                 // setting builder position to `after` block
@@ -794,27 +1451,29 @@ impl<'ctx> Compiler<'ctx> {
                 iterator,
                 block,
                 line,
+                ..
             } => {
                 // creating basic blocks
                 let before_basic_block = self.context.append_basic_block(function, "for_before");
                 let then_basic_block = self.context.append_basic_block(function, "for_then");
+                let iterator_basic_block =
+                    self.context.append_basic_block(function, "for_iterator");
                 let after_basic_block = self.context.append_basic_block(function, "for_after");
 
-                // building initializer
-                self.compile_statement(*initializer, function);
+                // building initializer in its own scope
+                self.variables.enter();
+                self.compile_statement(*initializer, function)?;
 
                 // setting current position to block `before`
 
-                if let Some(last_instruction) = self.current_block.get_last_instruction() {
-                    if last_instruction.get_opcode() != inkwell::values::InstructionOpcode::Return {
-                        let _ = self.builder.build_unconditional_branch(before_basic_block);
-                    }
+                if !self.is_block_terminated() {
+                    let _ = self.builder.build_unconditional_branch(before_basic_block);
                 }
 
                 self.switch_block(before_basic_block);
 
                 // building condition
-                let compiled_condition = self.compile_condition(condition, line, function);
+                let compiled_condition = self.compile_condition(condition, line, function)?;
 
                 // building conditional branch to blocks
                 let _ = self.builder.build_conditional_branch(
@@ -825,140 +1484,525 @@ impl<'ctx> Compiler<'ctx> {
 
                 // building `then` block
                 self.switch_block(then_basic_block);
+                self.loop_blocks.push((iterator_basic_block, after_basic_block));
 
-                for stmt in block {
-                    self.compile_statement(stmt, function);
+                self.compile_scoped_block(block, function);
+
+                self.loop_blocks.pop();
+
+                if !self.is_block_terminated() {
+                    let _ = self.builder.build_unconditional_branch(iterator_basic_block);
                 }
 
                 // building iterator
 
-                self.compile_statement(*iterator, function);
+                self.switch_block(iterator_basic_block);
+                self.compile_statement(*iterator, function)?;
 
                 // returning to block `before` for comparing condition
-                if let Some(last_instruction) = then_basic_block.get_last_instruction() {
-                    if last_instruction.get_opcode() != inkwell::values::InstructionOpcode::Return {
-                        let _ = self.builder.build_unconditional_branch(before_basic_block);
-                    }
+                if !self.is_block_terminated() {
+                    let _ = self.builder.build_unconditional_branch(before_basic_block);
                 }
 
+                // initializer variables live only inside the cycle
+                self.variables.exit();
+
                 // setting builder position to `after` block
                 self.switch_block(after_basic_block);
             }
 
-            Statements::BreakStatement { line } => {
-                GenError::throw(
-                    "`break` keyword is not supported yet.",
-                    ErrorType::NotSupported,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
-            }
-
-            // NOTE: Import
-            Statements::ImportStatement { path, line } => {
-                if let Expressions::Value(Value::String(stringified_path)) = path {
-                    // getting import object
-                    let obj = ImportObject::from(stringified_path);
+            Statements::ForInStatement {
+                variable,
+                iterable,
+                block,
+                line,
+                ..
+            } => {
+                // creating basic blocks
+                let before_basic_block = self.context.append_basic_block(function, "for_before");
+                let then_basic_block = self.context.append_basic_block(function, "for_then");
+                let iterator_basic_block =
+                    self.context.append_basic_block(function, "for_iterator");
+                let after_basic_block = self.context.append_basic_block(function, "for_after");
 
-                    // testing if import already exists
-                    if self.imports.contains_key(&obj.name) {
-                        GenError::throw(
-                            format!("Imported module `{}` already exists!", obj.name),
-                            ErrorType::ImportError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
-                            line,
-                        );
-                        std::process::exit(1);
-                    }
+                // map is iterated by snapshot of its keys, which is freed after cycle
+                let mut keys_snapshot = None;
+
+                // iterable is computed once, hidden counter walks through it
+                let (counter_type, counter_start, (iterable_type, iterable_value, inclusive)) =
+                    match iterable {
+                        Expressions::Range {
+                            start,
+                            end,
+                            inclusive,
+                            ..
+                        } => {
+                            let range_start =
+                                self.compile_expression(*start, line, function, None)?;
+
+                            if !matches!(
+                                range_start.0.as_str(),
+                                "int8" | "int16" | "int32" | "int64"
+                            ) {
+                                return Err(self.error(
+                                    format!(
+                                        "Range bounds must be integers, but found `{}`!",
+                                        range_start.0
+                                    ),
+                                    ErrorType::TypeError,
+                                    line,
+                                ));
+                            }
 
-                    // initializating lightweight compiler
-                    // lexer
-                    let mut lw_lexer = tpl_lexer::Lexer::new(obj.source.clone(), obj.name.clone());
-                    let tokens = match lw_lexer.tokenize() {
-                        Ok(tokens) => tokens,
-                        Err(e) => {
-                            let info = e.informate();
-                            eprintln!("{}", info);
-                            std::process::exit(1);
-                        }
-                    };
+                            let range_end = self.compile_expression(
+                                *end,
+                                line,
+                                function,
+                                Some(range_start.0.clone()),
+                            )?;
 
-                    // parser
-                    let mut parser =
-                        tpl_parser::Parser::new(tokens, obj.name.clone(), obj.source.clone());
-                    let ast = parser.parse();
+                            if range_end.0 != range_start.0 {
+                                return Err(self.error(
+                                format!(
+                                    "Range bounds must have the same type, but found `{}` and `{}`!",
+                                    range_start.0, range_end.0
+                                ),
+                                ErrorType::TypeError,
+                                line,
+                            ));
+                            }
 
-                    // compiling statements
+                            (
+                                range_start.1.get_type().into_int_type(),
+                                range_start.1.into_int_value(),
+                                (range_start.0, range_end.1, inclusive),
+                            )
+                        }
+                        other => {
+                            let mut object =
+                                self.compile_expression(other, line, function, None)?;
+
+                            if Compiler::__is_map_type(&object.0) {
+                                object = self.build_map_keys(
+                                    &object.0,
+                                    object.1.into_pointer_value(),
+                                    line,
+                                )?;
+                                keys_snapshot = Some(object.1.into_pointer_value());
+                            }
 
-                    match ast {
-                        Ok(stmts) => {
-                            for stmt in stmts {
-                                self.compile_statement(stmt, function);
+                            if object.0 != "str"
+                                && !Compiler::__is_arr_type(&object.0)
+                                && !Compiler::__is_slice_type(&object.0)
+                                && !Compiler::__is_list_type(&object.0)
+                            {
+                                return Err(self.error(
+                                    format!("Type `{}` is not iterable!", object.0),
+                                    ErrorType::TypeError,
+                                    line,
+                                ));
                             }
 
-                            // adding function to imported
-                            self.imports.insert(obj.name.clone(), obj);
-                        }
-                        Err(err) => {
-                            // printing all errors in terminal and quitting
-                            eprintln!("{}", err.informate());
-                            std::process::exit(1);
+                            let counter_type = self.context.i64_type();
+                            (
+                                counter_type,
+                                counter_type.const_zero(),
+                                (object.0, object.1, false),
+                            )
                         }
+                    };
+
+                let item_type = match iterable_type.as_str() {
+                    "str" => String::from("char"),
+                    array_type
+                        if Compiler::__is_arr_type(array_type)
+                            || Compiler::__is_slice_type(array_type) =>
+                    {
+                        Compiler::clean_array_datatype(array_type)
                     }
-                } else {
-                    GenError::throw(
-                        "Unexpected import found!",
-                        ErrorType::NotExpected,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
-                        line,
-                    );
-                    std::process::exit(1);
-                }
-            }
+                    list_type if Compiler::__is_list_type(list_type) => {
+                        Compiler::__list_element_type(list_type)
+                    }
+                    range_type => range_type.to_string(),
+                };
+                let item_basic_type = self.get_storage_type(&item_type, line)?;
 
-            Statements::Expression(expr) => match expr {
-                Expressions::SubElement {
-                    parent,
-                    child,
-                    line,
-                } => {
-                    self.compile_subelement(
-                        Expressions::SubElement {
-                            parent,
-                            child,
+                // loop variable and counter live only inside the cycle
+                self.variables.enter();
+
+                let counter = self
+                    .builder
+                    .build_alloca(counter_type, "for_counter")
+                    .map_err(|_| {
+                        self.error(
+                            "Unable to create alloca for cycle counter!",
+                            ErrorType::MemoryError,
                             line,
-                        },
-                        function,
-                    );
+                        )
+                    })?;
+                let item = self
+                    .builder
+                    .build_alloca(item_basic_type, &variable)
+                    .map_err(|_| {
+                        self.error(
+                            format!("Unable to create alloca for `{}` variable!", variable),
+                            ErrorType::MemoryError,
+                            line,
+                        )
+                    })?;
+
+                let _ = self.builder.build_store(counter, counter_start);
+
+                self.variables.insert(
+                    variable.clone(),
+                    Variable::new(item_type.clone(), true, item_basic_type, item, None),
+                );
+
+                if !self.is_block_terminated() {
+                    let _ = self.builder.build_unconditional_branch(before_basic_block);
+                }
+
+                // building condition and taking current item
+                self.switch_block(before_basic_block);
+
+                let counter_value = self
+                    .builder
+                    .build_load(counter_type, counter, "")
+                    .unwrap()
+                    .into_int_value();
+
+                let (compiled_condition, item_value) = match iterable_type.as_str() {
+                    "str" => {
+                        let char_type = self.context.i8_type();
+                        let char_pointer = unsafe {
+                            self.builder
+                                .build_in_bounds_gep(
+                                    char_type,
+                                    iterable_value.into_pointer_value(),
+                                    &[counter_value],
+                                    "",
+                                )
+                                .unwrap()
+                        };
+                        let char_value = self
+                            .builder
+                            .build_load(char_type, char_pointer, "")
+                            .unwrap()
+                            .into_int_value();
+
+                        // strings are iterated until terminating zero
+                        let condition = self
+                            .builder
+                            .build_int_compare(
+                                inkwell::IntPredicate::NE,
+                                char_value,
+                                char_type.const_zero(),
+                                "",
+                            )
+                            .unwrap();
+
+                        (condition, Some(char_value.as_basic_value_enum()))
+                    }
+                    array_type if Compiler::__is_arr_type(array_type) => {
+                        let array_len = Compiler::get_array_datatype_len(array_type);
+                        let condition = self
+                            .builder
+                            .build_int_compare(
+                                inkwell::IntPredicate::SLT,
+                                counter_value,
+                                counter_type.const_int(array_len, false),
+                                "",
+                            )
+                            .unwrap();
+
+                        // element is taken only after the bounds check
+                        (condition, None)
+                    }
+                    slice_type if Compiler::__is_slice_type(slice_type) => {
+                        let slice_len = self
+                            .builder
+                            .build_extract_value(iterable_value.into_struct_value(), 1, "")
+                            .map_err(|_| {
+                                self.error(
+                                    "Unable to get slice length!",
+                                    ErrorType::BuildError,
+                                    line,
+                                )
+                            })?;
+                        let condition = self
+                            .builder
+                            .build_int_compare(
+                                inkwell::IntPredicate::SLT,
+                                counter_value,
+                                slice_len.into_int_value(),
+                                "",
+                            )
+                            .unwrap();
+
+                        (condition, None)
+                    }
+                    list_type if Compiler::__is_list_type(list_type) => {
+                        // length is loaded every iteration, because list can be changed in cycle
+                        let list_len =
+                            self.build_list_len(iterable_value.into_pointer_value(), line)?;
+                        let condition = self
+                            .builder
+                            .build_int_compare(
+                                inkwell::IntPredicate::SLT,
+                                counter_value,
+                                list_len,
+                                "",
+                            )
+                            .unwrap();
+
+                        (condition, None)
+                    }
+                    _ => {
+                        let predicate = if inclusive {
+                            inkwell::IntPredicate::SLE
+                        } else {
+                            inkwell::IntPredicate::SLT
+                        };
+                        let condition = self
+                            .builder
+                            .build_int_compare(
+                                predicate,
+                                counter_value,
+                                iterable_value.into_int_value(),
+                                "",
+                            )
+                            .unwrap();
+
+                        (condition, Some(counter_value.as_basic_value_enum()))
+                    }
+                };
+
+                let _ = self.builder.build_conditional_branch(
+                    compiled_condition,
+                    then_basic_block,
+                    after_basic_block,
+                );
+
+                // building `then` block
+                self.switch_block(then_basic_block);
+
+                let item_value = match item_value {
+                    Some(value) => (item_type, value),
+                    None => {
+                        let (element_type, element_pointer) = self.array_element_pointer(
+                            (iterable_type, iterable_value),
+                            counter_value,
+                            line,
+                        )?;
+                        let value = self.build_value_load(&element_type, element_pointer, line)?;
+
+                        (element_type, value)
+                    }
+                };
+
+                self.build_value_store(item, &item_value, line)?;
+
+                self.loop_blocks
+                    .push((iterator_basic_block, after_basic_block));
+
+                self.compile_scoped_block(block, function);
+
+                self.loop_blocks.pop();
+
+                if !self.is_block_terminated() {
+                    let _ = self
+                        .builder
+                        .build_unconditional_branch(iterator_basic_block);
+                }
+
+                // building iterator
+                self.switch_block(iterator_basic_block);
+
+                // inclusive range stops on its end before increment, so the maximal
+                // value of counter type (`0..=127` for `int8`) doesn't overflow
+                if inclusive {
+                    let increment_basic_block =
+                        self.context.append_basic_block(function, "for_increment");
+                    let is_last = self
+                        .builder
+                        .build_int_compare(
+                            inkwell::IntPredicate::EQ,
+                            counter_value,
+                            iterable_value.into_int_value(),
+                            "",
+                        )
+                        .unwrap();
+
+                    let _ = self.builder.build_conditional_branch(
+                        is_last,
+                        after_basic_block,
+                        increment_basic_block,
+                    );
+
+                    self.switch_block(increment_basic_block);
+                }
+
+                let next_counter = self
+                    .builder
+                    .build_int_add(counter_value, counter_type.const_int(1, false), "")
+                    .unwrap();
+                let _ = self.builder.build_store(counter, next_counter);
+                let _ = self.builder.build_unconditional_branch(before_basic_block);
+
+                self.variables.exit();
+
+                // setting builder position to `after` block
+                self.switch_block(after_basic_block);
+
+                if let Some(keys_list) = keys_snapshot {
+                    self.build_list_free(keys_list, line)?;
+                }
+            }
+
+            Statements::BreakStatement { line, .. } => {
+                let (_, break_block) = *self.loop_blocks.last().ok_or_else(|| {
+                    self.error(
+                        "`break` keyword can be used only inside `while` or `for` cycle!",
+                        ErrorType::NotExpected,
+                        line,
+                    )
+                })?;
+
+                let _ = self.builder.build_unconditional_branch(break_block);
+
+                // statements after `break` are unreachable, so they get their own block
+                let unreachable_block = self.context.append_basic_block(function, "break_after");
+                self.switch_block(unreachable_block);
+            }
+
+            Statements::ContinueStatement { line, .. } => {
+                let (continue_block, _) = *self.loop_blocks.last().ok_or_else(|| {
+                    self.error(
+                        "`continue` keyword can be used only inside `while` or `for` cycle!",
+                        ErrorType::NotExpected,
+                        line,
+                    )
+                })?;
+
+                let _ = self.builder.build_unconditional_branch(continue_block);
+
+                // statements after `continue` are unreachable, so they get their own block
+                let unreachable_block =
+                    self.context.append_basic_block(function, "continue_after");
+                self.switch_block(unreachable_block);
+            }
+
+            // NOTE: Import
+            Statements::ImportStatement { path, line, .. } => {
+                if let Expressions::Value(Value::String(stringified_path), _) = path {
+                    // getting import object
+                    let obj = ImportObject::try_from(stringified_path)
+                        .map_err(|err| self.error(err, ErrorType::ImportError, line))?;
+
+                    // testing if import already exists
+                    if self.imports.contains_key(&obj.name) {
+                        return Err(self.error(
+                            format!("Imported module `{}` already exists!", obj.name),
+                            ErrorType::ImportError,
+                            line,
+                        ));
+                    }
+
+                    // every module gets its own id, nested imports included
+                    let file_id = self.next_file_id;
+                    self.next_file_id += 1;
+
+                    // initializating lightweight compiler
+                    // lexer
+                    let mut lw_lexer = tpl_lexer::Lexer::new(obj.source.clone(), obj.name.clone())
+                        .with_file_id(file_id);
+                    let tokens = lw_lexer.tokenize().map_err(|err| {
+                        self.error(
+                            format!(
+                                "Unable to tokenize `{}` module:\n{}",
+                                obj.name,
+                                err.informate()
+                            ),
+                            ErrorType::ImportError,
+                            line,
+                        )
+                    })?;
+
+                    // parser
+                    let mut parser =
+                        tpl_parser::Parser::new(tokens, obj.name.clone(), obj.source.clone());
+                    let stmts = parser.parse().map_err(|err| {
+                        self.error(
+                            format!(
+                                "Unable to parse `{}` module:\n{}",
+                                obj.name,
+                                err.informate()
+                            ),
+                            ErrorType::ImportError,
+                            line,
+                        )
+                    })?;
+
+                    // compiling statements with module's own name and source for diagnostics
+
+                    let old_module_name =
+                        std::mem::replace(&mut self.module_name, obj.name.clone());
+                    let old_module_source =
+                        std::mem::replace(&mut self.module_source, obj.source.clone());
+
+                    self.compile_block(stmts, function);
+
+                    self.module_name = old_module_name;
+                    self.module_source = old_module_source;
+
+                    // adding function to imported
+                    self.imports.insert(obj.name.clone(), obj);
+                } else {
+                    return Err(self.error(
+                        "Unexpected import found!",
+                        ErrorType::NotExpected,
+                        line,
+                    ));
+                }
+            }
+
+            Statements::Expression(expr) => match expr {
+                Expressions::SubElement {
+                    parent,
+                    child,
+                    line,
+                    span,
+                } => {
+                    self.compile_subelement(
+                        Expressions::SubElement {
+                            parent,
+                            child,
+                            line,
+                            span,
+                        },
+                        function,
+                    )?;
                 }
                 _ => {
-                    GenError::throw(
+                    return Err(self.error(
                         format!("Unsupported expression found! Please open issue with your code on Github! Debug data:\n{:#?}", expr),
                         ErrorType::NotSupported,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         0,
-                    );
-                    std::process::exit(1);
+                    ));
                 }
             },
 
             // NOTE: Not supported
             _ => {
-                GenError::throw(
+                return Err(self.error(
                     "Unsupported statement found! Please open issue with your code on Github!",
                     ErrorType::NotSupported,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     0,
-                );
-                std::process::exit(1);
+                ));
             }
         }
+
+        Ok(())
     }
 
     fn compile_expression(
@@ -967,177 +2011,92 @@ impl<'ctx> Compiler<'ctx> {
         line: usize,
         function: FunctionValue<'ctx>,
         expected_datatype: Option<String>,
-    ) -> (String, BasicValueEnum<'ctx>) {
-        match expr.clone() {
-            Expressions::Value(val) => self.compile_value(val, line, expected_datatype),
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        // errors inside of expression mark the expression itself, not the whole statement
+        let outer_span = self.current_span;
+        self.current_span = expr.span().or(outer_span);
+
+        let result = self.compile_expression_node(expr, line, function, expected_datatype);
+
+        self.current_span = outer_span;
+        result
+    }
+
+    fn compile_expression_node(
+        &mut self,
+        expr: Expressions,
+        line: usize,
+        function: FunctionValue<'ctx>,
+        expected_datatype: Option<String>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        Ok(match expr.clone() {
+            Expressions::Value(val, _) => self.compile_value(val, line, expected_datatype)?,
             Expressions::Call {
                 function_name,
                 arguments,
                 line,
+                ..
             } => {
                 // calling and taking value from user defined function
-                self.fn_call(function_name, arguments, line, function)
+                self.fn_call(function_name, arguments, line, function)?
             }
             Expressions::Lambda {
                 arguments,
                 statements,
                 ftype,
                 line,
-            } => {
-                let func = self.define_user_function(
-                    LAMBDA_NAME.to_string(),
-                    ftype.clone(),
-                    arguments,
-                    statements,
-                    line,
-                );
-
-                self.current_assign_function = Some(func);
-
-                (
-                    format!("fn<{}>", ftype),
-                    self.context.i8_type().const_zero().into(),
-                )
-            }
+                ..
+            } => self.build_closure(arguments, statements, ftype, line)?,
             Expressions::Slice {
                 object,
                 index,
                 line,
+                ..
             } => {
-                let obj = self.compile_expression(*object, line, function, expected_datatype);
-                let idx = self.compile_expression(*index, line, function, None);
+                let obj = self.compile_expression(*object, line, function, expected_datatype)?;
+                let idx = self.compile_expression(*index, line, function, None)?;
                 let int_index = match idx.0 {
                     itype if itype.starts_with("int") => idx.1.into_int_value(),
                     _ => {
-                        GenError::throw(
+                        return Err(self.error(
                             "Non-integer slice index found!",
                             ErrorType::TypeError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
+                        ));
                     }
                 };
 
-                match obj.0.as_str() {
-                    obj_type if Compiler::__is_ptr_type(obj_type) => {
-                        let raw_type = Compiler::__unwrap_ptr_type(obj_type);
-                        let raw_basic_type = self.get_basic_type(&raw_type, line);
-
-                        let ptr = unsafe {
-                            self.builder
-                                .build_in_bounds_gep(
-                                    raw_basic_type,
-                                    obj.1.into_pointer_value(),
-                                    &[int_index],
-                                    "",
-                                )
-                                .unwrap()
-                        };
-
-                        let value = self.builder.build_load(raw_basic_type, ptr, "").unwrap();
-
-                        (raw_type, value)
-                    }
-                    "str" => {
-                        let basic_type = self.context.i8_type();
-
-                        let ptr = unsafe {
-                            self.builder
-                                .build_in_bounds_gep(
-                                    basic_type,
-                                    obj.1.into_pointer_value(),
-                                    &[int_index],
-                                    "",
-                                )
-                                .unwrap()
-                        };
-
-                        let char_value = self.builder.build_load(basic_type, ptr, "").unwrap();
-
-                        (String::from("char"), char_value)
-                    }
-                    array_type if Compiler::__is_arr_type(array_type) => {
-                        let raw_type = Compiler::clean_array_datatype(array_type);
-                        let raw_len = Compiler::get_array_datatype_len(array_type);
-
-                        let raw_index = int_index.get_sign_extended_constant().unwrap_or(0);
-                        // if we cannot verify index on build, it will cause some bugs on runtime
-
-                        if raw_index > raw_len as i64 - 1 || raw_index < 0 && raw_index != 0 {
-                            GenError::throw(
-                                format!(
-                                    "Wrong array index found! Array len is {} but index is {}",
-                                    raw_len, raw_index
-                                ),
-                                ErrorType::NotExpected,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
-                                line,
-                            );
-                            std::process::exit(1);
-                        }
-
-                        let output_value = self
-                            .builder
-                            .build_extract_element(obj.1.into_vector_value(), int_index, "")
-                            .unwrap_or_else(|_| {
-                                GenError::throw(
-                                    "Unable to extract array element!",
-                                    ErrorType::BuildError,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
-                                    line,
-                                );
-                                std::process::exit(1);
-                            });
+                let (element_type, element_pointer) =
+                    self.array_element_pointer(obj, int_index, line)?;
+                let value = self.build_value_load(&element_type, element_pointer, line)?;
 
-                        (raw_type, output_value)
-                    }
-                    _ => {
-                        GenError::throw(
-                            format!("Unsupported slicing type found: {}", obj.0),
-                            ErrorType::NotSupported,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
-                            line,
-                        );
-                        std::process::exit(1);
-                    }
-                }
+                (element_type, value)
             }
-            Expressions::Reference { object, line } => {
+            Expressions::Reference { object, line, .. } => {
                 match *object {
-                    Expressions::Value(Value::Identifier(id)) => {
+                    Expressions::Value(Value::Identifier(id), _) => {
                         // referencing to a variable
 
-                        let variable = self.variables.get(&id).unwrap_or_else(|| {
-                            GenError::throw(
-                                format!("Variable `{}` is not defined!", id),
+                        let variable = self.variables.get(&id).ok_or_else(|| {
+                            self.error(
+                                self.undefined_variable_message(&id),
                                 ErrorType::NotDefined,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
                                 line,
-                            );
-                            std::process::exit(1);
-                        });
+                            )
+                        })?;
 
                         (format!("{}*", variable.str_type), variable.pointer.into())
                     }
                     _ => {
-                        GenError::throw(
+                        return Err(self.error(
                             "Unsupported expression for reference found",
                             ErrorType::NotSupported,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
+                        ));
                     }
                 }
             }
-            Expressions::Dereference { object, line } => {
+            Expressions::Dereference { object, line, .. } => {
                 let value = self.compile_expression(
                     *object,
                     line,
@@ -1145,21 +2104,18 @@ impl<'ctx> Compiler<'ctx> {
                     Some(
                         String::from("*"), // requesting raw pointer
                     ),
-                );
+                )?;
 
                 if !Compiler::__is_ptr_type(&value.0) {
-                    GenError::throw(
+                    return Err(self.error(
                         format!("Non pointer type `{}` cannot by dereferenced!", value.0),
                         ErrorType::TypeError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
+                    ));
                 }
 
                 let raw_type = Compiler::__unwrap_ptr_type(&value.0);
-                let raw_basic_type = self.get_basic_type(&raw_type, line);
+                let raw_basic_type = self.get_basic_type(&raw_type, line)?;
 
                 let ptr_value = value.1.into_pointer_value();
                 let ptr_type = self.context.ptr_type(AddressSpace::default());
@@ -1167,30 +2123,29 @@ impl<'ctx> Compiler<'ctx> {
                 let loaded_ptr = self
                     .builder
                     .build_load(ptr_type, ptr_value, "")
-                    .unwrap_or_else(|_| {
-                        GenError::throw(
+                    .map_err(|_| {
+                        self.error(
                             "Unable to load pointer for dereference!",
                             ErrorType::BuildError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
-                    });
+                        )
+                    })?;
+
+                // pointed array is used by its address
+                if Compiler::__is_arr_type(&raw_type) {
+                    return Ok((raw_type, loaded_ptr));
+                }
 
                 let loaded_value = self
                     .builder
                     .build_load(raw_basic_type, loaded_ptr.into_pointer_value(), "")
-                    .unwrap_or_else(|_| {
-                        GenError::throw(
+                    .map_err(|_| {
+                        self.error(
                             "Unable to load a pointer value for dereference!",
                             ErrorType::BuildError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
-                    });
+                        )
+                    })?;
 
                 // When we provide dereferenced value into function (for example print)
                 // it causes segmentation fault, but if we just copy that value by storing
@@ -1223,9 +2178,48 @@ impl<'ctx> Compiler<'ctx> {
                 lhs,
                 rhs,
                 line,
+                ..
             } => {
-                let left = self.compile_expression(*lhs, line, function, expected_datatype.clone());
-                let right = self.compile_expression(*rhs, line, function, expected_datatype);
+                let left =
+                    self.compile_expression(*lhs, line, function, expected_datatype.clone())?;
+                let right =
+                    self.compile_expression(*rhs, line, function, expected_datatype.clone())?;
+
+                // float (integer side is converted to float, `float64` side is never narrowed)
+                if Compiler::__is_float_type(&left.0) || Compiler::__is_float_type(&right.0) {
+                    let float_type = match expected_datatype {
+                        _ if left.0 == "float64" || right.0 == "float64" => "float64".to_string(),
+                        Some(exp) if Compiler::__is_float_type(&exp) => exp,
+                        _ => "float32".to_string(),
+                    };
+
+                    let lhs_value = self.build_float_cast(left, &float_type, line)?;
+                    let rhs_value = self.build_float_cast(right, &float_type, line)?;
+
+                    let result = match operand.as_str() {
+                        "+" => self.builder.build_float_add(lhs_value, rhs_value, "tmpadd"),
+                        "-" => self.builder.build_float_sub(lhs_value, rhs_value, "tmpsub"),
+                        "*" => self.builder.build_float_mul(lhs_value, rhs_value, "tmpmul"),
+                        "/" => self.builder.build_float_div(lhs_value, rhs_value, "tmpdiv"),
+                        "%" => self.builder.build_float_rem(lhs_value, rhs_value, "tmprem"),
+                        _ => {
+                            return Err(self.error(
+                                format!("Unsupported binary operation found: `{}`", operand),
+                                ErrorType::NotSupported,
+                                line,
+                            ));
+                        }
+                    }
+                    .map_err(|_| {
+                        self.error(
+                            "Unable to build float binary operation!",
+                            ErrorType::BuildError,
+                            line,
+                        )
+                    })?;
+
+                    return Ok((float_type, result.into()));
+                }
 
                 // matching types
                 match left.0.as_str() {
@@ -1326,27 +2320,41 @@ impl<'ctx> Compiler<'ctx> {
                                         .into(),
                                 )
                             }
-                            _ => {
-                                GenError::throw(
-                                    format!("Unsupported binary operation found: `{}`", operand),
-                                    ErrorType::NotSupported,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
-                                    line,
-                                );
-                                std::process::exit(1);
-                            }
-                        }
-                    }
-                    _ => {
-                        GenError::throw(
+                            "%" => {
+                                // signed remainder
+                                (
+                                    if let Some(exp_type) = self.current_expectation_value.clone() {
+                                        exp_type
+                                    } else if get_int_order(&left.0) > get_int_order(&right.0) {
+                                        left.0
+                                    } else {
+                                        right.0
+                                    },
+                                    self.builder
+                                        .build_int_signed_rem(
+                                            left.1.into_int_value(),
+                                            right.1.into_int_value(),
+                                            "tmprem",
+                                        )
+                                        .unwrap()
+                                        .into(),
+                                )
+                            }
+                            _ => {
+                                return Err(self.error(
+                                    format!("Unsupported binary operation found: `{}`", operand),
+                                    ErrorType::NotSupported,
+                                    line,
+                                ));
+                            }
+                        }
+                    }
+                    _ => {
+                        return Err(self.error(
                             format!("Binary operations is not supported for `{}` type!", left.0),
                             ErrorType::NotSupported,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
+                        ));
                     }
                 }
             }
@@ -1355,9 +2363,11 @@ impl<'ctx> Compiler<'ctx> {
                 lhs,
                 rhs,
                 line,
+                ..
             } => {
-                let left = self.compile_expression(*lhs, line, function, expected_datatype.clone());
-                let right = self.compile_expression(*rhs, line, function, expected_datatype);
+                let left =
+                    self.compile_expression(*lhs, line, function, expected_datatype.clone())?;
+                let right = self.compile_expression(*rhs, line, function, expected_datatype)?;
 
                 // matching types
                 match left.0.as_str() {
@@ -1428,25 +2438,67 @@ impl<'ctx> Compiler<'ctx> {
                                 .as_basic_value_enum(),
                         ),
                         _ => {
-                            GenError::throw(
+                            return Err(self.error(
                                 "Unsupported bitwise operator found! Please open issue on Github!",
                                 ErrorType::NotSupported,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
                                 line,
-                            );
-                            std::process::exit(1);
+                            ));
                         }
                     },
                     _ => {
-                        GenError::throw(
+                        return Err(self.error(
                             format!("Type `{}` is not supported for bitwise operations!", left.0),
                             ErrorType::NotSupported,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
+                        ));
+                    }
+                }
+            }
+            Expressions::Unary {
+                operand,
+                object,
+                line,
+                ..
+            } => {
+                if operand == "!" {
+                    // logical not works only with conditions
+                    let condition = self.compile_condition(expr.clone(), line, function)?;
+                    return Ok(("bool".to_string(), condition.into()));
+                }
+
+                let value = self.compile_expression(*object, line, function, expected_datatype)?;
+
+                match (operand.as_str(), value.0.as_str()) {
+                    ("-", "int8" | "int16" | "int32" | "int64") => (
+                        value.0,
+                        self.builder
+                            .build_int_neg(value.1.into_int_value(), "tmpneg")
+                            .unwrap()
+                            .into(),
+                    ),
+                    ("-", "float32" | "float64") => (
+                        value.0,
+                        self.builder
+                            .build_float_neg(value.1.into_float_value(), "tmpneg")
+                            .unwrap()
+                            .into(),
+                    ),
+                    ("~", "int8" | "int16" | "int32" | "int64") => (
+                        value.0,
+                        self.builder
+                            .build_not(value.1.into_int_value(), "tmpnot")
+                            .unwrap()
+                            .into(),
+                    ),
+                    _ => {
+                        return Err(self.error(
+                            format!(
+                                "Unary operator `{}` is not supported for `{}` type!",
+                                operand, value.0
+                            ),
+                            ErrorType::TypeError,
+                            line,
+                        ));
                     }
                 }
             }
@@ -1455,86 +2507,271 @@ impl<'ctx> Compiler<'ctx> {
                 lhs,
                 rhs,
                 line,
+                ..
             } => {
                 let _ = (operand, lhs, rhs); // 0_0
 
                 (
                     "bool".to_string(),
-                    self.compile_condition(expr.clone(), line, function).into(),
+                    self.compile_condition(expr.clone(), line, function)?.into(),
                 )
             }
             Expressions::SubElement {
                 parent,
                 child,
                 line,
+                span,
             } => self.compile_subelement(
                 Expressions::SubElement {
                     parent,
                     child,
                     line,
+                    span,
                 },
                 function,
-            ),
-            Expressions::Array { values, len, line } => {
+            )?,
+            Expressions::Struct {
+                name, fields, line, ..
+            } => {
+                let structure = self.structures.get(&name).cloned().ok_or_else(|| {
+                    self.error(
+                        format!("Structure `{}` is not defined!", name),
+                        ErrorType::NotDefined,
+                        line,
+                    )
+                })?;
+
+                for (index, (field, _)) in fields.iter().enumerate() {
+                    if structure.field(field).is_none() {
+                        return Err(self.error(
+                            format!("Structure `{}` has no field `{}`!", name, field),
+                            ErrorType::NotDefined,
+                            line,
+                        ));
+                    }
+
+                    if fields[..index].iter().any(|(other, _)| other == field) {
+                        return Err(self.error(
+                            format!("Field `{}` is initialized twice!", field),
+                            ErrorType::NotExpected,
+                            line,
+                        ));
+                    }
+                }
+
+                let mut struct_value = structure.struct_type.get_undef();
+
+                for (index, (field, field_type)) in structure.fields.iter().enumerate() {
+                    let value = fields
+                        .iter()
+                        .find(|(other, _)| other == field)
+                        .map(|(_, value)| value.clone())
+                        .ok_or_else(|| {
+                            self.error(
+                                format!(
+                                    "Field `{}` of `{}` structure is not initialized!",
+                                    field, name
+                                ),
+                                ErrorType::NoValue,
+                                line,
+                            )
+                        })?;
+
+                    let compiled_value =
+                        self.compile_expression(value, line, function, Some(field_type.clone()))?;
+                    let compiled_value = self.coerce_value(compiled_value, field_type, line)?;
+
+                    if compiled_value.0 != *field_type {
+                        return Err(self.error(
+                            format!(
+                                "Field `{}` has type `{}`, but found `{}`!",
+                                field, field_type, compiled_value.0
+                            ),
+                            ErrorType::TypeError,
+                            line,
+                        ));
+                    }
+
+                    let field_value = self.array_aggregate(&compiled_value, line)?;
+
+                    struct_value = self
+                        .builder
+                        .build_insert_value(struct_value, field_value, index as u32, field)
+                        .map_err(|_| {
+                            self.error(
+                                format!("Unable to initialize `{}` field!", field),
+                                ErrorType::BuildError,
+                                line,
+                            )
+                        })?
+                        .into_struct_value();
+                }
+
+                (name, struct_value.into())
+            }
+            Expressions::Range { line, .. } => {
+                return Err(self.error(
+                    "Ranges can be used only in `for` cycles!",
+                    ErrorType::NotExpected,
+                    line,
+                ));
+            }
+            Expressions::Array {
+                values, len, line, ..
+            } => {
+                // array literal expected as list initializes it
+                let list_expectation = expected_datatype
+                    .clone()
+                    .filter(|datatype| Compiler::__is_list_type(datatype));
+
+                if values.is_empty() {
+                    if let Some(list_type) = list_expectation {
+                        return self.build_list_new(&list_type, None, line);
+                    }
+
+                    return Err(self.error(
+                        "Empty arrays are not supported!",
+                        ErrorType::NotSupported,
+                        line,
+                    ));
+                }
+
+                // literal can be expected as the whole array or as its element
+                let element_expectation = match expected_datatype {
+                    Some(datatype)
+                        if Compiler::__is_arr_type(&datatype)
+                            || Compiler::__is_slice_type(&datatype) =>
+                    {
+                        Some(Compiler::clean_array_datatype(&datatype))
+                    }
+                    Some(datatype) if Compiler::__is_list_type(&datatype) => {
+                        Some(Compiler::__list_element_type(&datatype))
+                    }
+                    other => other,
+                };
+
                 let mut compiled_values = Vec::new();
                 for val in values {
                     let compiled =
-                        self.compile_expression(val, line, function, expected_datatype.clone());
+                        self.compile_expression(val, line, function, element_expectation.clone())?;
                     compiled_values.push(compiled);
                 }
 
                 let types: Vec<String> = compiled_values.iter().map(|x| x.0.clone()).collect();
-                let values: Vec<BasicValueEnum> = compiled_values.iter().map(|x| x.1).collect();
 
                 let arr_type = types[0].clone();
-                let arr_type_basic = match self.get_basic_type(&arr_type, line) {
-                    BasicTypeEnum::IntType(int) => int.vec_type(len as u32),
-                    BasicTypeEnum::PointerType(ptr) => ptr.vec_type(len as u32),
-                    _ => unreachable!(),
-                };
 
                 if !Compiler::validate_types(&types, arr_type.clone()) {
-                    GenError::throw(
+                    return Err(self.error(
                         format!(
                             "Array has type `{}`, but found: {}",
                             &arr_type,
                             types.join(", ")
                         ),
                         ErrorType::TypeError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
+                    ));
                 }
 
-                let expr_type = format!("{}[{}]", arr_type, len);
-                let mut expr_value = arr_type_basic.const_zero().as_basic_value_enum();
+                let expr_type = Compiler::array_datatype(&arr_type, len as u64);
+                let arr_type_basic = self.get_basic_type(&expr_type, line)?;
 
-                for (index, value) in values.iter().enumerate() {
-                    let index = self.context.i8_type().const_int(index as u64, false);
-                    expr_value = expr_value
-                        .into_vector_value()
-                        .const_insert_element(index, *value);
+                // array lives in memory and is used by its address
+                let alloca = self
+                    .builder
+                    .build_alloca(arr_type_basic, "array")
+                    .map_err(|_| {
+                        self.error("Unable to allocate array!", ErrorType::MemoryError, line)
+                    })?;
+
+                for (index, value) in compiled_values.iter().enumerate() {
+                    let index = self.context.i64_type().const_int(index as u64, false);
+                    let element_pointer = unsafe {
+                        self.builder
+                            .build_in_bounds_gep(
+                                arr_type_basic,
+                                alloca,
+                                &[self.context.i64_type().const_zero(), index],
+                                "",
+                            )
+                            .unwrap()
+                    };
+
+                    self.build_value_store(element_pointer, value, line)?;
                 }
 
-                (expr_type, expr_value)
+                match list_expectation {
+                    Some(list_type) if Compiler::__list_element_type(&list_type) == arr_type => {
+                        return self.build_list_new(&list_type, Some((alloca, len as u64)), line);
+                    }
+                    _ => (expr_type, alloca.into()),
+                }
             }
             _ => {
-                GenError::throw(
+                return Err(self.error(
                     format!("`{:?}` is not supported!", expr),
                     ErrorType::NotSupported,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     0,
-                );
-                std::process::exit(1);
+                ));
+            }
+        })
+    }
+
+    fn build_float_cast(
+        &self,
+        value: (String, BasicValueEnum<'ctx>),
+        target_type: &str,
+        line: usize,
+    ) -> CompileResult<FloatValue<'ctx>> {
+        let float_type = self.get_basic_type(target_type, line)?.into_float_type();
+
+        match value.0.as_str() {
+            "float32" | "float64" => {
+                self.builder
+                    .build_float_cast(value.1.into_float_value(), float_type, "")
+            }
+            "int8" | "int16" | "int32" | "int64" => {
+                self.builder
+                    .build_signed_int_to_float(value.1.into_int_value(), float_type, "")
+            }
+            _ => {
+                return Err(self.error(
+                    format!("Unable to use `{}` type as `{}`!", value.0, target_type),
+                    ErrorType::TypeError,
+                    line,
+                ));
             }
         }
+        .map_err(|_| {
+            self.error(
+                format!("Unable to convert `{}` to `{}`!", value.0, target_type),
+                ErrorType::BuildError,
+                line,
+            )
+        })
     }
 
     #[inline]
     fn clean_array_datatype(val: &str) -> String {
-        val.split("[").collect::<Vec<&str>>()[0].to_string()
+        // element type: `int32[3][4]` -> `int32[4]`, `int32[]` -> `int32`
+        match (val.find('['), val.find(']')) {
+            (Some(start), Some(end)) => format!("{}{}", &val[..start], &val[end + 1..]),
+            _ => val.to_string(),
+        }
+    }
+
+    #[inline]
+    fn array_datatype(element_type: &str, len: u64) -> String {
+        // outer length goes first: 3 elements of `int32[4]` are `int32[3][4]`
+        match element_type.find('[') {
+            Some(start) => format!(
+                "{}[{}]{}",
+                &element_type[..start],
+                len,
+                &element_type[start..]
+            ),
+            None => format!("{}[{}]", element_type, len),
+        }
     }
 
     #[inline]
@@ -1552,33 +2789,36 @@ impl<'ctx> Compiler<'ctx> {
         value: Value,
         line: usize,
         expected: Option<String>,
-    ) -> (String, BasicValueEnum<'ctx>) {
-        match value {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        Ok(match value {
             Value::Integer(i) => {
                 if let Some(exp) = expected {
+                    if Compiler::__is_float_type(&exp) {
+                        // integer literal in float context
+                        let basic_type = self.get_basic_type(exp.as_str(), line)?.into_float_type();
+                        return Ok((exp, basic_type.const_float(i as f64).into()));
+                    }
+
                     if exp != "void" {
                         let unwrapped_type = Compiler::__unwrap_ptr_type(&exp);
-                        let basic_type = self.get_basic_type(exp.as_str(), line).into_int_type();
-                        let avaible_type = self.compile_value(Value::Integer(i), line, None);
+                        let basic_type = self.get_basic_type(exp.as_str(), line)?.into_int_type();
+                        let avaible_type = self.compile_value(Value::Integer(i), line, None)?;
 
                         if get_int_order(&avaible_type.0) > get_int_order(&unwrapped_type) {
-                            GenError::throw(
+                            return Err(self.error(
                                 format!(
                                     "Unable to compile `{}` value on `{}` type!",
                                     avaible_type.0, exp
                                 ),
                                 ErrorType::TypeError,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
                                 line,
-                            );
-                            std::process::exit(1)
+                            ));
                         }
 
-                        return (
+                        return Ok((
                             unwrapped_type.to_string(),
                             basic_type.const_int(i as u64, true).into(),
-                        );
+                        ));
                     }
                 }
 
@@ -1601,28 +2841,27 @@ impl<'ctx> Compiler<'ctx> {
                     ),
                 }
             }
+            Value::Float(f) => {
+                if expected.as_deref() == Some("float32") {
+                    (
+                        "float32".to_string(),
+                        self.context.f32_type().const_float(f).into(),
+                    )
+                } else {
+                    (
+                        "float64".to_string(),
+                        self.context.f64_type().const_float(f).into(),
+                    )
+                }
+            }
             Value::Boolean(b) => (
                 "bool".to_string(),
                 self.context.bool_type().const_int(b as u64, false).into(),
             ),
-            Value::String(str) => {
-                let str_val = self
-                    .builder
-                    .build_global_string_ptr(&str, "str")
-                    .unwrap_or_else(|_| {
-                        GenError::throw(
-                            format!("Error while creating string: `{}`", str),
-                            ErrorType::MemoryError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
-                            line,
-                        );
-                        std::process::exit(1);
-                    });
-
-                str_val.set_constant(false);
-                ("str".to_string(), str_val.as_pointer_value().into())
-            }
+            Value::String(str) => (
+                "str".to_string(),
+                self.build_str_literal(&str, "str").into(),
+            ),
             Value::Char(ch) => (
                 "char".to_string(),
                 self.context.i8_type().const_int(ch as u64, false).into(),
@@ -1630,45 +2869,38 @@ impl<'ctx> Compiler<'ctx> {
             Value::Identifier(id) => {
                 if let Some(var_ptr) = self.variables.get(&id) {
                     if !var_ptr.assigned {
-                        GenError::throw(
+                        return Err(self.error(
                             format!("No value assigned to `{}` variable!", id),
                             ErrorType::NoValue,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
-                            line
-                        );
-                        std::process::exit(1);
+                            line,
+                        ));
                     }
 
                     let exp = expected.unwrap_or_default();
 
-                    let value = if Compiler::__is_ptr_type(&exp) {
+                    // raw pointer to the variable is requested by dereference,
+                    // fixed arrays are always used by their address
+                    let value = if exp == "*" || Compiler::__is_arr_type(&var_ptr.str_type) {
                         var_ptr.pointer.into()
                     } else {
                         self.builder
                             .build_load(var_ptr.basic_type, var_ptr.pointer, "")
-                            .unwrap_or_else(|_| {
-                                GenError::throw(
+                            .map_err(|_| {
+                                self.error(
                                     format!("Error with loading `{}` variable", id),
                                     ErrorType::MemoryError,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
                                     line,
-                                );
-                                std::process::exit(1);
-                            })
+                                )
+                            })?
                     };
 
                     (var_ptr.str_type.clone(), value)
                 } else {
-                    GenError::throw(
-                        format!("Undefined variable with id: `{}`!", id),
+                    return Err(self.error(
+                        self.undefined_variable_message(&id),
                         ErrorType::NotDefined,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
+                    ));
                 }
             }
             Value::Keyword(word) => {
@@ -1678,14 +2910,11 @@ impl<'ctx> Compiler<'ctx> {
                         self.context.bool_type().const_zero().into()
                     ),
                     _ => {
-                        GenError::throw(
+                        return Err(self.error(
                             format!("Unsupported value with keyword `{}` found!", word),
                             ErrorType::NotSupported,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
-                            line
-                        );
-                        std::process::exit(1);
+                            line,
+                        ));
                     }
                 }
             }
@@ -1699,55 +2928,638 @@ impl<'ctx> Compiler<'ctx> {
             //     );
             //     std::process::exit(1);
             // }
-        }
+        })
     }
 
     fn compile_subelement(
         &mut self,
         subelement: Expressions,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
-        match subelement {
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        Ok(match subelement {
             Expressions::SubElement {
                 parent,
                 child,
                 line,
+                span,
             } => {
-                match *child {
+                // enumeration variant: `Shape.Circle(5)` or `Shape.Empty`
+                if let Expressions::Value(Value::Identifier(name), _) = parent.as_ref() {
+                    if self.enums.contains_key(name) && self.variables.get(name).is_none() {
+                        return self.compile_enum_variant(name.clone(), *child, line, function);
+                    }
+                }
+
+                let (parent, child) = Compiler::rotate_subelement(*parent, *child, line, span);
+
+                match child {
                     Expressions::Call {
                         function_name,
                         arguments,
                         line,
+                        span,
                     } => {
+                        // methods which take pointer to structure get variable by reference
+                        let takes_pointer = self
+                            .functions
+                            .get(&function_name)
+                            .and_then(|func| func.arguments_types.first())
+                            .is_some_and(|first_type| Compiler::__is_ptr_type(first_type));
+
+                        let parent = match parent {
+                            Expressions::Value(Value::Identifier(id), id_span)
+                                if takes_pointer
+                                    && self.variables.get(&id).is_some_and(|var| {
+                                        self.structures.contains_key(&var.str_type)
+                                    }) =>
+                            {
+                                Expressions::Reference {
+                                    object: Box::new(Expressions::Value(
+                                        Value::Identifier(id),
+                                        id_span,
+                                    )),
+                                    line,
+                                    span,
+                                }
+                            }
+                            parent => parent,
+                        };
+
                         // inserting parent as a first argument
-                        let modified_args = [vec![*parent], arguments].concat();
-                        let call = self.fn_call(function_name, modified_args, line, function);
+                        let modified_args = [vec![parent], arguments].concat();
+                        let call = self.fn_call(function_name, modified_args, line, function)?;
 
                         call
                     }
+                    Expressions::Value(Value::Identifier(field), field_span) => {
+                        // reading structure field
+                        let (field_type, field_ptr) = self.struct_field_pointer(
+                            Expressions::SubElement {
+                                parent: Box::new(parent),
+                                child: Box::new(Expressions::Value(
+                                    Value::Identifier(field.clone()),
+                                    field_span,
+                                )),
+                                line,
+                                span,
+                            },
+                            line,
+                            function,
+                        )?;
+
+                        let value = self.build_value_load(&field_type, field_ptr, line)?;
+                        (field_type, value)
+                    }
                     _ => {
-                        GenError::throw(
+                        return Err(self.error(
                             "Unsupported subelement found! Please open issue on github repo for bug report!",
                             ErrorType::TypeError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
-                            line
-                        );
-                        std::process::exit(1);
+                            line,
+                        ));
                     }
                 }
             }
             _ => {
-                GenError::throw(
+                return Err(self.error(
                     "`compile_subelement` takes only 'SubElement' expression!",
                     ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     0,
-                );
-                std::process::exit(1);
+                ));
             }
+        })
+    }
+
+    fn compile_enum_variant(
+        &mut self,
+        enum_name: String,
+        variant: Expressions,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let enumeration = self.enums[&enum_name].clone();
+
+        let (variant, arguments) = match variant {
+            Expressions::Call {
+                function_name,
+                arguments,
+                ..
+            } => (function_name, arguments),
+            Expressions::Value(Value::Identifier(variant), _) => (variant, Vec::new()),
+            _ => {
+                return Err(self.error(
+                    format!("Unexpected `{}` enumeration variant found!", enum_name),
+                    ErrorType::NotExpected,
+                    line,
+                ));
+            }
+        };
+
+        let (tag, payload) = enumeration.variant(&variant).ok_or_else(|| {
+            self.error(
+                format!("Enumeration `{}` has no variant `{}`!", enum_name, variant),
+                ErrorType::NotDefined,
+                line,
+            )
+        })?;
+
+        if arguments.len() != payload.len() {
+            return Err(self.error(
+                format!(
+                    "Variant `{}.{}` takes {} values, but {} found!",
+                    enum_name,
+                    variant,
+                    payload.len(),
+                    arguments.len()
+                ),
+                ErrorType::NotExpected,
+                line,
+            ));
         }
+
+        // filling payload of the variant
+        let mut payload_value = enumeration
+            .enum_type
+            .get_field_type_at_index(tag + 1)
+            .ok_or_else(|| {
+                self.error(
+                    format!("Unable to get `{}` variant payload!", variant),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?
+            .into_struct_type()
+            .get_undef();
+
+        for (index, (argument, datatype)) in arguments.into_iter().zip(payload).enumerate() {
+            let compiled_argument =
+                self.compile_expression(argument, line, function, Some(datatype.clone()))?;
+            let compiled_argument = self.coerce_value(compiled_argument, &datatype, line)?;
+
+            if compiled_argument.0 != datatype {
+                return Err(self.error(
+                    format!(
+                        "Variant `{}.{}` expected `{}` type, but found `{}`!",
+                        enum_name, variant, datatype, compiled_argument.0
+                    ),
+                    ErrorType::TypeError,
+                    line,
+                ));
+            }
+
+            let argument_value = self.array_aggregate(&compiled_argument, line)?;
+
+            payload_value = self
+                .builder
+                .build_insert_value(payload_value, argument_value, index as u32, "")
+                .map_err(|_| {
+                    self.error(
+                        "Unable to build variant payload!",
+                        ErrorType::BuildError,
+                        line,
+                    )
+                })?
+                .into_struct_value();
+        }
+
+        // tag and payload
+        let tag_value = self.context.i32_type().const_int(tag as u64, false);
+        let enum_value = enumeration.enum_type.const_zero();
+
+        let enum_value = self
+            .builder
+            .build_insert_value(enum_value, tag_value, 0, "")
+            .and_then(|value| {
+                self.builder
+                    .build_insert_value(value, payload_value, tag + 1, &variant)
+            })
+            .map_err(|_| {
+                self.error(
+                    format!("Unable to build `{}` variant!", variant),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?;
+
+        Ok((enum_name, enum_value.as_basic_value_enum()))
+    }
+
+    #[inline]
+    fn rotate_subelement(
+        parent: Expressions,
+        child: Expressions,
+        line: usize,
+        span: Span,
+    ) -> (Expressions, Expressions) {
+        // `a.b.c` is parsed as `a.(b.c)`, so chain is rebuilt from the left: `(a.b).c`
+        match child {
+            Expressions::SubElement {
+                parent: inner_parent,
+                child: inner_child,
+                ..
+            } => {
+                let parent = Expressions::SubElement {
+                    parent: Box::new(parent),
+                    child: inner_parent,
+                    line,
+                    span,
+                };
+
+                Compiler::rotate_subelement(parent, *inner_child, line, span)
+            }
+            child => (parent, child),
+        }
+    }
+
+    fn struct_pointer(
+        &mut self,
+        object: Expressions,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(Structure<'ctx>, PointerValue<'ctx>)> {
+        // getting type of the object and pointer to its storage
+        let (datatype, address) = match object {
+            Expressions::Value(Value::Identifier(id), _) => {
+                let variable = self.variables.get(&id).ok_or_else(|| {
+                    self.error(
+                        self.undefined_variable_message(&id),
+                        ErrorType::NotDefined,
+                        line,
+                    )
+                })?;
+
+                (variable.str_type.clone(), variable.pointer)
+            }
+            Expressions::SubElement { .. } => self.struct_field_pointer(object, line, function)?,
+            _ => {
+                // temporary values are stored to get their address
+                let value = self.compile_expression(object, line, function, None)?;
+                let alloca = self
+                    .builder
+                    .build_alloca(self.get_storage_type(&value.0, line)?, "")
+                    .map_err(|_| {
+                        self.error(
+                            "Unable to allocate temporary value!",
+                            ErrorType::MemoryError,
+                            line,
+                        )
+                    })?;
+
+                let _ = self.builder.build_store(alloca, value.1);
+                (value.0, alloca)
+            }
+        };
+
+        if let Some(structure) = self.structures.get(&datatype) {
+            return Ok((structure.clone(), address));
+        }
+
+        // pointers to structures are dereferenced automatically
+        match self
+            .structures
+            .get(&Compiler::__unwrap_ptr_type(&datatype))
+            .cloned()
+        {
+            Some(structure) if Compiler::__is_ptr_type(&datatype) => {
+                let pointer = self
+                    .builder
+                    .build_load(self.context.ptr_type(AddressSpace::default()), address, "")
+                    .map_err(|_| {
+                        self.error(
+                            "Unable to load pointer to structure!",
+                            ErrorType::MemoryError,
+                            line,
+                        )
+                    })?;
+
+                Ok((structure, pointer.into_pointer_value()))
+            }
+            _ => Err(self.error(
+                format!("Type `{}` has no fields!", datatype),
+                ErrorType::TypeError,
+                line,
+            )),
+        }
+    }
+
+    fn struct_field_pointer(
+        &mut self,
+        target: Expressions,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, PointerValue<'ctx>)> {
+        let Expressions::SubElement {
+            parent,
+            child,
+            line,
+            span,
+        } = target
+        else {
+            return Err(self.error(
+                "Field access expected, but found anything else!",
+                ErrorType::NotExpected,
+                line,
+            ));
+        };
+
+        let (parent, child) = Compiler::rotate_subelement(*parent, *child, line, span);
+
+        let Expressions::Value(Value::Identifier(field), _) = child else {
+            return Err(self.error(
+                "Only structure fields can be assigned!",
+                ErrorType::NotSupported,
+                line,
+            ));
+        };
+
+        let (structure, address) = self.struct_pointer(parent, line, function)?;
+        let (index, field_type) = structure.field(&field).ok_or_else(|| {
+            self.error(
+                format!("Structure `{}` has no field `{}`!", structure.name, field),
+                ErrorType::NotDefined,
+                line,
+            )
+        })?;
+
+        let field_ptr = self
+            .builder
+            .build_struct_gep(structure.struct_type, address, index, &field)
+            .map_err(|_| {
+                self.error(
+                    format!("Unable to get pointer to `{}` field!", field),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?;
+
+        Ok((field_type, field_ptr))
+    }
+
+    fn array_element_pointer(
+        &mut self,
+        object: (String, BasicValueEnum<'ctx>),
+        index: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<(String, PointerValue<'ctx>)> {
+        let index_type = self.context.i64_type();
+        let index = self
+            .builder
+            .build_int_cast_sign_flag(index, index_type, true, "")
+            .map_err(|_| self.error("Unable to cast index!", ErrorType::BuildError, line))?;
+
+        let (element_type, element_basic_type, elements, indexes) = match object.0.as_str() {
+            list_type if Compiler::__is_list_type(list_type) => {
+                return self.list_element_pointer(object, index, line);
+            }
+            array_type if Compiler::__is_arr_type(array_type) => {
+                let array_len = Compiler::get_array_datatype_len(array_type);
+
+                // constant indexes are checked on build, others on runtime
+                if let Some(raw_index) = index.get_sign_extended_constant() {
+                    if raw_index < 0 || raw_index >= array_len as i64 {
+                        return Err(self.error(
+                            format!(
+                                "Wrong array index found! Array len is {} but index is {}",
+                                array_len, raw_index
+                            ),
+                            ErrorType::NotExpected,
+                            line,
+                        ));
+                    }
+                } else {
+                    self.build_bounds_check(index, index_type.const_int(array_len, false), line)?;
+                }
+
+                (
+                    Compiler::clean_array_datatype(array_type),
+                    self.get_basic_type(array_type, line)?,
+                    object.1.into_pointer_value(),
+                    vec![index_type.const_zero(), index],
+                )
+            }
+            slice_type if Compiler::__is_slice_type(slice_type) => {
+                let element_type = Compiler::clean_array_datatype(slice_type);
+                let elements = self
+                    .builder
+                    .build_extract_value(object.1.into_struct_value(), 0, "")
+                    .map_err(|_| {
+                        self.error("Unable to get slice elements!", ErrorType::BuildError, line)
+                    })?;
+                let slice_len = self
+                    .builder
+                    .build_extract_value(object.1.into_struct_value(), 1, "")
+                    .map_err(|_| {
+                        self.error("Unable to get slice length!", ErrorType::BuildError, line)
+                    })?;
+
+                self.build_bounds_check(index, slice_len.into_int_value(), line)?;
+
+                (
+                    element_type.clone(),
+                    self.get_storage_type(&element_type, line)?,
+                    elements.into_pointer_value(),
+                    vec![index],
+                )
+            }
+            pointer_type if Compiler::__is_ptr_type(pointer_type) => {
+                let raw_type = Compiler::__unwrap_ptr_type(pointer_type);
+
+                (
+                    raw_type.clone(),
+                    self.get_storage_type(&raw_type, line)?,
+                    object.1.into_pointer_value(),
+                    vec![index],
+                )
+            }
+            "str" => {
+                if self.bounds_checks {
+                    let string_len = self.__str_len(object.1.into_pointer_value());
+                    self.build_bounds_check(index, string_len, line)?;
+                }
+
+                (
+                    String::from("char"),
+                    self.context.i8_type().into(),
+                    object.1.into_pointer_value(),
+                    vec![index],
+                )
+            }
+            _ => {
+                return Err(self.error(
+                    format!("Unsupported slicing type found: {}", object.0),
+                    ErrorType::NotSupported,
+                    line,
+                ));
+            }
+        };
+
+        let pointer = unsafe {
+            self.builder
+                .build_in_bounds_gep(element_basic_type, elements, &indexes, "")
+                .map_err(|_| {
+                    self.error(
+                        "Unable to get pointer to element!",
+                        ErrorType::BuildError,
+                        line,
+                    )
+                })?
+        };
+
+        Ok((element_type, pointer))
+    }
+
+    fn build_value_load(
+        &self,
+        datatype: &str,
+        pointer: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<BasicValueEnum<'ctx>> {
+        // fixed arrays are never loaded, their address is used as value
+        if Compiler::__is_arr_type(datatype) {
+            return Ok(pointer.into());
+        }
+
+        let basic_type = self.get_storage_type(datatype, line)?;
+
+        self.builder
+            .build_load(basic_type, pointer, "")
+            .map_err(|_| {
+                self.error(
+                    format!("Unable to load `{}` value!", datatype),
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })
+    }
+
+    fn build_value_store(
+        &self,
+        pointer: PointerValue<'ctx>,
+        value: &(String, BasicValueEnum<'ctx>),
+        line: usize,
+    ) -> CompileResult<()> {
+        if !Compiler::__is_arr_type(&value.0) {
+            let _ = self.builder.build_store(pointer, value.1);
+            return Ok(());
+        }
+
+        // fixed arrays are copied from memory to memory
+        let size = self
+            .get_basic_type(&value.0, line)?
+            .size_of()
+            .ok_or_else(|| {
+                self.error(
+                    format!("Unable to get size of `{}` type!", value.0),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?;
+
+        self.builder
+            .build_memcpy(pointer, 1, value.1.into_pointer_value(), 1, size)
+            .map(|_| ())
+            .map_err(|_| {
+                self.error(
+                    format!("Unable to copy `{}` array!", value.0),
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })
+    }
+
+    fn array_aggregate(
+        &self,
+        value: &(String, BasicValueEnum<'ctx>),
+        line: usize,
+    ) -> CompileResult<BasicValueEnum<'ctx>> {
+        // whole array is loaded when it's passed by value (arguments, fields, returns)
+        if !Compiler::__is_arr_type(&value.0) {
+            return Ok(value.1);
+        }
+
+        self.builder
+            .build_load(
+                self.get_basic_type(&value.0, line)?,
+                value.1.into_pointer_value(),
+                "",
+            )
+            .map_err(|_| {
+                self.error(
+                    format!("Unable to load `{}` array!", value.0),
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })
+    }
+
+    fn array_from_aggregate(
+        &self,
+        value: (String, BasicValueEnum<'ctx>),
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        // arrays returned by value are stored to get their address
+        if !Compiler::__is_arr_type(&value.0) {
+            return Ok(value);
+        }
+
+        let alloca = self
+            .builder
+            .build_alloca(self.get_basic_type(&value.0, line)?, "array")
+            .map_err(|_| {
+                self.error(
+                    "Unable to allocate temporary array!",
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })?;
+
+        let _ = self.builder.build_store(alloca, value.1);
+        Ok((value.0, alloca.into()))
+    }
+
+    fn coerce_value(
+        &self,
+        value: (String, BasicValueEnum<'ctx>),
+        expected_type: &str,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        // function with signature is accepted where only return type is known:
+        // `fn<int32(int32)>` -> `fn<int32>`
+        if Compiler::__is_fn_type(expected_type)
+            && Compiler::__is_fn_type(&value.0)
+            && Compiler::__fn_signature(expected_type)
+                == (Compiler::__fn_signature(&value.0).0, None)
+        {
+            return Ok((expected_type.to_string(), value.1));
+        }
+
+        // fixed array is viewed as slice where slice is expected: `int32[3]` -> `int32[]`
+        if !Compiler::__is_slice_type(expected_type)
+            || !Compiler::__is_arr_type(&value.0)
+            || Compiler::clean_array_datatype(expected_type)
+                != Compiler::clean_array_datatype(&value.0)
+        {
+            return Ok(value);
+        }
+
+        let slice_type = self.get_basic_type(expected_type, line)?.into_struct_type();
+        let array_len = self
+            .context
+            .i64_type()
+            .const_int(Compiler::get_array_datatype_len(&value.0), false);
+
+        let slice = self
+            .builder
+            .build_insert_value(slice_type.get_undef(), value.1, 0, "")
+            .and_then(|slice| self.builder.build_insert_value(slice, array_len, 1, ""))
+            .map_err(|_| {
+                self.error(
+                    format!("Unable to create `{}` slice!", expected_type),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?;
+
+        Ok((expected_type.to_string(), slice.as_basic_value_enum()))
     }
 
     fn compile_condition(
@@ -1755,49 +3567,44 @@ impl<'ctx> Compiler<'ctx> {
         condition: Expressions,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> IntValue<'ctx> {
-        match condition {
+    ) -> CompileResult<IntValue<'ctx>> {
+        Ok(match condition {
             Expressions::Boolean {
                 operand,
                 lhs,
                 rhs,
                 line,
+                ..
             } => {
                 match operand.as_str() {
                     "&&" => {
-                        let left_condition = self.compile_condition(*lhs, line, function);
-                        let right_condition = self.compile_condition(*rhs, line, function);
+                        let left_condition = self.compile_condition(*lhs, line, function)?;
+                        let right_condition = self.compile_condition(*rhs, line, function)?;
 
                         return self
                             .builder
                             .build_and(left_condition, right_condition, "and_cmp")
-                            .unwrap_or_else(|_| {
-                                GenError::throw(
+                            .map_err(|_| {
+                                self.error(
                                     "Unable to build AND comparison!",
                                     ErrorType::BuildError,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
                                     line,
-                                );
-                                std::process::exit(1);
+                                )
                             });
                     }
                     "||" => {
-                        let left_condition = self.compile_condition(*lhs, line, function);
-                        let right_condition = self.compile_condition(*rhs, line, function);
+                        let left_condition = self.compile_condition(*lhs, line, function)?;
+                        let right_condition = self.compile_condition(*rhs, line, function)?;
 
                         return self
                             .builder
                             .build_or(left_condition, right_condition, "and_cmp")
-                            .unwrap_or_else(|_| {
-                                GenError::throw(
+                            .map_err(|_| {
+                                self.error(
                                     "Unable to build OR comparison!",
                                     ErrorType::BuildError,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
                                     line,
-                                );
-                                std::process::exit(1);
+                                )
                             });
                     }
                     _ => {}
@@ -1808,10 +3615,10 @@ impl<'ctx> Compiler<'ctx> {
                     line,
                     function,
                     self.current_expectation_value.clone(),
-                );
+                )?;
 
                 // fix different size type comparison
-                let _old_exp_value = self.current_expectation_value.clone();
+                let old_expectation_value = self.current_expectation_value.clone();
                 self.current_expectation_value = Some(left.0.clone());
 
                 let right = self.compile_expression(
@@ -1821,81 +3628,124 @@ impl<'ctx> Compiler<'ctx> {
                     self.current_expectation_value.clone(),
                 );
 
+                self.current_expectation_value = old_expectation_value;
+                let right = right?;
+
                 // matching same supported types
                 match (left.0.as_str(), right.0.as_str()) {
-                    ("null", "null") => {
-                        self.context.bool_type().const_int(1, false)
-                    }
+                    ("null", "null") => match operand.as_str() {
+                        "==" => self.context.bool_type().const_int(1, false),
+                        "!=" => self.context.bool_type().const_zero(),
+                        _ => {
+                            return Err(self.error(
+                                format!(
+                                    "Operand `{}` is not supported for `null` checker!",
+                                    operand
+                                ),
+                                ErrorType::NotSupported,
+                                line,
+                            ));
+                        }
+                    },
+                    (ltype, rtype) if ltype == "null" || rtype == "null" => {
+                        let (value_type, value) = if ltype == "null" {
+                            (rtype, right.1)
+                        } else {
+                            (ltype, left.1)
+                        };
 
-                    (ltype, rtype) if ltype == "null" => {
-                        if !Compiler::__is_ptr_type(rtype) {
-                            return self.context.bool_type().const_zero();
+                        // strings are pointers too, so they can be `null`
+                        if !Compiler::__is_ptr_type(value_type) && value_type != "str" {
+                            return Ok(self.context.bool_type().const_zero());
                         }
 
                         match operand.as_str() {
                             "==" => {
-                                self.builder.build_is_null(right.1.into_pointer_value(), "").unwrap()
+                                self.builder.build_is_null(value.into_pointer_value(), "").unwrap()
                             },
                             "!=" => {
-                                self.builder.build_is_not_null(right.1.into_pointer_value(), "").unwrap()
+                                self.builder.build_is_not_null(value.into_pointer_value(), "").unwrap()
                             },
                             _ => {
-                                GenError::throw(
+                                return Err(self.error(
                                     format!("Operand `{}` is not supported for `null` checker!", operand),
                                     ErrorType::NotSupported,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
-                                    line
-                                );
-                                std::process::exit(1);
+                                    line,
+                                ));
                             }
                         }
                     }
-                    (ltype, rtype) if rtype == "null" => {
-                        if !Compiler::__is_ptr_type(ltype) {
-                            return self.context.bool_type().const_zero();
-                        }
 
-                        match operand.as_str() {
-                            "==" => {
-                                self.builder.build_is_null(left.1.into_pointer_value(), "").unwrap()
-                            },
-                            "!=" => {
-                                self.builder.build_is_not_null(left.1.into_pointer_value(), "").unwrap()
-                            },
+                    (ltype, rtype)
+                        if Compiler::__is_float_type(ltype) || Compiler::__is_float_type(rtype) =>
+                    {
+                        // matching operand (ordered comparisons are false for NaN)
+                        let predicate = match operand.as_str() {
+                            ">" => inkwell::FloatPredicate::OGT,
+                            "<" => inkwell::FloatPredicate::OLT,
+                            ">=" => inkwell::FloatPredicate::OGE,
+                            "<=" => inkwell::FloatPredicate::OLE,
+                            "==" => inkwell::FloatPredicate::OEQ,
+                            "!=" => inkwell::FloatPredicate::UNE,
                             _ => {
-                                GenError::throw(
-                                    format!("Operand `{}` is not supported for `null` checker!", operand),
+                                return Err(self.error(
+                                    format!("Compare operand `{}` is not supported!", operand),
                                     ErrorType::NotSupported,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
-                                    line
-                                );
-                                std::process::exit(1);
-                            }
-                        }
-                    }
+                                    line,
+                                ));
+                            }
+                        };
+
+                        let float_type = if ltype == "float64" || rtype == "float64" {
+                            "float64"
+                        } else {
+                            "float32"
+                        };
+
+                        let (left_type, right_type) = (left.0.clone(), right.0.clone());
+                        let lhs_value = self.build_float_cast(left, float_type, line)?;
+                        let rhs_value = self.build_float_cast(right, float_type, line)?;
 
+                        self.builder
+                            .build_float_compare(predicate, lhs_value, rhs_value, "float_condition")
+                            .map_err(|_| {
+                                self.error(
+                                    format!(
+                                        "An error occured while building condition `{} {} {}`!",
+                                        left_type, operand, right_type
+                                    ),
+                                    ErrorType::BuildError,
+                                    line,
+                                )
+                            })?
+                    }
                     ("int8", "int8")
                     | ("int16", "int16")
                     | ("int32", "int32")
                     | ("int64", "int64")
-                    | ("bool", "bool") => {
+                    | ("bool", "bool")
+                    | ("char", "char") => {
+                        // characters are ordered like `strcmp` does it: as unsigned bytes
+                        let is_unsigned = left.0 == "char";
+
                         // matching operand
                         let predicate = match operand.as_str() {
+                            ">" if is_unsigned => inkwell::IntPredicate::UGT,
+                            "<" if is_unsigned => inkwell::IntPredicate::ULT,
+                            ">=" if is_unsigned => inkwell::IntPredicate::UGE,
+                            "<=" if is_unsigned => inkwell::IntPredicate::ULE,
                             ">" => inkwell::IntPredicate::SGT,
                             "<" => inkwell::IntPredicate::SLT,
+                            ">=" => inkwell::IntPredicate::SGE,
+                            "<=" => inkwell::IntPredicate::SLE,
                             "==" => inkwell::IntPredicate::EQ,
                             "!=" => inkwell::IntPredicate::NE,
                             _ => {
-                                GenError::throw(
+                                return Err(self.error(
                                     format!("Compare operand `{}` is not supported!", operand),
                                     ErrorType::NotSupported,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
                                     line,
-                                );
-                                std::process::exit(1);
+                                ));
                             }
                         };
 
@@ -1907,101 +3757,129 @@ impl<'ctx> Compiler<'ctx> {
                             "int_condition",
                         );
 
-                        condition.unwrap_or_else(|_| {
-                            GenError::throw(
+                        condition.map_err(|_| {
+                            self.error(
                                 format!(
                                     "An error occured while building condition `{} {} {}`!",
                                     left.0, operand, right.0
                                 ),
                                 ErrorType::BuildError,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
                                 line,
-                            );
-                            std::process::exit(1);
-                        })
+                            )
+                        })?
                     }
                     ("str", "str") => {
                         // matching operand
                         let predicate = match operand.as_str() {
                             ">" => inkwell::IntPredicate::SGT,
                             "<" => inkwell::IntPredicate::SLT,
+                            ">=" => inkwell::IntPredicate::SGE,
+                            "<=" => inkwell::IntPredicate::SLE,
                             "==" => inkwell::IntPredicate::EQ,
                             "!=" => inkwell::IntPredicate::NE,
                             _ => {
-                                GenError::throw(
+                                return Err(self.error(
                                     format!("Compare operand `{}` is not supported!", operand),
                                     ErrorType::NotSupported,
-                                    self.module_name.clone(),
-                                    self.module_source.clone(),
                                     line,
-                                );
-                                std::process::exit(1);
+                                ));
                             }
                         };
 
-                        let strcmp_fn = self.__c_strcmp();
-                        let strcmp_result = self
-                            .builder
-                            .build_call(strcmp_fn, &[left.1.into(), right.1.into()], "")
-                            .unwrap()
-                            .try_as_basic_value()
-                            .left()
-                            .unwrap();
-
-                        let zero_value = self.context.i32_type().const_zero().as_basic_value_enum();
-
-                        let condition = self.builder.build_int_compare(
-                            predicate,
-                            strcmp_result.into_int_value(),
-                            zero_value.into_int_value(),
-                            "",
-                        );
+                        // strings are compared by value for every operand, `null` is the least
+                        let compare_result = self.build_str_compare(
+                            left.1.into_pointer_value(),
+                            right.1.into_pointer_value(),
+                            line,
+                        )?;
 
-                        condition.unwrap()
+                        self.builder
+                            .build_int_compare(
+                                predicate,
+                                compare_result,
+                                self.context.i32_type().const_zero(),
+                                "str_condition",
+                            )
+                            .map_err(|_| {
+                                self.error(
+                                    format!(
+                                        "An error occured while building condition `{} {} {}`!",
+                                        left.0, operand, right.0
+                                    ),
+                                    ErrorType::BuildError,
+                                    line,
+                                )
+                            })?
+                    }
+                    ("char", "str") | ("str", "char") => {
+                        return Err(self.error(
+                            format!(
+                                "Cannot compare `{}` and `{}` types! Convert `char` with `to_str()` first",
+                                left.0, right.0
+                            ),
+                            ErrorType::TypeError,
+                            line,
+                        ));
                     }
                     _ => {
-                        GenError::throw(
+                        return Err(self.error(
                             format!("Cannot compare `{}` and `{}` types!", left.0, right.0),
                             ErrorType::TypeError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
+                        ));
                     }
                 }
             }
-            Expressions::Value(val) => {
-                let compiled_value = self.compile_value(val, line, None);
+            Expressions::Unary {
+                operand,
+                object,
+                line,
+                ..
+            } if operand == "!" => {
+                let condition = self.compile_condition(*object, line, function)?;
+
+                self.builder.build_not(condition, "not_cmp").map_err(|_| {
+                    self.error(
+                        "Unable to build NOT condition!",
+                        ErrorType::BuildError,
+                        line,
+                    )
+                })?
+            }
+            Expressions::Value(val, _) => {
+                let compiled_value = self.compile_value(val, line, None)?;
 
                 if compiled_value.0 != "bool" {
-                    GenError::throw(
+                    return Err(self.error(
                         format!(
                             "Unsupported `{}` type found for condition!",
                             compiled_value.0
                         ),
                         ErrorType::NotSupported,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
+                    ));
                 }
 
                 compiled_value.1.into_int_value()
             }
             _ => {
-                GenError::throw(
-                    "Unexpected expression found on condition!",
-                    ErrorType::NotExpected,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
+                // any other expression is a condition only if it returns `bool`
+                let compiled_value = self.compile_expression(condition, line, function, None)?;
+
+                if compiled_value.0 != "bool" {
+                    return Err(self.error(
+                        format!(
+                            "Unsupported `{}` type found for condition!",
+                            compiled_value.0
+                        ),
+                        ErrorType::NotSupported,
+                        line,
+                    ));
+                }
+
+                compiled_value.1.into_int_value()
             }
-        }
+        })
     }
 
     // user defined call
@@ -2011,8 +3889,10 @@ impl<'ctx> Compiler<'ctx> {
         arguments: Vec<Expressions>,
         line: usize,
         function: FunctionValue<'ctx>,
-    ) -> (String, BasicValueEnum<'ctx>) {
-        let mut is_var_stored = false;
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        if self.is_callable_variable(&function_name) {
+            return self.build_closure_call(function_name, arguments, line, function);
+        }
 
         if !self.functions.contains_key(&function_name) {
             match function_name.as_str() {
@@ -2021,14 +3901,11 @@ impl<'ctx> Compiler<'ctx> {
                 "len" => return self.build_len_call(arguments, line, function),
                 "size" => return self.build_size_call(arguments, line, function),
                 "print" => {
-                    GenError::throw(
+                    return Err(self.error(
                         "Function `print` is 'void' type!",
                         ErrorType::TypeError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
+                    ));
                 }
                 "input" => return self.build_input_call(arguments, line, function),
 
@@ -2037,6 +3914,8 @@ impl<'ctx> Compiler<'ctx> {
                 "to_int16" => return self.build_to_int16_call(arguments, line, function),
                 "to_int32" => return self.build_to_int32_call(arguments, line, function),
                 "to_int64" => return self.build_to_int64_call(arguments, line, function),
+                "to_float32" => return self.build_to_float32_call(arguments, line, function),
+                "to_float64" => return self.build_to_float64_call(arguments, line, function),
 
                 "malloc" => return self.build_malloc_call(arguments, line, function),
                 "realloc" => return self.build_realloc_call(arguments, line, function),
@@ -2046,48 +3925,48 @@ impl<'ctx> Compiler<'ctx> {
                 "close" => return self.build_close_call(arguments, line, function),
 
                 "write" => return self.build_write_call(arguments, line, function),
+                "read_char" => return self.build_read_char_call(arguments, line, function),
+                "read_line" => return self.build_read_line_call(arguments, line, function),
+                "read_all" => return self.build_read_all_call(arguments, line, function),
+                "eof" => return self.build_eof_call(arguments, line, function),
+                "seek" => return self.build_seek_call(arguments, line, function),
+                "tell" => return self.build_tell_call(arguments, line, function),
+                "exists" => return self.build_exists_call(arguments, line, function),
+
+                "args" => return self.build_args_call(arguments, line),
+                "arg_count" => return self.build_arg_count_call(arguments, line),
+                "exit" => return self.build_exit_call(arguments, line, function),
+
+                "push" | "pop" | "get" | "set" | "insert" | "remove" | "clear" | "has" | "keys" => {
+                    return self.build_collection_call(function_name, arguments, line, function);
+                }
+                "substr" | "find" | "contains" | "starts_with" | "ends_with" | "split"
+                | "replace" | "trim" | "to_upper" | "to_lower" | "repeat" | "join" => {
+                    return self.build_string_call(function_name, arguments, line, function);
+                }
                 _ => {
-                    if let Some(var) = self.variables.get(&function_name) {
-                        if var.assigned_function.is_some() {
-                            is_var_stored = true;
-                        } else {
-                            GenError::throw(
-                                format!("Variable `{}` is not a function!", function_name),
-                                ErrorType::TypeError,
-                                self.module_name.clone(),
-                                self.module_source.clone(),
-                                line,
-                            );
-                            std::process::exit(1);
-                        }
+                    if self.variables.get(&function_name).is_some() {
+                        return Err(self.error(
+                            format!("Variable `{}` is not a function!", function_name),
+                            ErrorType::TypeError,
+                            line,
+                        ));
                     } else {
-                        GenError::throw(
+                        return Err(self.error(
                             format!("Function `{}()` is not defined!", function_name),
                             ErrorType::NotDefined,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
+                        ));
                     }
                 }
             };
         }
 
-        let func = if is_var_stored {
-            self.variables
-                .get(&function_name)
-                .unwrap()
-                .clone()
-                .assigned_function
-                .unwrap()
-        } else {
-            self.functions.get(&function_name).unwrap().clone()
-        };
+        let func = self.functions.get(&function_name).unwrap().clone();
 
         // compiling args len
         if arguments.len() != func.arguments_types.len() {
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `{}` has {} arguments, but {} found!",
                     function_name,
@@ -2095,11 +3974,8 @@ impl<'ctx> Compiler<'ctx> {
                     arguments.len()
                 ),
                 ErrorType::NotExpected,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
         // matching arguments types
@@ -2114,33 +3990,21 @@ impl<'ctx> Compiler<'ctx> {
                 line,
                 function,
                 Some(func.arguments_types[index].clone()),
-            );
+            )?;
+            let compiled_arg =
+                self.coerce_value(compiled_arg, &func.arguments_types[index], line)?;
 
             if compiled_arg.0 != func.arguments_types[index] {
                 arguments_error = true;
             } else {
-                values.push(compiled_arg.1.into());
+                values.push(self.array_aggregate(&compiled_arg, line)?.into());
             }
 
             arguments_types.push(compiled_arg.0.clone());
         }
 
         if arguments_error {
-            if func.name == LAMBDA_NAME {
-                GenError::throw(
-                    format!(
-                        "Lambda function expected arguments types [{}], but found [{}]!",
-                        func.arguments_types.clone().join(", "),
-                        arguments_types.join(", "),
-                    ),
-                    ErrorType::TypeError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
-            }
-            GenError::throw(
+            return Err(self.error(
                 format!(
                     "Function `{}` expected arguments types [{}], but found [{}]!",
                     func.name,
@@ -2148,11 +4012,8 @@ impl<'ctx> Compiler<'ctx> {
                     arguments_types.join(", "),
                 ),
                 ErrorType::TypeError,
-                self.module_name.clone(),
-                self.module_source.clone(),
                 line,
-            );
-            std::process::exit(1);
+            ));
         }
 
         // calling function
@@ -2162,85 +4023,143 @@ impl<'ctx> Compiler<'ctx> {
                 &values,
                 format!("{}_call", &func.name).as_str(),
             )
-            .unwrap_or_else(|_| {
-                GenError::throw(
+            .map_err(|_| {
+                self.error(
                     format!("An error occured while calling `{}` function!", &func.name),
                     ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
                     line,
-                );
-                std::process::exit(1);
-            })
+                )
+            })?
             .try_as_basic_value()
-            .left()
-            .unwrap_or_else(|| {
-                if func.function_type == "void" {
-                    self.context.i8_type().const_zero().into()
-                } else {
-                    GenError::throw("Error with compiling function's returned value to basic datatype! Please open issue on github repo!", ErrorType::BuildError, self.module_name.clone(), self.module_source.clone(), line);
-                    std::process::exit(1);
-                }
-            });
+            .left();
+
+        let call_result = match call_result {
+            Some(value) => value,
+            None if func.function_type == "void" => self.context.i8_type().const_zero().into(),
+            None => {
+                return Err(self.error(
+                    "Error with compiling function's returned value to basic datatype! Please open issue on github repo!",
+                    ErrorType::BuildError,
+                    line,
+                ));
+            }
+        };
 
-        (func.function_type.clone(), call_result)
+        self.array_from_aggregate((func.function_type.clone(), call_result), line)
     }
 
     // getting types
 
     #[inline]
-    fn get_basic_type(&self, datatype: &str, line: usize) -> BasicTypeEnum<'ctx> {
-        match datatype {
-            _ if datatype.starts_with("fn<") => {
-                let fn_type = datatype.replace("fn<", "").replace(">", "");
-                self.get_basic_type(fn_type.as_str(), line)
+    fn get_basic_type(&self, datatype: &str, line: usize) -> CompileResult<BasicTypeEnum<'ctx>> {
+        Ok(match datatype {
+            _ if Compiler::__is_fn_type(datatype) => {
+                // closure is a function pointer with its environment
+                self.__closure_type().into()
+            }
+            _ if Compiler::__is_list_type(datatype) => {
+                // list is a pointer to its heap header
+                self.context.ptr_type(AddressSpace::default()).into()
+            }
+            _ if Compiler::__is_map_type(datatype) => {
+                let (key_type, value_type) = Compiler::__map_types(datatype);
+
+                if !["str", "int8", "int16", "int32", "int64"].contains(&key_type.as_str()) {
+                    return Err(self.error(
+                        format!(
+                            "Map keys must be `str` or integers, but found `{}`!",
+                            key_type
+                        ),
+                        ErrorType::TypeError,
+                        line,
+                    ));
+                }
+
+                // value type must exist too
+                let _ = self.get_storage_type(&value_type, line)?;
+
+                // map is a pointer to its heap header
+                self.context.ptr_type(AddressSpace::default()).into()
+            }
+            _ if Compiler::__is_ptr_type(datatype) => {
+                let unwrapped_type = Compiler::__unwrap_ptr_type(datatype);
+                self.get_basic_type(&unwrapped_type, line)?
+            }
+            _ if Compiler::__is_slice_type(datatype) => {
+                // slice is pointer to the first element and elements count
+                self.context
+                    .struct_type(
+                        &[
+                            self.context.ptr_type(AddressSpace::default()).into(),
+                            self.context.i64_type().into(),
+                        ],
+                        false,
+                    )
+                    .into()
             }
             _ if datatype.contains("[") => {
-                let type_parts = datatype.split("[").collect::<Vec<&str>>();
-                let raw_type = type_parts[0];
-                let array_len: u32 = type_parts[1].split("]").collect::<Vec<&str>>()[0]
+                let array_len: u32 = datatype.split("[").collect::<Vec<&str>>()[1]
+                    .split("]")
+                    .collect::<Vec<&str>>()[0]
                     .parse()
-                    .unwrap_or_else(|_| {
-                        GenError::throw(
+                    .map_err(|_| {
+                        self.error(
                             "Unable to compile array's length!",
                             ErrorType::BuildError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
                             line,
-                        );
-                        std::process::exit(1);
-                    });
+                        )
+                    })?;
 
-                match self.get_basic_type(raw_type, line) {
-                    BasicTypeEnum::IntType(int) => int.vec_type(array_len).into(),
-                    BasicTypeEnum::PointerType(ptr) => ptr.vec_type(array_len).into(),
-                    _ => unreachable!(),
-                }
-            }
-            _ if Compiler::__is_ptr_type(datatype) => {
-                let unwrapped_type = Compiler::__unwrap_ptr_type(datatype);
-                self.get_basic_type(&unwrapped_type, line)
+                let element_type = Compiler::clean_array_datatype(datatype);
+                self.get_storage_type(&element_type, line)?
+                    .array_type(array_len)
+                    .into()
             }
             "int8" => self.context.i8_type().into(),
             "int16" => self.context.i16_type().into(),
             "int32" => self.context.i32_type().into(),
             "int64" => self.context.i64_type().into(),
+            "float32" => self.context.f32_type().into(),
+            "float64" => self.context.f64_type().into(),
             "bool" => self.context.bool_type().into(),
             "str" => self.context.ptr_type(AddressSpace::default()).into(),
             "char" => self.context.i8_type().into(),
             "auto" => self.context.i8_type().into(),
             "void" => self.context.ptr_type(AddressSpace::default()).into(),
-            _ => {
-                GenError::throw(
-                    format!("Unsupported `{}` datatype!", datatype),
-                    ErrorType::NotSupported,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
-            }
+            _ => match self.get_user_type(datatype) {
+                Some(user_type) => user_type.into(),
+                None => {
+                    return Err(self.error(
+                        format!("Unsupported `{}` datatype!", datatype),
+                        ErrorType::NotSupported,
+                        line,
+                    ));
+                }
+            },
+        })
+    }
+
+    #[inline]
+    fn get_user_type(&self, datatype: &str) -> Option<StructType<'ctx>> {
+        // structures and enumerations are both lowered to LLVM structs
+        self.structures
+            .get(datatype)
+            .map(|structure| structure.struct_type)
+            .or_else(|| {
+                self.enums
+                    .get(datatype)
+                    .map(|enumeration| enumeration.enum_type)
+            })
+    }
+
+    #[inline]
+    fn get_storage_type(&self, datatype: &str, line: usize) -> CompileResult<BasicTypeEnum<'ctx>> {
+        // pointers are stored as pointers, not as values they point to
+        if Compiler::__is_ptr_type(datatype) {
+            return Ok(self.context.ptr_type(AddressSpace::default()).into());
         }
+
+        self.get_basic_type(datatype, line)
     }
 
     #[inline]
@@ -2250,29 +4169,41 @@ impl<'ctx> Compiler<'ctx> {
         params: &[BasicMetadataTypeEnum<'ctx>],
         is_var_args: bool,
         line: usize,
-    ) -> FunctionType<'ctx> {
-        match datatype {
+    ) -> CompileResult<FunctionType<'ctx>> {
+        Ok(match datatype {
             "int8" => self.context.i8_type().fn_type(params, is_var_args),
             "int16" => self.context.i16_type().fn_type(params, is_var_args),
             "int32" => self.context.i32_type().fn_type(params, is_var_args),
             "int64" => self.context.i64_type().fn_type(params, is_var_args),
+            "float32" => self.context.f32_type().fn_type(params, is_var_args),
+            "float64" => self.context.f64_type().fn_type(params, is_var_args),
             "bool" => self.context.bool_type().fn_type(params, is_var_args),
             "void" => self.context.void_type().fn_type(params, is_var_args),
             "str" => self
                 .context
                 .ptr_type(AddressSpace::default())
                 .fn_type(params, is_var_args),
-            _ => {
-                GenError::throw(
-                    format!("Unsupported `{}` function type found!", datatype),
-                    ErrorType::NotSupported,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
+            _ if Compiler::__is_fn_type(datatype) => {
+                self.__closure_type().fn_type(params, is_var_args)
             }
-        }
+            _ if Compiler::__is_list_type(datatype) || Compiler::__is_map_type(datatype) => self
+                .context
+                .ptr_type(AddressSpace::default())
+                .fn_type(params, is_var_args),
+            _ if datatype.contains("[") => self
+                .get_basic_type(datatype, line)?
+                .fn_type(params, is_var_args),
+            _ => match self.get_user_type(datatype) {
+                Some(user_type) => user_type.fn_type(params, is_var_args),
+                None => {
+                    return Err(self.error(
+                        format!("Unsupported `{}` function type found!", datatype),
+                        ErrorType::NotSupported,
+                        line,
+                    ));
+                }
+            },
+        })
     }
 
     pub fn define_user_function(
@@ -2282,20 +4213,28 @@ impl<'ctx> Compiler<'ctx> {
         arguments: Vec<(String, String)>,
         block: Vec<Statements>,
         line: usize,
-    ) -> Function<'ctx> {
+        environment: Option<&[(String, Variable<'ctx>)]>,
+    ) -> CompileResult<Function<'ctx>> {
         // setting function expected return value
         let old_expectation_value = self.current_expectation_value.clone();
         self.current_expectation_value = Some(function_type.clone());
 
         // compiling args types
         let mut args: Vec<BasicMetadataTypeEnum<'ctx>> = Vec::new();
+
+        // closure takes its environment as the first hidden parameter
+        let parameters_offset = environment.is_some() as u32;
+        if environment.is_some() {
+            args.push(self.context.ptr_type(AddressSpace::default()).into());
+        }
+
         for item in arguments.clone() {
-            let arg = self.get_basic_type(item.1.as_str(), line);
+            let arg = self.get_storage_type(item.1.as_str(), line)?;
             args.push(arg.into())
         }
 
         // creating function type
-        let fn_type = self.get_fn_type(function_type.as_str(), &args, false, line);
+        let fn_type = self.get_fn_type(function_type.as_str(), &args, false, line)?;
 
         // adding function
         let function = self
@@ -2309,48 +4248,61 @@ impl<'ctx> Compiler<'ctx> {
         let old_position = self.current_block;
         self.builder.position_at_end(entry);
 
+        // cycles from outer scope cannot be controlled inside function
+        let old_loop_blocks = std::mem::take(&mut self.loop_blocks);
+
+        // function body and its parameters get their own scope
+        self.variables.enter();
+
+        if let Some(captures) = environment {
+            let environment_ptr = function
+                .get_first_param()
+                .map(|parameter| parameter.into_pointer_value())
+                .ok_or_else(|| {
+                    self.error(
+                        "Unable to get closure environment!",
+                        ErrorType::BuildError,
+                        line,
+                    )
+                })?;
+
+            self.bind_environment(environment_ptr, captures, line)?;
+        }
+
         // storing arguments values to variables
-        let mut old_variables = HashMap::new();
 
         for (index, arg) in arguments.iter().enumerate() {
             let varname = arg.0.clone();
-            let arg_value = function.get_nth_param(index as u32).unwrap_or_else(|| {
-                GenError::throw(
-                    format!(
-                        "An error occured with fetching parameter while defining `{}` function!",
-                        function_name
-                    ),
-                    ErrorType::BuildError,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line,
-                );
-                std::process::exit(1);
-            });
-            let old_value = self.variables.remove(&varname);
-            old_variables.insert(varname.clone(), old_value);
-
+            let arg_value = function
+                .get_nth_param(index as u32 + parameters_offset)
+                .ok_or_else(|| {
+                    self.error(
+                        format!(
+                            "An error occured with fetching parameter while defining `{}` function!",
+                            function_name
+                        ),
+                        ErrorType::BuildError,
+                        line,
+                    )
+                })?;
             // storing value
-            let parameter_type = self.get_basic_type(arg.1.as_str(), line);
+            let parameter_type = self.get_storage_type(arg.1.as_str(), line)?;
             let parameter_alloca = self
                 .builder
                 .build_alloca(
                     parameter_type,
                     format!("{}_param_{}", function_name, index).as_str(),
                 )
-                .unwrap_or_else(|_| {
-                    GenError::throw(
+                .map_err(|_| {
+                    self.error(
                         format!(
                             "An error occured with creating alloca for parameter `{}`!",
                             varname.clone()
                         ),
                         ErrorType::BuildError,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
                         line,
-                    );
-                    std::process::exit(1);
-                });
+                    )
+                })?;
 
             let _ = self.builder.build_store(parameter_alloca, arg_value);
 
@@ -2378,9 +4330,12 @@ impl<'ctx> Compiler<'ctx> {
             .insert(function_name.clone(), function_object.clone());
 
         // compiling statements
-        for stmt in block {
-            self.compile_statement(stmt, function);
-        }
+        let diagnostics_count = self.diagnostics.len();
+
+        self.compile_block(block, function);
+        self.variables.exit();
+
+        let body_failed = self.diagnostics.len() > diagnostics_count;
 
         // add terminator if dont have
         let terminator_instructions = self
@@ -2394,7 +4349,11 @@ impl<'ctx> Compiler<'ctx> {
                 .builder
                 .build_return(Some(&match function_type.as_str() {
                     "int8" | "int16" | "int32" | "int64" => {
-                        self.compile_value(Value::Integer(0), line, Some(function_type.clone()))
+                        self.compile_value(Value::Integer(0), line, Some(function_type.clone()))?
+                            .1
+                    }
+                    "float32" | "float64" => {
+                        self.compile_value(Value::Float(0.0), line, Some(function_type.clone()))?
                             .1
                     }
                     "str" => {
@@ -2402,68 +4361,51 @@ impl<'ctx> Compiler<'ctx> {
                             Value::String("@tplc:auto-return".to_string()),
                             line,
                             None,
-                        )
+                        )?
                         .1
                     }
-                    "char" => self.compile_value(Value::Char('0'), line, None).1,
-                    "bool" => self.compile_value(Value::Boolean(false), line, None).1,
-                    _ => unreachable!(),
+                    "char" => self.compile_value(Value::Char('0'), line, None)?.1,
+                    "bool" => self.compile_value(Value::Boolean(false), line, None)?.1,
+                    _ => self.get_basic_type(&function_type, line)?.const_zero(),
                 }));
         };
 
-        // verification
+        // verification (function with errors in body cannot be verified)
 
-        if !function.verify(true) {
-            if function_type == "void" {
-                self.builder.build_return(None)
-                    .unwrap_or_else(|_| {
-                        GenError::throw(
-                            format!("An error occured with wrapping void '{}' function!\nPlease open an issue on project's repo!", function_name),
-                            ErrorType::BuildError,
-                            self.module_name.clone(),
-                            self.module_source.clone(),
-                            line
-                        );
-                        std::process::exit(1);
-                    });
-            } else {
-                if function_name == LAMBDA_NAME {
-                    function.print_to_stderr();
-
-                    GenError::throw(
-                        "Lambda failed verification! Here's the possible reasons:\n* Function doesn't returns value or returns wrong value's type.\n* Function have branches after returning a value.\n* Function doesn't matches types, or matches wrong.\nPlease check your code or open issue on github repo!".to_string(),
-                        ErrorType::VerificationFailure,
-                        self.module_name.clone(),
-                        self.module_source.clone(),
-                        line
-                    );
-                    std::process::exit(1);
-                }
-                GenError::throw(
-                    format!("Function `{}` failed verification! Here's the possible reasons:\n* Function doesn't returns value or returns wrong value's type.\n* Function have branches after returning a value.\n* Function doesn't matches types, or matches wrong.\nPlease check your code or open issue on github repo!", &function_name),
-                    ErrorType::VerificationFailure,
-                    self.module_name.clone(),
-                    self.module_source.clone(),
-                    line
-                );
-                std::process::exit(1);
-            }
-        }
+        let verification = if body_failed || function.verify(true) {
+            Ok(())
+        } else if function_type == "void" {
+            self.builder.build_return(None).map(|_| ()).map_err(|_| {
+                self.error(
+                    format!("An error occured with wrapping void '{}' function!\nPlease open an issue on project's repo!", function_name),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })
+        } else if function_name == LAMBDA_NAME {
+            function.print_to_stderr();
 
-        // and switching to old position
-        self.builder.position_at_end(old_position);
+            Err(self.error(
+                "Lambda failed verification! Here's the possible reasons:\n* Function doesn't returns value or returns wrong value's type.\n* Function have branches after returning a value.\n* Function doesn't matches types, or matches wrong.\nPlease check your code or open issue on github repo!".to_string(),
+                ErrorType::VerificationFailure,
+                line,
+            ))
+        } else {
+            Err(self.error(
+                format!("Function `{}` failed verification! Here's the possible reasons:\n* Function doesn't returns value or returns wrong value's type.\n* Function have branches after returning a value.\n* Function doesn't matches types, or matches wrong.\nPlease check your code or open issue on github repo!", &function_name),
+                ErrorType::VerificationFailure,
+                line,
+            ))
+        };
 
-        // returning old variables
-        for opt in old_variables {
-            if let Some(value) = opt.1 {
-                self.variables.insert(opt.0, value);
-            }
-        }
+        // and switching to old position
+        self.switch_block(old_position);
+        self.loop_blocks = old_loop_blocks;
 
         // returning expectation value
         self.current_expectation_value = old_expectation_value;
 
-        function_object
+        verification.map(|_| function_object)
     }
 
     fn validate_types(types: &[String], expected_type: String) -> bool {
@@ -2485,7 +4427,146 @@ impl<'ctx> Compiler<'ctx> {
     #[allow(non_snake_case)]
     #[inline]
     fn __is_arr_type(type_str: &str) -> bool {
-        type_str.contains("[") && type_str.contains("]")
+        // fixed size array: `int32[3]`
+        type_str.contains("[")
+            && type_str.contains("]")
+            && !Compiler::__is_slice_type(type_str)
+            && !Compiler::__is_ptr_type(type_str)
+            && !Compiler::__is_list_type(type_str)
+            && !Compiler::__is_map_type(type_str)
+            && !Compiler::__is_fn_type(type_str)
+    }
+
+    #[allow(non_snake_case)]
+    #[inline]
+    fn __is_slice_type(type_str: &str) -> bool {
+        // array of any length: `int32[]`
+        !Compiler::__is_ptr_type(type_str)
+            && !Compiler::__is_list_type(type_str)
+            && !Compiler::__is_map_type(type_str)
+            && !Compiler::__is_fn_type(type_str)
+            && type_str
+                .find('[')
+                .is_some_and(|start| type_str[start + 1..].starts_with(']'))
+    }
+
+    #[allow(non_snake_case)]
+    #[inline]
+    fn __is_list_type(type_str: &str) -> bool {
+        // growable list: `list<int32>`
+        type_str.starts_with("list<") && type_str.ends_with('>')
+    }
+
+    #[allow(non_snake_case)]
+    #[inline]
+    fn __list_element_type(type_str: &str) -> String {
+        type_str
+            .strip_prefix("list<")
+            .and_then(|element_type| element_type.strip_suffix('>'))
+            .unwrap_or(type_str)
+            .to_string()
+    }
+
+    #[allow(non_snake_case)]
+    #[inline]
+    fn __is_fn_type(type_str: &str) -> bool {
+        // function value: `fn<int32>` or `fn<int32(int32, str)>`
+        type_str.starts_with("fn<") && type_str.ends_with('>')
+    }
+
+    #[allow(non_snake_case)]
+    #[inline]
+    fn __fn_signature(type_str: &str) -> (String, Option<Vec<String>>) {
+        let inner = type_str
+            .strip_prefix("fn<")
+            .and_then(|inner| inner.strip_suffix('>'))
+            .unwrap_or(type_str);
+
+        // return type ends at the first not nested parenthesis: `fn<list<int32>(int32)>`
+        let mut depth = 0;
+        for (index, chr) in inner.char_indices() {
+            match chr {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                '(' if depth == 0 => {
+                    let arguments = inner[index + 1..].strip_suffix(')').unwrap_or_default();
+                    return (
+                        inner[..index].to_string(),
+                        Some(Compiler::__split_types(arguments)),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        // arguments are unknown: `fn<int32>`
+        (inner.to_string(), None)
+    }
+
+    #[allow(non_snake_case)]
+    #[inline]
+    fn __split_types(types: &str) -> Vec<String> {
+        let mut parts = Vec::new();
+        let mut depth = 0;
+        let mut start = 0;
+
+        for (index, chr) in types.char_indices() {
+            match chr {
+                '<' | '(' => depth += 1,
+                '>' | ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(types[start..index].trim().to_string());
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+
+        if !types.trim().is_empty() {
+            parts.push(types[start..].trim().to_string());
+        }
+
+        parts
+    }
+
+    #[allow(non_snake_case)]
+    #[inline]
+    fn __is_map_type(type_str: &str) -> bool {
+        // hash map: `map<str, int32>`
+        type_str.starts_with("map<") && type_str.ends_with('>')
+    }
+
+    #[allow(non_snake_case)]
+    #[inline]
+    fn __map_types(type_str: &str) -> (String, String) {
+        let inner = type_str
+            .strip_prefix("map<")
+            .and_then(|inner| inner.strip_suffix('>'))
+            .unwrap_or(type_str);
+
+        // splitting by comma which is not nested: `map<str, map<int32, str>>`
+        let mut depth = 0;
+        for (index, chr) in inner.char_indices() {
+            match chr {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                ',' if depth == 0 => {
+                    return (
+                        inner[..index].trim().to_string(),
+                        inner[index + 1..].trim().to_string(),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        (inner.to_string(), String::new())
+    }
+
+    #[allow(non_snake_case)]
+    #[inline]
+    fn __is_float_type(type_str: &str) -> bool {
+        type_str == "float32" || type_str == "float64"
     }
 
     #[allow(non_snake_case)]
@@ -2506,6 +4587,8 @@ impl<'ctx> Compiler<'ctx> {
             "int16" => "%hd",
             "int32" => "%d",
             "int64" => "%lld",
+            "float32" => "%g",
+            "float64" => "%g",
             "bool" => "%s",
             "str" => "%s",
             "char" => "%c",
@@ -2544,6 +4627,36 @@ impl<'ctx> Compiler<'ctx> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tpl_lexer::span::Span;
+
+    fn parse_source(source: &str) -> Vec<Statements> {
+        let tokens = tpl_lexer::Lexer::new(source.to_string(), String::from("test.tpl"))
+            .tokenize()
+            .unwrap();
+
+        tpl_parser::Parser::new(tokens, String::from("test.tpl"), source.to_string())
+            .parse()
+            .unwrap()
+    }
+
+    // compiled program must have valid `main`, module is returned as IR text
+    fn compile_source(source: &str) -> Result<String, Vec<CodegenDiagnostic>> {
+        let ctx = inkwell::context::Context::create();
+        let mut compiler =
+            Compiler::new(&ctx, "test", String::from("test.tpl"), source.to_string());
+        compiler.generate(parse_source(source))?;
+
+        assert!(compiler.main_function.verify(false));
+        Ok(compiler.module.print_to_string().to_string())
+    }
+
+    fn compile_errors(source: &str) -> Vec<String> {
+        compile_source(source)
+            .unwrap_err()
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
 
     #[test]
     fn validate_types_test() {
@@ -2628,15 +4741,27 @@ mod tests {
         let compiler = Compiler::new(&ctx, "test", String::from("none"), String::from("test.tpl"));
         compiler.builder.position_at_end(compiler.current_block);
 
-        let int8 = compiler.compile_value(Value::Integer(15), 0, None);
-        let int16 = compiler.compile_value(Value::Integer(256), 0, None);
-        let int32 = compiler.compile_value(Value::Integer(65_535), 0, None);
-        let int64 = compiler.compile_value(Value::Integer(2_147_483_648), 0, None);
-
-        let boolean_true = compiler.compile_value(Value::Boolean(true), 0, None);
-        let boolean_false = compiler.compile_value(Value::Boolean(false), 0, None);
-
-        let str = compiler.compile_value(Value::String(String::from("some")), 0, None);
+        let int8 = compiler.compile_value(Value::Integer(15), 0, None).unwrap();
+        let int16 = compiler
+            .compile_value(Value::Integer(256), 0, None)
+            .unwrap();
+        let int32 = compiler
+            .compile_value(Value::Integer(65_535), 0, None)
+            .unwrap();
+        let int64 = compiler
+            .compile_value(Value::Integer(2_147_483_648), 0, None)
+            .unwrap();
+
+        let boolean_true = compiler
+            .compile_value(Value::Boolean(true), 0, None)
+            .unwrap();
+        let boolean_false = compiler
+            .compile_value(Value::Boolean(false), 0, None)
+            .unwrap();
+
+        let str = compiler
+            .compile_value(Value::String(String::from("some")), 0, None)
+            .unwrap();
 
         assert_eq!(
             (
@@ -2677,22 +4802,26 @@ mod tests {
 
         let condition_true = Expressions::Boolean {
             operand: String::from("=="),
-            lhs: Box::new(Expressions::Value(Value::Integer(123))),
-            rhs: Box::new(Expressions::Value(Value::Integer(123))),
+            lhs: Box::new(Expressions::Value(Value::Integer(123), Span::default())),
+            rhs: Box::new(Expressions::Value(Value::Integer(123), Span::default())),
             line: 0,
+            span: Span::default(),
         };
 
         let condition_false = Expressions::Boolean {
             operand: String::from("=="),
-            lhs: Box::new(Expressions::Value(Value::Integer(0))),
-            rhs: Box::new(Expressions::Value(Value::Integer(123))),
+            lhs: Box::new(Expressions::Value(Value::Integer(0), Span::default())),
+            rhs: Box::new(Expressions::Value(Value::Integer(123), Span::default())),
             line: 0,
+            span: Span::default(),
         };
 
-        let compiled_true_condition =
-            compiler.compile_condition(condition_true, 0, compiler.main_function);
-        let compiled_false_condition =
-            compiler.compile_condition(condition_false, 0, compiler.main_function);
+        let compiled_true_condition = compiler
+            .compile_condition(condition_true, 0, compiler.main_function)
+            .unwrap();
+        let compiled_false_condition = compiler
+            .compile_condition(condition_false, 0, compiler.main_function)
+            .unwrap();
 
         assert_eq!(
             compiled_true_condition
@@ -2718,18 +4847,860 @@ mod tests {
 
         let array_expr = Expressions::Array {
             values: vec![
-                Expressions::Value(Value::Integer(5)),
-                Expressions::Value(Value::Integer(3)),
-                Expressions::Value(Value::Integer(4)),
+                Expressions::Value(Value::Integer(5), Span::default()),
+                Expressions::Value(Value::Integer(3), Span::default()),
+                Expressions::Value(Value::Integer(4), Span::default()),
             ],
             len: 3,
             line: 0,
+            span: Span::default(),
         };
 
-        let compiled = compiler.compile_expression(array_expr, 0, compiler.main_function, None);
+        let compiled = compiler
+            .compile_expression(array_expr, 0, compiler.main_function, None)
+            .unwrap();
         assert_eq!(compiled.0, String::from("int8[3]"))
     }
 
+    #[test]
+    fn memory_arrays_test() {
+        let module_ir = compile_source(
+            "define int64 sum(int32[] values) {
+                int32 total = 0;
+                for value in values { total += value; };
+                return len(values) + to_int64(total);
+            };
+            define int32[2] pair(int32 value) {
+                return [value, value + 1];
+            };
+            int32[3][4] grid;
+            grid[1][2] = 7;
+            int32[4] row = grid[1];
+            row[0] = grid[1][2] + 1;
+            int32[3] small = [1, 2, 3];
+            int32[] view = small;
+            int64 total = sum(small) + sum(view) + len(grid) + len(grid[0]);
+            int32[2] numbers = pair(5);
+            for line in grid { print(line); };
+            print(grid, small, numbers);
+            int32[1000] big;
+            big[999] = sum([4, 5]);",
+        )
+        .unwrap();
+
+        // arrays are stored in memory, not in vector registers
+        assert!(module_ir.contains("alloca [1000 x i32]"));
+        assert!(module_ir.contains("alloca [3 x [4 x i32]]"));
+        assert!(!module_ir.contains("x i32>"));
+    }
+
+    #[test]
+    fn memory_arrays_errors_test() {
+        let diagnostics = compile_errors(
+            "define int32 first(int32[] values) { return values[0]; };\nint8[2] bytes = [1, 2];\nint32 a = first(bytes);\nint32[3] b = [1, 2, 3];\nb[3] = 4;\nint32[2] c = b;",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Function `first` expected arguments types [int32[]], but found [int8[2]]!",
+                "Wrong array index found! Array len is 3 but index is 3",
+                "Type `int32[2]` expected for 'c' variable, but found `int32[3]`!",
+            ]
+        );
+    }
+
+    #[test]
+    fn bounds_checks_test() {
+        let source = "int32 i = 5; int32[3] a = [1, 2, 3]; a[i] = 1; int32 b = a[i]; int32[] view = a; int32 c = view[i]; str s = \"ab\"; char d = s[i];";
+
+        assert!(compile_source(source)
+            .unwrap()
+            .contains("__tpl_bounds_panic"));
+
+        // checks are disabled by the compiler option
+        let ctx = inkwell::context::Context::create();
+        let mut compiler =
+            Compiler::new(&ctx, "test", String::from("test.tpl"), source.to_string())
+                .with_bounds_checks(false);
+        compiler.generate(parse_source(source)).unwrap();
+
+        assert!(compiler.main_function.verify(false));
+        assert!(!compiler
+            .module
+            .print_to_string()
+            .to_string()
+            .contains("__tpl_bounds_panic"));
+    }
+
+    #[test]
+    fn string_length_test() {
+        let module = compile_source(
+            "str s = \"hello\";
+            int64 i = 0;
+            int32 count = 0;
+            while i < len(s) { if s[i] == 'l' { count += 1; }; i += 1; };",
+        )
+        .unwrap();
+
+        // `len(s)` and `s[i]` read length from string header instead of measuring it
+        assert!(module.contains("__tpl_bounds_panic"));
+        assert!(!module.contains("@strlen"));
+    }
+
+    #[test]
+    fn lists_test() {
+        let module = compile_source(
+            "define int64 count(list<int32> values) { return values.len(); };
+            list<int32> xs;
+            xs.push(1);
+            push(xs, 2);
+            xs.insert(0, 5);
+            int32 last = xs.pop();
+            xs.set(0, xs.get(1) + last);
+            xs[1] = 7;
+            int32 first = xs[0];
+            int32 removed = xs.remove(0);
+            int64 total = count(xs) + len(xs);
+            list<str> words = [\"a\", \"b\"];
+            for word in words { print(word); };
+            list<list<int32>> rows = [];
+            rows.push(xs);
+            xs.clear();
+            free(words);",
+        )
+        .unwrap();
+
+        assert!(module.contains("__tpl_list_reserve"));
+    }
+
+    #[test]
+    fn lists_errors_test() {
+        let diagnostics = compile_errors(
+            "list<int32> xs;\nxs.push(\"a\");\nint32 a = 5;\na.push(1);\nint32 b = xs.get();",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Function `push()` expected value of type `int32`, but found `str`!",
+                "Function `push()` requires list or map as the first argument, but found `int32`!",
+                "Function `get()` requires 2 arguments, but 1 found!",
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_test() {
+        let module = compile_source(
+            "map<str, int32> counts;
+            list<str> words = [\"a\", \"b\", \"a\"];
+            for word in words { counts.set(word, counts.get(word, 0) + 1); };
+            bool has_a = counts.has(\"a\");
+            bool removed = counts.remove(\"b\");
+            int64 total = len(counts);
+            list<str> keys = counts.keys();
+            for key in counts { print(key); };
+            map<int64, str> names;
+            names.set(1, \"one\");
+            str name = names.get(2, \"none\");
+            free(counts);",
+        )
+        .unwrap();
+
+        assert!(module.contains("__tpl_map_find_str"));
+        assert!(module.contains("__tpl_map_reserve_int"));
+    }
+
+    #[test]
+    fn maps_errors_test() {
+        let diagnostics = compile_errors(
+            "map<str, int32> counts;
+            counts.set(1, 2);
+            counts.push(1);
+            map<bool, int32> flags;",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Map key must be `str`, but found `int32`!",
+                "Function `push()` is not supported for `map<str, int32>` type!",
+                "Map keys must be `str` or integers, but found `bool`!",
+            ]
+        );
+    }
+
+    #[test]
+    fn closures_test() {
+        let source = String::from(
+            "define int32 apply(fn<int32(int32)> callback, int32 value) { return callback(value); };
+            int32 base = 10;
+            int32[2] offsets = [1, 2];
+            fn<int32(int32)> add = int32 (int32 x) { return x + base + offsets[1]; };
+            int32 first = add(5);
+            int32 second = apply(add, 7);
+            fn<int32()> counter = int32 () { base += 1; return base; };
+            counter();
+            fn<int32(int32)> twice = int32 (int32 x) { return add(add(x)); };
+            int32 third = twice(1);",
+        );
+        let ast = parse_source(&source);
+
+        let ctx = inkwell::context::Context::create();
+        let mut compiler = Compiler::new(&ctx, "test", String::from("test.tpl"), source);
+        compiler.generate(ast).unwrap();
+
+        assert!(compiler.main_function.verify(false));
+        assert!(compiler.module.verify().is_ok());
+    }
+
+    #[test]
+    fn closures_errors_test() {
+        let diagnostics = compile_errors(
+            "int32 base = 10;
+            fn<int32(int32)> add = int32 (int32 x) { return x + base; };
+            add(\"a\");
+            fn<int32()> broken = int32 () { return missing; };
+            define int32 apply(fn<int32> callback) { return callback(1); };
+            fn<int32> erased = add;
+            erased(\"a\", 2);",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Lambda function expected arguments types [int32], but found [str]!",
+                "Variable `missing` is not defined!",
+                "Function `callback` can't be called through `fn<int32>` type without signature, use `fn<int32(...)>` instead!",
+                "Function `erased` can't be called through `fn<int32>` type without signature, use `fn<int32(...)>` instead!",
+            ]
+        );
+    }
+
+    #[test]
+    fn higher_order_functions_test() {
+        let source = String::from(
+            "define list<int32> transform(list<int32> xs, fn<int32(int32)> f) { list<int32> out; for x in xs { out.push(f(x)); }; return out; };
+            define list<int32> filter(list<int32> xs, fn<bool(int32)> keep) { list<int32> out; for x in xs { if keep(x) { out.push(x); }; }; return out; };
+            define fn<int32(int32)> make_adder(int32 n) { return int32 (int32 x) { return x + n; }; };
+            list<int32> xs = [1, 2, 3];
+            fn<int32(int32)> add2 = make_adder(2);
+            list<int32> ys = transform(xs, add2);
+            list<int32> big = filter(ys, bool (int32 x) { return x > 3; });
+            fn<int32> erased = add2;
+            int32 value = add2(5);",
+        );
+        let ast = parse_source(&source);
+
+        let ctx = inkwell::context::Context::create();
+        let mut compiler = Compiler::new(&ctx, "test", String::from("test.tpl"), source);
+        compiler.generate(ast).unwrap();
+
+        assert!(compiler.main_function.verify(false));
+        assert!(compiler.module.verify().is_ok());
+    }
+
+    #[test]
+    fn fn_signature_errors_test() {
+        let diagnostics = compile_errors(
+            "define int32 apply(fn<int32(int32)> f, int32 v) { return f(v); };
+            fn<int32(str)> named = int32 (str s) { return 0; };
+            apply(named, 1);
+            fn<int32(int32)> f = int32 (int32 x) { return x; };
+            f(\"a\");
+            define fn<int32(int32)> make() { return bool (int32 x) { return true; }; };",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Function `apply` expected arguments types [fn<int32(int32)>, int32], but found [fn<int32(str)>, int32]!",
+                "Lambda function expected arguments types [int32], but found [str]!",
+                "Function must return `fn<int32(int32)>`, but found `fn<bool(int32)>`!",
+            ]
+        );
+    }
+
+    #[test]
+    fn strings_runtime_test() {
+        let module = compile_source(
+            "str greeting = \"Hello, \";
+            str name = input(\"Name: \");
+            str message = concat(greeting, name);
+            str count = to_str(len(message));
+            print(concat(message, count));
+            free(message);
+            free(greeting);",
+        )
+        .unwrap();
+
+        assert!(module.contains("__tpl_str_concat"));
+        assert!(module.contains("__tpl_str_read_line"));
+        assert!(module.contains("__tpl_str_free"));
+        assert!(module.contains("__tpl_alloc_panic"));
+        // literals carry the same `{len, owned}` header as heap strings
+        assert!(module.contains("{ i64 7, i64 0, [8 x i8] c\"Hello, \\00\" }"));
+        assert!(module.contains("snprintf"));
+        assert!(!module.contains("strcat"));
+    }
+
+    #[test]
+    fn strings_library_test() {
+        let module = compile_source(
+            "str text = \"  Hello, World  \";
+            str trimmed = trim(text);
+            str hello = substr(trimmed, 0, 5);
+            int64 comma = find(trimmed, \",\");
+            bool has_world = trimmed.contains(\"World\");
+            bool starts = trimmed.starts_with(\"Hello\") && trimmed.ends_with(\"World\");
+            list<str> words = split(\"a,b,c\", \",\");
+            str joined = join(words, \" - \");
+            str replaced = replace(trimmed, \"World\", \"TPL\");
+            str loud = to_upper(hello);
+            str quiet = hello.to_lower();
+            int32 times = 3;
+            str line = repeat(\"=\", times);",
+        )
+        .unwrap();
+
+        assert!(module.contains("__tpl_str_split"));
+        assert!(module.contains("__tpl_str_join"));
+        assert!(module.contains("__tpl_str_replace"));
+        assert!(module.contains("__tpl_str_trim"));
+    }
+
+    #[test]
+    fn callable_variables_shadowing_test() {
+        // function values are called instead of builtins with the same name
+        let module = compile_source(
+            "fn<int64(str)> find = int64 (str text) { return 0; };
+            int64 position = find(\"abc\");
+            fn<int32()> args = int32 () { return 2; };
+            int32 count = args();
+            list<int32> xs = [1, 2];
+            int64 len = len(xs);",
+        )
+        .unwrap();
+
+        assert!(!module.contains("strstr"));
+    }
+
+    #[test]
+    fn strings_library_errors_test() {
+        let diagnostics = compile_errors(
+            "str text = \"abc\";
+            str part = substr(text, \"1\", 2);
+            bool found = contains(text);
+            str joined = join(text, \",\");",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Function `substr()` expected `int` as argument 2, but found `str`!",
+                "Function `contains()` requires 2 arguments, but 1 found!",
+                "Function `join()` expected `list<str>` as argument 1, but found `str`!",
+            ]
+        );
+    }
+
+    #[test]
+    fn string_comparison_test() {
+        let module = compile_source(
+            "define bool is_missing(str value) { return value == null; };
+            str name = \"tpl\";
+            str other = concat(\"t\", \"pl\");
+            bool same = name == other;
+            bool before = \"abc\" < name;
+            bool after = name >= \"tpl\";
+            bool different = other != \"TPL\";
+            bool first = name[0] == 't';
+            bool ordered = 'a' < 'b';
+            bool missing = is_missing(name);",
+        )
+        .unwrap();
+
+        assert!(module.contains("__tpl_str_compare"));
+    }
+
+    #[test]
+    fn string_comparison_errors_test() {
+        let diagnostics = compile_errors(
+            "str name = \"tpl\";
+            bool first = name == 't';
+            bool second = 'a' != name;",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Cannot compare `str` and `char` types! Convert `char` with `to_str()` first",
+                "Cannot compare `char` and `str` types! Convert `char` with `to_str()` first",
+            ]
+        );
+    }
+
+    #[test]
+    fn files_test() {
+        let module = compile_source(
+            "FILE* handle = file(\"data.txt\", \"r\");
+            if handle == null { print(\"cannot open data.txt\"); } else {
+                while !eof(handle) { str text = read_line(handle); print(text); };
+                bool rewound = seek(handle, 0);
+                char first = read_char(handle);
+                int64 position = tell(handle);
+                close(handle);
+            };
+            str content = read_all(\"data.txt\");
+            bool missing = content == null || !exists(\"data.txt\");",
+        )
+        .unwrap();
+
+        assert!(module.contains("__tpl_str_read_file"));
+        assert!(module.contains("ungetc"));
+    }
+
+    #[test]
+    fn files_errors_test() {
+        let diagnostics = compile_errors(
+            "str path = \"data.txt\";
+            str text = read_line(path);
+            bool found = exists(1);
+            FILE* handle = file(path, \"r\");
+            bool moved = seek(handle, \"start\");",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Function `read_line` requires file pointer as the first argument!",
+                "Function `exists` requires path string, but found `int8`!",
+                "Function `seek` requires integer position, but found `str`!",
+            ]
+        );
+    }
+
+    #[test]
+    fn process_test() {
+        let module = compile_source(
+            "str[] arguments = args();
+            int32 count = arg_count();
+            for argument in arguments { print(argument); };
+            define int32 check(int32 expected) { if arg_count() != expected { exit(2); }; return 0; };
+            check(1);
+            if len(arguments) > 3 { exit(count); };
+            if count > 1 { return 1; print(count); };
+            return 0;
+            print(count);",
+        )
+        .unwrap();
+
+        assert!(module.contains("define i32 @main(i32 %0, ptr %1)"));
+        assert!(module.contains("@__tpl_argc"));
+        assert!(module.contains("@__tpl_argv"));
+        assert!(module.contains("@__tpl_arguments"));
+        assert!(module.contains("@exit"));
+    }
+
+    #[test]
+    fn process_errors_test() {
+        let diagnostics = compile_errors(
+            "int32 count = arg_count(1);
+            exit(\"failure\");
+            return 1.5;",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Function `arg_count()` requires 0 arguments, but 1 found!",
+                "Function `exit` requires integer exit code, but found `str`!",
+                "Top-level `return` requires integer exit code, but found `float64`!",
+            ]
+        );
+    }
+
+    #[test]
+    fn loop_control_test() {
+        let source = String::from(
+            "int32 a = 0; while a < 10 { a += 1; if a == 2 { continue; }; if a == 5 { break; }; }; for (int32 i = 0; i < 3; i++) { continue; };",
+        );
+        let ast = parse_source(&source);
+
+        let ctx = inkwell::context::Context::create();
+        let mut compiler = Compiler::new(&ctx, "test", String::from("test.tpl"), source);
+        compiler.generate(ast).unwrap();
+
+        assert!(compiler.loop_blocks.is_empty());
+        assert!(compiler.main_function.verify(false));
+    }
+
+    #[test]
+    fn block_scoping_test() {
+        let source = String::from(
+            "int32 a = 1; if a == 1 { int64 a = 2; int64 b = a; }; for (int32 i = 0; i < 3; i++) { int32 c = i; };",
+        );
+        let ast = parse_source(&source);
+
+        let ctx = inkwell::context::Context::create();
+        let mut compiler = Compiler::new(&ctx, "test", String::from("test.tpl"), source);
+        compiler.generate(ast).unwrap();
+
+        assert_eq!(compiler.variables.depth(), 1);
+        assert_eq!(compiler.variables.get("a").unwrap().str_type, "int32");
+
+        for id in ["b", "i", "c"] {
+            assert!(compiler.variables.get(id).is_none());
+            assert!(compiler.variables.is_expired(id));
+        }
+
+        assert!(!compiler.variables.is_expired("a"));
+        assert!(compiler.main_function.verify(false));
+    }
+
+    #[test]
+    fn codegen_diagnostics_test() {
+        let diagnostics =
+            compile_source("int32 a = b;\nc = 5;\nint32 d = 1;\nprint(d);").unwrap_err();
+
+        assert_eq!(diagnostics.len(), 2);
+
+        assert_eq!(diagnostics[0].error_type, ErrorType::NotDefined);
+        assert_eq!(diagnostics[0].file, "test.tpl");
+        assert_eq!(diagnostics[0].line, 0);
+
+        assert_eq!(diagnostics[1].error_type, ErrorType::NotDefined);
+        assert_eq!(diagnostics[1].message, "Variable `c` is not defined!");
+        assert_eq!(diagnostics[1].line, 1);
+
+        // statement `c = 5` is marked under the source line
+        assert!(diagnostics[1].format_error().contains("^^^^^"));
+
+        // only the failed expression `b` is marked, not the whole statement
+        let first_error = diagnostics[0].format_error();
+        assert!(first_error.contains(&format!("{}^", " ".repeat(10))));
+        assert!(!first_error.contains("^^"));
+    }
+
+    #[test]
+    fn float_arithmetic_test() {
+        let source = String::from(
+            "float64 a = 1.5; float32 b = 2; a = a * 2 + b; if a > 3 { a -= 0.5; }; int32 c = to_int32(a); float32 d = to_float32(c); print(a, b, d, to_str(d));",
+        );
+        let ast = parse_source(&source);
+
+        let ctx = inkwell::context::Context::create();
+        let mut compiler = Compiler::new(&ctx, "test", String::from("test.tpl"), source);
+        compiler.generate(ast).unwrap();
+
+        assert_eq!(compiler.variables.get("a").unwrap().str_type, "float64");
+        assert_eq!(compiler.variables.get("d").unwrap().str_type, "float32");
+        assert!(compiler.main_function.verify(false));
+
+        let float32 = compiler
+            .compile_value(Value::Float(0.25), 0, Some(String::from("float32")))
+            .unwrap();
+        let float64 = compiler.compile_value(Value::Float(0.25), 0, None).unwrap();
+
+        assert_eq!(
+            (float32.0.as_str(), float64.0.as_str()),
+            ("float32", "float64")
+        );
+        assert!(float32.1.is_float_value());
+    }
+
+    #[test]
+    fn float_widening_test() {
+        // `float64` operand is kept wide even when `float32` is expected
+        let module_ir =
+            compile_source("float64 a = 0.1; float32 b = 2; float64 c = b * a;").unwrap();
+
+        assert!(module_ir.contains("fpext float"));
+        assert!(module_ir.contains("fmul double"));
+        assert!(!module_ir.contains("fptrunc"));
+
+        assert_eq!(
+            compile_errors("float64 a = 0.1; float32 b = 2; float32 c = a * b;"),
+            vec!["Type `float32` expected for 'c' variable, but found `float64`!"]
+        );
+    }
+
+    #[test]
+    fn unary_operators_test() {
+        let source = String::from(
+            "int32 a = 5; int32 b = -(a + 1) * 2; int32 c = ~a; float64 d = -1.5; bool e = !(a > b) && !false; if !e { print(b); };",
+        );
+        let ast = parse_source(&source);
+
+        let ctx = inkwell::context::Context::create();
+        let mut compiler = Compiler::new(&ctx, "test", String::from("test.tpl"), source);
+        compiler.generate(ast).unwrap();
+
+        assert!(compiler.main_function.verify(false));
+
+        let negated = compiler
+            .compile_expression(
+                Expressions::Unary {
+                    operand: String::from("-"),
+                    object: Box::new(Expressions::Value(Value::Integer(7), Span::default())),
+                    line: 0,
+                    span: Span::default(),
+                },
+                0,
+                compiler.main_function,
+                Some(String::from("int32")),
+            )
+            .unwrap();
+
+        assert_eq!(
+            negated.1.into_int_value().get_sign_extended_constant(),
+            Some(-7)
+        );
+
+        let wrong_type = compiler.compile_expression(
+            Expressions::Unary {
+                operand: String::from("~"),
+                object: Box::new(Expressions::Value(Value::Boolean(true), Span::default())),
+                line: 0,
+                span: Span::default(),
+            },
+            0,
+            compiler.main_function,
+            None,
+        );
+
+        assert!(wrong_type.is_err());
+    }
+
+    #[test]
+    fn compound_assignment_test() {
+        compile_source(
+            "int32 a = 7 % 3; a %= 2; a <<= 2; a |= 1; a &= 6; a ^= 3; a >>= 1; float64 f = 7.5 % 2.0; bool b = a <= 3 && a >= 0 && f >= 1.5;",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn compound_assignment_errors_test() {
+        let diagnostics = compile_errors("int32 a = 1;\na += 1.5;\na -= 1;");
+
+        assert_eq!(
+            diagnostics,
+            vec!["Expected type `int32`, but found `float64`!"]
+        );
+    }
+
+    #[test]
+    fn structures_test() {
+        let source = String::from(
+            "struct Point { int32 x; int32 y; };
+            struct Line { Point a; Point b; str name; Line* next; };
+            define int32 sum(Point p) { return p.x + p.y; };
+            define void shift(Point* p, int32 dx) { p.x = p.x + dx; };
+            define Point make(int32 x) { return Point { x: x, y: 0 }; };
+            Point p = Point { y: 2, x: 1 };
+            p.x = 3;
+            shift(&p, 2);
+            p.shift(1);
+            Point* pp = &p;
+            pp.y = 5;
+            shift(pp, 1);
+            Line l;
+            l.a = make(4);
+            l.a.y = p.x;
+            l.next = &l;
+            int32 s = sum(l.a) + l.next.a.x;
+            int64 size_p = size(Point);
+            str t = type(pp);",
+        );
+        let ast = parse_source(&source);
+
+        let ctx = inkwell::context::Context::create();
+        let mut compiler = Compiler::new(&ctx, "test", String::from("test.tpl"), source);
+        compiler.generate(ast).unwrap();
+
+        assert!(compiler.main_function.verify(false));
+        assert_eq!(
+            compiler.structures["Line"].fields,
+            vec![
+                (String::from("a"), String::from("Point")),
+                (String::from("b"), String::from("Point")),
+                (String::from("name"), String::from("str")),
+                (String::from("next"), String::from("Line*")),
+            ]
+        );
+    }
+
+    #[test]
+    fn structures_errors_test() {
+        let diagnostics = compile_errors(
+            "struct Point { int32 x; int32 y; };\nPoint a = Point { x: 1 };\nPoint b = Point { x: 1, y: 2, z: 3 };\nPoint c;\nc.z = 1;\nc.x = \"a\";\nint32 d = 5;\nd.x = 1;",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Field `y` of `Point` structure is not initialized!",
+                "Structure `Point` has no field `z`!",
+                "Structure `Point` has no field `z`!",
+                "Expected type `int32`, but found `str`!",
+                "Type `int32` has no fields!",
+            ]
+        );
+    }
+
+    #[test]
+    fn enums_and_match_test() {
+        let source = String::from(
+            "struct Point { int32 x; int32 y; };
+            enum Shape { Circle(int32), Rect(int32, Point), Empty };
+            define int32 area(Shape shape) {
+                int32 result = 0;
+                match shape {
+                    Circle(r) => { result = 3 * r * r; }
+                    Rect(w, corner) => { result = w * corner.x; }
+                    Empty => { return 0; }
+                };
+                return result;
+            };
+            Shape a = Shape.Circle(2);
+            Shape b = Shape.Rect(3, Point { x: 4, y: 0 });
+            int32 total = area(a) + area(b) + area(Shape.Empty);
+            match b {
+                Rect(_, corner) => { print(corner.x); }
+                _ => { print(total); }
+            };
+            int64 shape_size = size(Shape);",
+        );
+        let ast = parse_source(&source);
+
+        let ctx = inkwell::context::Context::create();
+        let mut compiler = Compiler::new(&ctx, "test", String::from("test.tpl"), source);
+        compiler.generate(ast).unwrap();
+
+        assert!(compiler.main_function.verify(false));
+        assert_eq!(compiler.enums["Shape"].variant("Rect").unwrap().0, 1);
+    }
+
+    #[test]
+    fn non_exhaustive_match_test() {
+        let diagnostics = compile_source(
+            "enum Shape { Circle(int32), Rect(int32, int32), Empty };\nShape a = Shape.Circle(1);\nmatch a { Circle(r) => {} };\nmatch a { Rect(w) => {} _ => {} };\nShape b = Shape.Square(1);",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.line, diagnostic.message.as_str()))
+                .collect::<Vec<(usize, &str)>>(),
+            vec![
+                (
+                    2,
+                    "Non-exhaustive match! Variants `Rect`, `Empty` are not covered, add them or `_` arm"
+                ),
+                (3, "Variant `Shape.Rect` has 2 values, but 1 bindings found!"),
+                (4, "Enumeration `Shape` has no variant `Square`!"),
+            ]
+        );
+    }
+
+    #[test]
+    fn else_if_and_switch_test() {
+        let source = String::from(
+            "define str grade(int32 score) {
+                if score >= 90 { return \"A\"; } else if score >= 75 { return \"B\"; } else if score >= 50 { return \"C\"; } else { return \"F\"; };
+            };
+            define int32 days(int8 month) {
+                int32 result = 0;
+                switch (month) {
+                    case 2 { result = 28; }
+                    case 4, 6, 9, 11 { result = 30; }
+                    default { result = 31; }
+                };
+                return result;
+            };
+            char c = 'b';
+            switch c { case 'a', 'b' { print(grade(80)); } };
+            int32 total = days(2) + days(12);",
+        );
+        let ast = parse_source(&source);
+
+        let ctx = inkwell::context::Context::create();
+        let mut compiler = Compiler::new(&ctx, "test", String::from("test.tpl"), source);
+        compiler.generate(ast).unwrap();
+
+        assert!(compiler.main_function.verify(false));
+
+        let days = compiler.functions["days"].function_value;
+        let switch_instruction = days
+            .get_basic_block_iter()
+            .flat_map(|block| block.get_instructions())
+            .find(|instruction| {
+                instruction.get_opcode() == inkwell::values::InstructionOpcode::Switch
+            });
+
+        assert!(switch_instruction.is_some());
+    }
+
+    #[test]
+    fn switch_errors_test() {
+        let diagnostics = compile_errors(
+            "int32 a = 1;\nswitch a { case 1 {} case 1 {} };\nswitch a { case a {} };\nswitch true { default {} };",
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Duplicate case value `1` found in `switch`!",
+                "Case value must be a constant!",
+                "Only integers and chars can be switched, but found `bool`!",
+            ]
+        );
+    }
+
+    #[test]
+    fn for_in_test() {
+        compile_source(
+            "int32 total = 0;
+            for i in 0..10 { total += i; };
+            for i in 1..=total { if i > 3 { break; }; };
+            int8[3] values = [1, 2, 3];
+            for value in values { print(value); };
+            for c in \"abc\" { if c == 'b' { continue; }; print(c); };",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn inclusive_range_test() {
+        // range ends on the maximal `int8` value, counter is checked before increment
+        let module = compile_source(
+            "int8 first = 0;
+            int32 count = 0;
+            for i in first..=127 { count += 1; };",
+        )
+        .unwrap();
+
+        assert!(module.contains("for_increment"));
+    }
+
+    #[test]
+    fn for_in_errors_test() {
+        let diagnostics =
+            compile_errors("for i in 0..true {};\nfor i in 5 {};\nfor i in 0..3 {};\nprint(i);");
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                "Range bounds must have the same type, but found `int32` and `bool`!",
+                "Type `int32` is not iterable!",
+                "Variable `i` is used outside of its scope!",
+            ]
+        );
+    }
+
     #[test]
     fn type_function_test() {
         let ctx = inkwell::context::Context::create();
@@ -2737,12 +5708,15 @@ mod tests {
             Compiler::new(&ctx, "test", String::from("none"), String::from("test.tpl"));
         compiler.builder.position_at_end(compiler.current_block);
 
-        let value_int8 = Expressions::Value(Value::Integer(0));
+        let value_int8 = Expressions::Value(Value::Integer(0), Span::default());
 
-        let call_result = compiler.build_type_call(vec![value_int8], 0, compiler.main_function);
-        let ptr_value = call_result.1.into_pointer_value().to_string();
+        let call_result = compiler
+            .build_type_call(vec![value_int8], 0, compiler.main_function)
+            .unwrap();
+        let module = compiler.module.print_to_string().to_string();
 
         assert_eq!(call_result.0, "str".to_string());
-        assert!(ptr_value.contains("int8"));
+        assert!(call_result.1.is_pointer_value());
+        assert!(module.contains("c\"int8\\00\""));
     }
 }
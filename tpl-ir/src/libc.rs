@@ -8,12 +8,20 @@ pub trait Libc {
 
     fn __c_printf(&mut self) -> Self::Function;
     fn __c_sprintf(&mut self) -> Self::Function;
+    fn __c_snprintf(&mut self) -> Self::Function;
+    fn __c_dprintf(&mut self) -> Self::Function;
 
     // strings
 
     fn __c_strcat(&mut self) -> Self::Function;
     fn __c_strcmp(&mut self) -> Self::Function;
     fn __c_strlen(&mut self) -> Self::Function;
+    fn __c_strncmp(&mut self) -> Self::Function;
+    fn __c_strstr(&mut self) -> Self::Function;
+
+    fn __c_toupper(&mut self) -> Self::Function;
+    fn __c_tolower(&mut self) -> Self::Function;
+    fn __c_isspace(&mut self) -> Self::Function;
 
     fn __c_scanf(&mut self) -> Self::Function;
     fn __c_sscanf(&mut self) -> Self::Function;
@@ -32,12 +40,19 @@ pub trait Libc {
     fn __c_fprintf(&mut self) -> Self::Function;
     fn __c_fwrite(&mut self) -> Self::Function;
     fn __c_fgetc(&mut self) -> Self::Function;
+    fn __c_fread(&mut self) -> Self::Function;
+    fn __c_ungetc(&mut self) -> Self::Function;
 
     fn __c_rewind(&mut self) -> Self::Function;
     fn __c_fseek(&mut self) -> Self::Function;
     fn __c_fsetpos(&mut self) -> Self::Function;
     fn __c_ftell(&mut self) -> Self::Function;
     fn __c_feof(&mut self) -> Self::Function;
+    fn __c_access(&mut self) -> Self::Function;
+
+    // process
+
+    fn __c_exit(&mut self) -> Self::Function;
 }
 
 impl<'ctx> Libc for Compiler<'ctx> {
@@ -65,6 +80,29 @@ impl<'ctx> Libc for Compiler<'ctx> {
         sprintf_fn
     }
 
+    fn __c_snprintf(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get("snprintf") {
+            return *function_value;
+        }
+
+        let snprintf_type = self.context.i32_type().fn_type(
+            &[
+                self.context.ptr_type(AddressSpace::default()).into(),
+                self.context.i64_type().into(),
+                self.context.ptr_type(AddressSpace::default()).into(),
+            ],
+            true,
+        );
+        let snprintf_fn =
+            self.module
+                .add_function("snprintf", snprintf_type, Some(Linkage::External));
+        let _ = self
+            .built_functions
+            .insert("snprintf".to_string(), snprintf_fn);
+
+        snprintf_fn
+    }
+
     fn __c_printf(&mut self) -> FunctionValue<'ctx> {
         if let Some(function_value) = self.built_functions.get("printf") {
             return *function_value;
@@ -82,6 +120,29 @@ impl<'ctx> Libc for Compiler<'ctx> {
         printf_fn
     }
 
+    fn __c_dprintf(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get("dprintf") {
+            return *function_value;
+        }
+
+        // printing to file descriptor (used for `stderr` without its symbol)
+        let dprintf_type = self.context.i32_type().fn_type(
+            &[
+                self.context.i32_type().into(),
+                self.context.ptr_type(AddressSpace::default()).into(),
+            ],
+            true,
+        );
+        let dprintf_fn = self
+            .module
+            .add_function("dprintf", dprintf_type, Some(Linkage::External));
+        let _ = self
+            .built_functions
+            .insert("dprintf".to_string(), dprintf_fn);
+
+        dprintf_fn
+    }
+
     fn __c_strcat(&mut self) -> FunctionValue<'ctx> {
         if let Some(function_value) = self.built_functions.get("strcat") {
             return *function_value;
@@ -139,6 +200,106 @@ impl<'ctx> Libc for Compiler<'ctx> {
         strlen_fn
     }
 
+    fn __c_strncmp(&mut self) -> Self::Function {
+        if let Some(function_value) = self.built_functions.get("strncmp") {
+            return *function_value;
+        }
+
+        let strncmp_type = self.context.i32_type().fn_type(
+            &[
+                self.context.ptr_type(AddressSpace::default()).into(),
+                self.context.ptr_type(AddressSpace::default()).into(),
+                self.context.i64_type().into(),
+            ],
+            false,
+        );
+        let strncmp_fn = self
+            .module
+            .add_function("strncmp", strncmp_type, Some(Linkage::External));
+        let _ = self
+            .built_functions
+            .insert("strncmp".to_string(), strncmp_fn);
+
+        strncmp_fn
+    }
+
+    fn __c_strstr(&mut self) -> Self::Function {
+        if let Some(function_value) = self.built_functions.get("strstr") {
+            return *function_value;
+        }
+
+        let strstr_type = self.context.ptr_type(AddressSpace::default()).fn_type(
+            &[
+                self.context.ptr_type(AddressSpace::default()).into(),
+                self.context.ptr_type(AddressSpace::default()).into(),
+            ],
+            false,
+        );
+        let strstr_fn = self
+            .module
+            .add_function("strstr", strstr_type, Some(Linkage::External));
+        let _ = self.built_functions.insert("strstr".to_string(), strstr_fn);
+
+        strstr_fn
+    }
+
+    fn __c_toupper(&mut self) -> Self::Function {
+        if let Some(function_value) = self.built_functions.get("toupper") {
+            return *function_value;
+        }
+
+        let toupper_type = self
+            .context
+            .i32_type()
+            .fn_type(&[self.context.i32_type().into()], false);
+        let toupper_fn = self
+            .module
+            .add_function("toupper", toupper_type, Some(Linkage::External));
+        let _ = self
+            .built_functions
+            .insert("toupper".to_string(), toupper_fn);
+
+        toupper_fn
+    }
+
+    fn __c_tolower(&mut self) -> Self::Function {
+        if let Some(function_value) = self.built_functions.get("tolower") {
+            return *function_value;
+        }
+
+        let tolower_type = self
+            .context
+            .i32_type()
+            .fn_type(&[self.context.i32_type().into()], false);
+        let tolower_fn = self
+            .module
+            .add_function("tolower", tolower_type, Some(Linkage::External));
+        let _ = self
+            .built_functions
+            .insert("tolower".to_string(), tolower_fn);
+
+        tolower_fn
+    }
+
+    fn __c_isspace(&mut self) -> Self::Function {
+        if let Some(function_value) = self.built_functions.get("isspace") {
+            return *function_value;
+        }
+
+        let isspace_type = self
+            .context
+            .i32_type()
+            .fn_type(&[self.context.i32_type().into()], false);
+        let isspace_fn = self
+            .module
+            .add_function("isspace", isspace_type, Some(Linkage::External));
+        let _ = self
+            .built_functions
+            .insert("isspace".to_string(), isspace_fn);
+
+        isspace_fn
+    }
+
     fn __c_scanf(&mut self) -> Self::Function {
         if let Some(function_value) = self.built_functions.get("scanf") {
             return *function_value;
@@ -342,6 +503,52 @@ impl<'ctx> Libc for Compiler<'ctx> {
         fn_obj
     }
 
+    fn __c_fread(&mut self) -> Self::Function {
+        const FN_NAME: &str = "fread";
+
+        if let Some(function_value) = self.built_functions.get(FN_NAME) {
+            return *function_value;
+        }
+
+        let fn_type = self.context.i64_type().fn_type(
+            &[
+                self.context.ptr_type(AddressSpace::default()).into(),
+                self.context.i64_type().into(),
+                self.context.i64_type().into(),
+                self.context.ptr_type(AddressSpace::default()).into(),
+            ],
+            false,
+        );
+        let fn_obj = self
+            .module
+            .add_function(FN_NAME, fn_type, Some(Linkage::External));
+        let _ = self.built_functions.insert(FN_NAME.to_string(), fn_obj);
+
+        fn_obj
+    }
+
+    fn __c_ungetc(&mut self) -> Self::Function {
+        const FN_NAME: &str = "ungetc";
+
+        if let Some(function_value) = self.built_functions.get(FN_NAME) {
+            return *function_value;
+        }
+
+        let fn_type = self.context.i32_type().fn_type(
+            &[
+                self.context.i32_type().into(),
+                self.context.ptr_type(AddressSpace::default()).into(),
+            ],
+            false,
+        );
+        let fn_obj = self
+            .module
+            .add_function(FN_NAME, fn_type, Some(Linkage::External));
+        let _ = self.built_functions.insert(FN_NAME.to_string(), fn_obj);
+
+        fn_obj
+    }
+
     fn __c_rewind(&mut self) -> Self::Function {
         const FN_NAME: &str = "rewind";
 
@@ -393,7 +600,8 @@ impl<'ctx> Libc for Compiler<'ctx> {
             return *function_value;
         }
 
-        let fn_type = self.context.i32_type().fn_type(
+        // `long` is 64-bit wide on supported targets
+        let fn_type = self.context.i64_type().fn_type(
             &[
                 self.context.ptr_type(AddressSpace::default()).into(),
             ],
@@ -428,6 +636,28 @@ impl<'ctx> Libc for Compiler<'ctx> {
         fn_obj
     }
 
+    fn __c_access(&mut self) -> Self::Function {
+        const FN_NAME: &str = "access";
+
+        if let Some(function_value) = self.built_functions.get(FN_NAME) {
+            return *function_value;
+        }
+
+        let fn_type = self.context.i32_type().fn_type(
+            &[
+                self.context.ptr_type(AddressSpace::default()).into(),
+                self.context.i32_type().into(),
+            ],
+            false,
+        );
+        let fn_obj = self
+            .module
+            .add_function(FN_NAME, fn_type, Some(Linkage::External));
+        let _ = self.built_functions.insert(FN_NAME.to_string(), fn_obj);
+
+        fn_obj
+    }
+
     fn __c_fsetpos(&mut self) -> Self::Function {
         const FN_NAME: &str = "fsetpos";
 
@@ -448,4 +678,21 @@ impl<'ctx> Libc for Compiler<'ctx> {
 
         fn_obj
     }
+
+    fn __c_exit(&mut self) -> Self::Function {
+        if let Some(function_value) = self.built_functions.get("exit") {
+            return *function_value;
+        }
+
+        let exit_type = self
+            .context
+            .void_type()
+            .fn_type(&[self.context.i32_type().into()], false);
+        let exit_fn = self
+            .module
+            .add_function("exit", exit_type, Some(Linkage::External));
+        let _ = self.built_functions.insert("exit".to_string(), exit_fn);
+
+        exit_fn
+    }
 }
@@ -0,0 +1,729 @@
+// Toy Programming Language | by mealet
+// https://github.com/mealet/tpl-lang
+// =========================================
+// Project licensed under the BSD-3 LICENSE.
+// Check the `LICENSE` file to more info.
+
+use inkwell::{
+    module::Linkage,
+    types::{BasicType, StructType},
+    values::{BasicValueEnum, FunctionValue, IntValue, PointerValue},
+    AddressSpace,
+};
+use tpl_parser::expressions::Expressions;
+
+use crate::{error::ErrorType, libc::Libc, runtime::Runtime, CompileResult, Compiler};
+
+// NOTE: `list<T>` value is a pointer to the heap header { T* data, int64 len, int64 capacity },
+// so copies of list share the same elements (like pointers do)
+
+const LIST_RESERVE_NAME: &str = "__tpl_list_reserve";
+const LIST_MIN_CAPACITY: u64 = 4;
+
+const DATA_FIELD: u32 = 0;
+const LEN_FIELD: u32 = 1;
+const CAPACITY_FIELD: u32 = 2;
+
+pub trait List<'ctx> {
+    fn __list_header_type(&self) -> StructType<'ctx>;
+    fn __runtime_list_reserve(&mut self) -> FunctionValue<'ctx>;
+
+    fn list_field_pointer(
+        &self,
+        list: PointerValue<'ctx>,
+        field: u32,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn list_data_pointer(
+        &self,
+        list: PointerValue<'ctx>,
+        element_type: &str,
+        index: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn list_element_pointer(
+        &mut self,
+        list: (String, BasicValueEnum<'ctx>),
+        index: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<(String, PointerValue<'ctx>)>;
+    fn list_element_value(
+        &self,
+        element_type: &str,
+        pointer: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<BasicValueEnum<'ctx>>;
+    fn list_element_size(&self, element_type: &str, line: usize) -> CompileResult<IntValue<'ctx>>;
+
+    fn build_list_new(
+        &mut self,
+        datatype: &str,
+        elements: Option<(PointerValue<'ctx>, u64)>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_list_with_len(
+        &mut self,
+        datatype: &str,
+        list_len: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_list_len(
+        &self,
+        list: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>>;
+    fn build_list_reserve(
+        &mut self,
+        list: PointerValue<'ctx>,
+        needed: IntValue<'ctx>,
+        element_type: &str,
+        line: usize,
+    ) -> CompileResult<()>;
+    fn build_list_shift(
+        &mut self,
+        list: PointerValue<'ctx>,
+        element_type: &str,
+        from: IntValue<'ctx>,
+        to: IntValue<'ctx>,
+        count: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<()>;
+    fn build_list_free(&mut self, list: PointerValue<'ctx>, line: usize) -> CompileResult<()>;
+
+    fn build_list_call(
+        &mut self,
+        method: String,
+        compiled_list: (String, BasicValueEnum<'ctx>),
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn list_index_argument(
+        &mut self,
+        argument: Expressions,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<IntValue<'ctx>>;
+    fn list_value_argument(
+        &mut self,
+        method: &str,
+        argument: Expressions,
+        element_type: &str,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+}
+
+impl<'ctx> List<'ctx> for Compiler<'ctx> {
+    fn __list_header_type(&self) -> StructType<'ctx> {
+        self.context.struct_type(
+            &[
+                self.context.ptr_type(AddressSpace::default()).into(),
+                self.context.i64_type().into(),
+                self.context.i64_type().into(),
+            ],
+            false,
+        )
+    }
+
+    fn __runtime_list_reserve(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(LIST_RESERVE_NAME) {
+            return *function_value;
+        }
+
+        // void __tpl_list_reserve(list<T> list, int64 needed, int64 element_size)
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let fn_type = self
+            .context
+            .void_type()
+            .fn_type(&[ptr_type.into(), i64_type.into(), i64_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(LIST_RESERVE_NAME, fn_type, Some(Linkage::Private));
+        let realloc_fn = self.__c_realloc();
+        let header_type = self.__list_header_type();
+
+        // routine body is built aside from current position
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, "entry");
+        let grow = self.context.append_basic_block(function, "grow");
+        let done = self.context.append_basic_block(function, "done");
+
+        self.builder.position_at_end(entry);
+
+        let list = function.get_nth_param(0).unwrap().into_pointer_value();
+        let needed = function.get_nth_param(1).unwrap().into_int_value();
+        let element_size = function.get_nth_param(2).unwrap().into_int_value();
+
+        let capacity_ptr = self
+            .builder
+            .build_struct_gep(header_type, list, CAPACITY_FIELD, "")
+            .unwrap();
+        let capacity = self
+            .builder
+            .build_load(i64_type, capacity_ptr, "capacity")
+            .unwrap()
+            .into_int_value();
+        let is_enough = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULE, needed, capacity, "")
+            .unwrap();
+
+        let _ = self.builder.build_conditional_branch(is_enough, done, grow);
+
+        // capacity is doubled, but it's never less than needed
+        self.builder.position_at_end(grow);
+
+        let min_capacity = i64_type.const_int(LIST_MIN_CAPACITY, false);
+        let doubled = self
+            .builder
+            .build_int_mul(capacity, i64_type.const_int(2, false), "")
+            .unwrap();
+
+        let is_small = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULT, doubled, min_capacity, "")
+            .unwrap();
+        let new_capacity = self
+            .builder
+            .build_select(is_small, min_capacity, doubled, "")
+            .unwrap()
+            .into_int_value();
+
+        let is_small = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULT, new_capacity, needed, "")
+            .unwrap();
+        let new_capacity = self
+            .builder
+            .build_select(is_small, needed, new_capacity, "")
+            .unwrap()
+            .into_int_value();
+
+        let data_ptr = self
+            .builder
+            .build_struct_gep(header_type, list, DATA_FIELD, "")
+            .unwrap();
+        let data = self.builder.build_load(ptr_type, data_ptr, "data").unwrap();
+        let new_size = self
+            .builder
+            .build_int_mul(new_capacity, element_size, "")
+            .unwrap();
+        let new_data = self
+            .builder
+            .build_call(realloc_fn, &[data.into(), new_size.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+
+        let _ = self.builder.build_store(data_ptr, new_data);
+        let _ = self.builder.build_store(capacity_ptr, new_capacity);
+        let _ = self.builder.build_unconditional_branch(done);
+
+        self.builder.position_at_end(done);
+        let _ = self.builder.build_return(None);
+
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self
+            .built_functions
+            .insert(LIST_RESERVE_NAME.to_string(), function);
+
+        function
+    }
+
+    fn list_field_pointer(
+        &self,
+        list: PointerValue<'ctx>,
+        field: u32,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        self.builder
+            .build_struct_gep(self.__list_header_type(), list, field, "")
+            .map_err(|_| self.error("Unable to get list field!", ErrorType::BuildError, line))
+    }
+
+    fn list_data_pointer(
+        &self,
+        list: PointerValue<'ctx>,
+        element_type: &str,
+        index: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        // data pointer is loaded every time, because it's moved on growth
+        let data_ptr = self.list_field_pointer(list, DATA_FIELD, line)?;
+        let data = self
+            .builder
+            .build_load(self.context.ptr_type(AddressSpace::default()), data_ptr, "")
+            .map_err(|_| {
+                self.error(
+                    "Unable to load list elements!",
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })?
+            .into_pointer_value();
+
+        unsafe {
+            self.builder
+                .build_in_bounds_gep(
+                    self.get_storage_type(element_type, line)?,
+                    data,
+                    &[index],
+                    "",
+                )
+                .map_err(|_| {
+                    self.error(
+                        "Unable to get pointer to list element!",
+                        ErrorType::BuildError,
+                        line,
+                    )
+                })
+        }
+    }
+
+    fn list_element_pointer(
+        &mut self,
+        list: (String, BasicValueEnum<'ctx>),
+        index: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<(String, PointerValue<'ctx>)> {
+        let element_type = Compiler::__list_element_type(&list.0);
+        let list = list.1.into_pointer_value();
+
+        let list_len = self.build_list_len(list, line)?;
+        self.build_bounds_check(index, list_len, line)?;
+
+        let pointer = self.list_data_pointer(list, &element_type, index, line)?;
+        Ok((element_type, pointer))
+    }
+
+    fn list_element_value(
+        &self,
+        element_type: &str,
+        pointer: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<BasicValueEnum<'ctx>> {
+        let value = self
+            .builder
+            .build_load(self.get_storage_type(element_type, line)?, pointer, "")
+            .map_err(|_| {
+                self.error(
+                    format!("Unable to load `{}` value!", element_type),
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })?;
+
+        // arrays are copied out, because list memory can be moved or overwritten
+        Ok(self
+            .array_from_aggregate((element_type.to_string(), value), line)?
+            .1)
+    }
+
+    fn list_element_size(&self, element_type: &str, line: usize) -> CompileResult<IntValue<'ctx>> {
+        self.get_storage_type(element_type, line)?
+            .size_of()
+            .ok_or_else(|| {
+                self.error(
+                    format!("Unable to get size of `{}` type!", element_type),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })
+    }
+
+    fn build_list_new(
+        &mut self,
+        datatype: &str,
+        elements: Option<(PointerValue<'ctx>, u64)>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let i64_type = self.context.i64_type();
+
+        let Some((array, array_len)) = elements else {
+            return self.build_list_with_len(datatype, i64_type.const_zero(), line);
+        };
+
+        // elements are copied from array literal to the heap
+        let element_type = Compiler::__list_element_type(datatype);
+        let list_len = i64_type.const_int(array_len, false);
+        let list = self.build_list_with_len(datatype, list_len, line)?;
+
+        let element_size = self.list_element_size(&element_type, line)?;
+        let data_size = self
+            .builder
+            .build_int_mul(element_size, list_len, "")
+            .unwrap();
+        let data = self.list_data_pointer(
+            list.1.into_pointer_value(),
+            &element_type,
+            i64_type.const_zero(),
+            line,
+        )?;
+
+        self.builder
+            .build_memcpy(data, 1, array, 1, data_size)
+            .map_err(|_| {
+                self.error(
+                    "Unable to copy list elements!",
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })?;
+
+        Ok(list)
+    }
+
+    fn build_list_with_len(
+        &mut self,
+        datatype: &str,
+        list_len: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        // elements memory is allocated, but it's not initialized
+        let element_type = Compiler::__list_element_type(datatype);
+        let malloc_fn = self.__c_malloc();
+
+        let header_size = self.__list_header_type().size_of().ok_or_else(|| {
+            self.error("Unable to get size of list!", ErrorType::BuildError, line)
+        })?;
+        let list = self
+            .builder
+            .build_call(malloc_fn, &[header_size.into()], "list")
+            .map_err(|_| self.error("Unable to allocate list!", ErrorType::MemoryError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        let element_size = self.list_element_size(&element_type, line)?;
+        let data_size = self
+            .builder
+            .build_int_mul(element_size, list_len, "")
+            .unwrap();
+        let data = self
+            .builder
+            .build_call(malloc_fn, &[data_size.into()], "")
+            .map_err(|_| {
+                self.error(
+                    "Unable to allocate list elements!",
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })?
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+
+        let data_ptr = self.list_field_pointer(list, DATA_FIELD, line)?;
+        let len_ptr = self.list_field_pointer(list, LEN_FIELD, line)?;
+        let capacity_ptr = self.list_field_pointer(list, CAPACITY_FIELD, line)?;
+
+        let _ = self.builder.build_store(data_ptr, data);
+        let _ = self.builder.build_store(len_ptr, list_len);
+        let _ = self.builder.build_store(capacity_ptr, list_len);
+
+        Ok((datatype.to_string(), list.into()))
+    }
+
+    fn build_list_len(
+        &self,
+        list: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>> {
+        let len_ptr = self.list_field_pointer(list, LEN_FIELD, line)?;
+
+        self.builder
+            .build_load(self.context.i64_type(), len_ptr, "list_len")
+            .map(|value| value.into_int_value())
+            .map_err(|_| self.error("Unable to load list length!", ErrorType::MemoryError, line))
+    }
+
+    fn build_list_reserve(
+        &mut self,
+        list: PointerValue<'ctx>,
+        needed: IntValue<'ctx>,
+        element_type: &str,
+        line: usize,
+    ) -> CompileResult<()> {
+        let reserve_fn = self.__runtime_list_reserve();
+        let element_size = self.list_element_size(element_type, line)?;
+
+        self.builder
+            .build_call(
+                reserve_fn,
+                &[list.into(), needed.into(), element_size.into()],
+                "",
+            )
+            .map(|_| ())
+            .map_err(|_| {
+                self.error(
+                    "Unable to grow list capacity!",
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })
+    }
+
+    fn build_list_shift(
+        &mut self,
+        list: PointerValue<'ctx>,
+        element_type: &str,
+        from: IntValue<'ctx>,
+        to: IntValue<'ctx>,
+        count: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<()> {
+        // moves `count` elements starting at `from` to `to` (ranges can overlap)
+        let source = self.list_data_pointer(list, element_type, from, line)?;
+        let destination = self.list_data_pointer(list, element_type, to, line)?;
+
+        let element_size = self.list_element_size(element_type, line)?;
+        let size = self.builder.build_int_mul(count, element_size, "").unwrap();
+
+        self.builder
+            .build_memmove(destination, 1, source, 1, size)
+            .map(|_| ())
+            .map_err(|_| {
+                self.error(
+                    "Unable to move list elements!",
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })
+    }
+
+    fn build_list_free(&mut self, list: PointerValue<'ctx>, line: usize) -> CompileResult<()> {
+        let free_fn = self.__c_free();
+
+        let data_ptr = self.list_field_pointer(list, DATA_FIELD, line)?;
+        let data = self
+            .builder
+            .build_load(self.context.ptr_type(AddressSpace::default()), data_ptr, "")
+            .map_err(|_| {
+                self.error(
+                    "Unable to load list elements!",
+                    ErrorType::MemoryError,
+                    line,
+                )
+            })?;
+
+        let _ = self.builder.build_call(free_fn, &[data.into()], "");
+        let _ = self.builder.build_call(free_fn, &[list.into()], "");
+
+        Ok(())
+    }
+
+    fn build_list_call(
+        &mut self,
+        method: String,
+        compiled_list: (String, BasicValueEnum<'ctx>),
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        // arguments go after the list, but it's counted too: `push(xs, 1)`
+        let required_arguments = match method.as_str() {
+            "pop" | "clear" => 0,
+            "push" | "get" | "remove" => 1,
+            "set" | "insert" => 2,
+            _ => {
+                return Err(self.error(
+                    format!(
+                        "Function `{}()` is not supported for `{}` type!",
+                        method, compiled_list.0
+                    ),
+                    ErrorType::NotSupported,
+                    line,
+                ));
+            }
+        };
+
+        if arguments.len() != required_arguments {
+            return Err(self.arguments_count_error(
+                &method,
+                required_arguments + 1,
+                arguments.len() + 1,
+                line,
+            ));
+        }
+
+        let element_type = Compiler::__list_element_type(&compiled_list.0);
+        let list = compiled_list.1.into_pointer_value();
+
+        let i64_type = self.context.i64_type();
+        let one = i64_type.const_int(1, false);
+        let void_value = (
+            String::from("void"),
+            self.context.bool_type().const_zero().into(),
+        );
+
+        match method.as_str() {
+            "push" => {
+                let value = self.list_value_argument(
+                    &method,
+                    arguments[0].clone(),
+                    &element_type,
+                    line,
+                    function,
+                )?;
+
+                let list_len = self.build_list_len(list, line)?;
+                let new_len = self.builder.build_int_add(list_len, one, "").unwrap();
+
+                self.build_list_reserve(list, new_len, &element_type, line)?;
+
+                let element_ptr = self.list_data_pointer(list, &element_type, list_len, line)?;
+                self.build_value_store(element_ptr, &value, line)?;
+
+                let len_ptr = self.list_field_pointer(list, LEN_FIELD, line)?;
+                let _ = self.builder.build_store(len_ptr, new_len);
+
+                Ok(void_value)
+            }
+            "pop" => {
+                let list_len = self.build_list_len(list, line)?;
+                let last_index = self.builder.build_int_sub(list_len, one, "").unwrap();
+
+                self.build_bounds_check(last_index, list_len, line)?;
+
+                let element_ptr = self.list_data_pointer(list, &element_type, last_index, line)?;
+                let value = self.list_element_value(&element_type, element_ptr, line)?;
+
+                let len_ptr = self.list_field_pointer(list, LEN_FIELD, line)?;
+                let _ = self.builder.build_store(len_ptr, last_index);
+
+                Ok((element_type, value))
+            }
+            "get" => {
+                let index = self.list_index_argument(arguments[0].clone(), line, function)?;
+                let (element_type, element_ptr) =
+                    self.list_element_pointer(compiled_list, index, line)?;
+                let value = self.list_element_value(&element_type, element_ptr, line)?;
+
+                Ok((element_type, value))
+            }
+            "set" => {
+                let index = self.list_index_argument(arguments[0].clone(), line, function)?;
+                let value = self.list_value_argument(
+                    &method,
+                    arguments[1].clone(),
+                    &element_type,
+                    line,
+                    function,
+                )?;
+
+                let (_, element_ptr) = self.list_element_pointer(compiled_list, index, line)?;
+                self.build_value_store(element_ptr, &value, line)?;
+
+                Ok(void_value)
+            }
+            "insert" => {
+                let index = self.list_index_argument(arguments[0].clone(), line, function)?;
+                let value = self.list_value_argument(
+                    &method,
+                    arguments[1].clone(),
+                    &element_type,
+                    line,
+                    function,
+                )?;
+
+                // inserting right after the last element is allowed
+                let list_len = self.build_list_len(list, line)?;
+                let new_len = self.builder.build_int_add(list_len, one, "").unwrap();
+
+                self.build_bounds_check(index, new_len, line)?;
+                self.build_list_reserve(list, new_len, &element_type, line)?;
+
+                let next_index = self.builder.build_int_add(index, one, "").unwrap();
+                let tail_len = self.builder.build_int_sub(list_len, index, "").unwrap();
+
+                self.build_list_shift(list, &element_type, index, next_index, tail_len, line)?;
+
+                let element_ptr = self.list_data_pointer(list, &element_type, index, line)?;
+                self.build_value_store(element_ptr, &value, line)?;
+
+                let len_ptr = self.list_field_pointer(list, LEN_FIELD, line)?;
+                let _ = self.builder.build_store(len_ptr, new_len);
+
+                Ok(void_value)
+            }
+            "remove" => {
+                let index = self.list_index_argument(arguments[0].clone(), line, function)?;
+                let (element_type, element_ptr) =
+                    self.list_element_pointer(compiled_list, index, line)?;
+                let value = self.list_element_value(&element_type, element_ptr, line)?;
+
+                let list_len = self.build_list_len(list, line)?;
+                let new_len = self.builder.build_int_sub(list_len, one, "").unwrap();
+
+                let next_index = self.builder.build_int_add(index, one, "").unwrap();
+                let tail_len = self.builder.build_int_sub(new_len, index, "").unwrap();
+
+                self.build_list_shift(list, &element_type, next_index, index, tail_len, line)?;
+
+                let len_ptr = self.list_field_pointer(list, LEN_FIELD, line)?;
+                let _ = self.builder.build_store(len_ptr, new_len);
+
+                Ok((element_type, value))
+            }
+            "clear" => {
+                // capacity is kept for next pushes
+                let len_ptr = self.list_field_pointer(list, LEN_FIELD, line)?;
+                let _ = self.builder.build_store(len_ptr, i64_type.const_zero());
+
+                Ok(void_value)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn list_index_argument(
+        &mut self,
+        argument: Expressions,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<IntValue<'ctx>> {
+        let index = self.compile_expression(argument, line, function, None)?;
+
+        if !index.0.starts_with("int") {
+            return Err(self.error("Non-integer index found!", ErrorType::NotExpected, line));
+        }
+
+        self.builder
+            .build_int_cast_sign_flag(index.1.into_int_value(), self.context.i64_type(), true, "")
+            .map_err(|_| self.error("Unable to cast index!", ErrorType::BuildError, line))
+    }
+
+    fn list_value_argument(
+        &mut self,
+        method: &str,
+        argument: Expressions,
+        element_type: &str,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let value =
+            self.compile_expression(argument, line, function, Some(element_type.to_string()))?;
+        let value = self.coerce_value(value, element_type, line)?;
+
+        if value.0 != element_type {
+            return Err(self.error(
+                format!(
+                    "Function `{}()` expected value of type `{}`, but found `{}`!",
+                    method, element_type, value.0
+                ),
+                ErrorType::TypeError,
+                line,
+            ));
+        }
+
+        Ok(value)
+    }
+}
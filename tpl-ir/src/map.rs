@@ -0,0 +1,1318 @@
+// Toy Programming Language | by mealet
+// https://github.com/mealet/tpl-lang
+// =========================================
+// Project licensed under the BSD-3 LICENSE.
+// Check the `LICENSE` file to more info.
+
+use inkwell::{
+    module::Linkage,
+    types::{BasicType, BasicTypeEnum, StructType},
+    values::{BasicValueEnum, FunctionValue, IntValue, PointerValue},
+    AddressSpace,
+};
+use tpl_parser::expressions::Expressions;
+
+use crate::{error::ErrorType, libc::Libc, list::List, CompileResult, Compiler};
+
+// NOTE: `map<K, V>` value is a pointer to the heap header
+// { int8* states, K* keys, V* values, int64 len, int64 capacity, int64 tombstones }.
+// Hash table uses open addressing with linear probing, removed slots become tombstones.
+// Integer keys are stored as `int64`, `str` keys are stored as pointers (without copying).
+
+const MAP_ALLOC_NAME: &str = "__tpl_map_alloc";
+const MAP_FIND_NAME: &str = "__tpl_map_find";
+const MAP_RESERVE_NAME: &str = "__tpl_map_reserve";
+const MAP_MIN_CAPACITY: u64 = 8;
+const MAP_KEY_SIZE: u64 = 8;
+
+const STATES_FIELD: u32 = 0;
+const KEYS_FIELD: u32 = 1;
+const VALUES_FIELD: u32 = 2;
+const LEN_FIELD: u32 = 3;
+const CAPACITY_FIELD: u32 = 4;
+const TOMBSTONES_FIELD: u32 = 5;
+
+const EMPTY_SLOT: u64 = 0;
+const USED_SLOT: u64 = 1;
+const TOMBSTONE_SLOT: u64 = 2;
+
+// FNV-1a for strings and Fibonacci hashing for integers
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+const FIBONACCI_MULTIPLIER: u64 = 0x9e3779b97f4a7c15;
+
+pub trait Map<'ctx> {
+    fn __map_header_type(&self) -> StructType<'ctx>;
+    fn __map_key_type(&self, string_keys: bool) -> BasicTypeEnum<'ctx>;
+    fn __runtime_map_alloc(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_map_find(&mut self, string_keys: bool) -> FunctionValue<'ctx>;
+    fn __runtime_map_reserve(&mut self, string_keys: bool) -> FunctionValue<'ctx>;
+
+    fn map_field_pointer(
+        &self,
+        map: PointerValue<'ctx>,
+        field: u32,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn map_field_value(
+        &self,
+        map: PointerValue<'ctx>,
+        field: u32,
+        line: usize,
+    ) -> CompileResult<BasicValueEnum<'ctx>>;
+    fn map_value_size(&self, value_type: &str, line: usize) -> CompileResult<IntValue<'ctx>>;
+    fn map_find(
+        &mut self,
+        map: PointerValue<'ctx>,
+        key: BasicValueEnum<'ctx>,
+        string_keys: bool,
+        line: usize,
+    ) -> CompileResult<(IntValue<'ctx>, PointerValue<'ctx>, IntValue<'ctx>)>;
+    fn map_value_pointer(
+        &self,
+        map: PointerValue<'ctx>,
+        value_type: &str,
+        index: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn map_key_argument(
+        &mut self,
+        argument: Expressions,
+        key_type: &str,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<BasicValueEnum<'ctx>>;
+
+    fn build_map_new(
+        &mut self,
+        datatype: &str,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_map_len(&self, map: PointerValue<'ctx>, line: usize) -> CompileResult<IntValue<'ctx>>;
+    fn build_map_keys(
+        &mut self,
+        datatype: &str,
+        map: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_map_free(&mut self, map: PointerValue<'ctx>, line: usize) -> CompileResult<()>;
+
+    fn build_map_call(
+        &mut self,
+        method: String,
+        compiled_map: (String, BasicValueEnum<'ctx>),
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+}
+
+impl<'ctx> Map<'ctx> for Compiler<'ctx> {
+    fn __map_header_type(&self) -> StructType<'ctx> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+
+        self.context.struct_type(
+            &[
+                ptr_type.into(),
+                ptr_type.into(),
+                ptr_type.into(),
+                i64_type.into(),
+                i64_type.into(),
+                i64_type.into(),
+            ],
+            false,
+        )
+    }
+
+    fn __map_key_type(&self, string_keys: bool) -> BasicTypeEnum<'ctx> {
+        if string_keys {
+            self.context.ptr_type(AddressSpace::default()).into()
+        } else {
+            self.context.i64_type().into()
+        }
+    }
+
+    fn __runtime_map_alloc(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(MAP_ALLOC_NAME) {
+            return *function_value;
+        }
+
+        // void __tpl_map_alloc(map<K, V> map, int64 capacity, int64 value_size)
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i8_type = self.context.i8_type();
+        let i64_type = self.context.i64_type();
+        let fn_type = self
+            .context
+            .void_type()
+            .fn_type(&[ptr_type.into(), i64_type.into(), i64_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(MAP_ALLOC_NAME, fn_type, Some(Linkage::Private));
+        let malloc_fn = self.__c_malloc();
+        let header_type = self.__map_header_type();
+
+        // routine body is built aside from current position
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, "entry");
+        self.builder.position_at_end(entry);
+
+        let map = function.get_nth_param(0).unwrap().into_pointer_value();
+        let capacity = function.get_nth_param(1).unwrap().into_int_value();
+        let value_size = function.get_nth_param(2).unwrap().into_int_value();
+
+        let keys_size = self
+            .builder
+            .build_int_mul(capacity, i64_type.const_int(MAP_KEY_SIZE, false), "")
+            .unwrap();
+        let values_size = self
+            .builder
+            .build_int_mul(capacity, value_size, "")
+            .unwrap();
+
+        let mut arrays = Vec::new();
+        for size in [capacity, keys_size, values_size] {
+            let array = self
+                .builder
+                .build_call(malloc_fn, &[size.into()], "")
+                .unwrap()
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_pointer_value();
+
+            // states must be empty and values are zeroed for `get()` default selection
+            let _ = self
+                .builder
+                .build_memset(array, 1, i8_type.const_zero(), size);
+
+            arrays.push(array);
+        }
+
+        let fields = [
+            (STATES_FIELD, arrays[0].into()),
+            (KEYS_FIELD, arrays[1].into()),
+            (VALUES_FIELD, arrays[2].into()),
+            (LEN_FIELD, i64_type.const_zero().into()),
+            (CAPACITY_FIELD, capacity.into()),
+            (TOMBSTONES_FIELD, i64_type.const_zero().into()),
+        ];
+
+        for (field, value) in fields {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(header_type, map, field, "")
+                .unwrap();
+            let _ = self.builder.build_store::<BasicValueEnum>(field_ptr, value);
+        }
+
+        let _ = self.builder.build_return(None);
+
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self
+            .built_functions
+            .insert(MAP_ALLOC_NAME.to_string(), function);
+
+        function
+    }
+
+    fn __runtime_map_find(&mut self, string_keys: bool) -> FunctionValue<'ctx> {
+        let function_name = format!(
+            "{}_{}",
+            MAP_FIND_NAME,
+            if string_keys { "str" } else { "int" }
+        );
+
+        if let Some(function_value) = self.built_functions.get(&function_name) {
+            return *function_value;
+        }
+
+        // int64 __tpl_map_find_str(map<K, V> map, K key)
+        // returns slot with the key, or slot where it should be inserted
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i8_type = self.context.i8_type();
+        let i64_type = self.context.i64_type();
+        let key_type = self.__map_key_type(string_keys);
+        let fn_type = i64_type.fn_type(&[ptr_type.into(), key_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(&function_name, fn_type, Some(Linkage::Private));
+        let strcmp_fn = self.__c_strcmp();
+        let header_type = self.__map_header_type();
+
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, "entry");
+        self.builder.position_at_end(entry);
+
+        let map = function.get_nth_param(0).unwrap().into_pointer_value();
+        let key = function.get_nth_param(1).unwrap();
+
+        let index_ptr = self.builder.build_alloca(i64_type, "index").unwrap();
+        let tombstone_ptr = self.builder.build_alloca(i64_type, "tombstone").unwrap();
+
+        let load_field = |field: u32, field_type: BasicTypeEnum<'ctx>| {
+            let field_ptr = self
+                .builder
+                .build_struct_gep(header_type, map, field, "")
+                .unwrap();
+            self.builder.build_load(field_type, field_ptr, "").unwrap()
+        };
+
+        let capacity = load_field(CAPACITY_FIELD, i64_type.into()).into_int_value();
+        let states = load_field(STATES_FIELD, ptr_type.into()).into_pointer_value();
+        let keys = load_field(KEYS_FIELD, ptr_type.into()).into_pointer_value();
+
+        let mask = self
+            .builder
+            .build_int_sub(capacity, i64_type.const_int(1, false), "mask")
+            .unwrap();
+
+        let hash = if string_keys {
+            let hash_ptr = self.builder.build_alloca(i64_type, "hash").unwrap();
+            let char_index_ptr = self.builder.build_alloca(i64_type, "char_index").unwrap();
+
+            let _ = self
+                .builder
+                .build_store(hash_ptr, i64_type.const_int(FNV_OFFSET, false));
+            let _ = self
+                .builder
+                .build_store(char_index_ptr, i64_type.const_zero());
+
+            let hash_loop = self.context.append_basic_block(function, "hash_loop");
+            let hash_step = self.context.append_basic_block(function, "hash_step");
+            let hash_done = self.context.append_basic_block(function, "hash_done");
+
+            let _ = self.builder.build_unconditional_branch(hash_loop);
+            self.builder.position_at_end(hash_loop);
+
+            let char_index = self
+                .builder
+                .build_load(i64_type, char_index_ptr, "")
+                .unwrap()
+                .into_int_value();
+            let char_ptr = unsafe {
+                self.builder
+                    .build_in_bounds_gep(i8_type, key.into_pointer_value(), &[char_index], "")
+                    .unwrap()
+            };
+            let char_value = self
+                .builder
+                .build_load(i8_type, char_ptr, "")
+                .unwrap()
+                .into_int_value();
+            let is_end = self
+                .builder
+                .build_int_compare(
+                    inkwell::IntPredicate::EQ,
+                    char_value,
+                    i8_type.const_zero(),
+                    "",
+                )
+                .unwrap();
+
+            let _ = self
+                .builder
+                .build_conditional_branch(is_end, hash_done, hash_step);
+            self.builder.position_at_end(hash_step);
+
+            let hash = self
+                .builder
+                .build_load(i64_type, hash_ptr, "")
+                .unwrap()
+                .into_int_value();
+            let char_value = self
+                .builder
+                .build_int_z_extend(char_value, i64_type, "")
+                .unwrap();
+            let hash = self.builder.build_xor(hash, char_value, "").unwrap();
+            let hash = self
+                .builder
+                .build_int_mul(hash, i64_type.const_int(FNV_PRIME, false), "")
+                .unwrap();
+            let next_index = self
+                .builder
+                .build_int_add(char_index, i64_type.const_int(1, false), "")
+                .unwrap();
+
+            let _ = self.builder.build_store(hash_ptr, hash);
+            let _ = self.builder.build_store(char_index_ptr, next_index);
+            let _ = self.builder.build_unconditional_branch(hash_loop);
+
+            self.builder.position_at_end(hash_done);
+            self.builder
+                .build_load(i64_type, hash_ptr, "")
+                .unwrap()
+                .into_int_value()
+        } else {
+            let hash = self
+                .builder
+                .build_int_mul(
+                    key.into_int_value(),
+                    i64_type.const_int(FIBONACCI_MULTIPLIER, false),
+                    "",
+                )
+                .unwrap();
+            let high_bits = self
+                .builder
+                .build_right_shift(hash, i64_type.const_int(32, false), false, "")
+                .unwrap();
+            self.builder.build_xor(hash, high_bits, "").unwrap()
+        };
+
+        let start_index = self.builder.build_and(hash, mask, "").unwrap();
+        let _ = self.builder.build_store(index_ptr, start_index);
+        let _ = self
+            .builder
+            .build_store(tombstone_ptr, i64_type.const_all_ones());
+
+        let probe = self.context.append_basic_block(function, "probe");
+        let not_empty = self.context.append_basic_block(function, "not_empty");
+        let tombstone = self.context.append_basic_block(function, "tombstone");
+        let compare = self.context.append_basic_block(function, "compare");
+        let found = self.context.append_basic_block(function, "found");
+        let next = self.context.append_basic_block(function, "next");
+        let empty = self.context.append_basic_block(function, "empty");
+
+        let _ = self.builder.build_unconditional_branch(probe);
+
+        // probing slots one by one until the key or an empty slot is found
+        self.builder.position_at_end(probe);
+
+        let index = self
+            .builder
+            .build_load(i64_type, index_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let state_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(i8_type, states, &[index], "")
+                .unwrap()
+        };
+        let state = self
+            .builder
+            .build_load(i8_type, state_ptr, "state")
+            .unwrap()
+            .into_int_value();
+        let is_empty = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                state,
+                i8_type.const_int(EMPTY_SLOT, false),
+                "",
+            )
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_empty, empty, not_empty);
+        self.builder.position_at_end(not_empty);
+
+        let is_used = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                state,
+                i8_type.const_int(USED_SLOT, false),
+                "",
+            )
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_used, compare, tombstone);
+
+        // the first tombstone is remembered to reuse it for insertion
+        self.builder.position_at_end(tombstone);
+
+        let first_tombstone = self
+            .builder
+            .build_load(i64_type, tombstone_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let is_first = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                first_tombstone,
+                i64_type.const_all_ones(),
+                "",
+            )
+            .unwrap();
+        let first_tombstone = self
+            .builder
+            .build_select(is_first, index, first_tombstone, "")
+            .unwrap();
+
+        let _ = self.builder.build_store(tombstone_ptr, first_tombstone);
+        let _ = self.builder.build_unconditional_branch(next);
+
+        self.builder.position_at_end(compare);
+
+        let slot_key_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(key_type, keys, &[index], "")
+                .unwrap()
+        };
+        let slot_key = self.builder.build_load(key_type, slot_key_ptr, "").unwrap();
+
+        let is_equal = if string_keys {
+            let compared = self
+                .builder
+                .build_call(strcmp_fn, &[slot_key.into(), key.into()], "")
+                .unwrap()
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+
+            self.builder
+                .build_int_compare(
+                    inkwell::IntPredicate::EQ,
+                    compared,
+                    compared.get_type().const_zero(),
+                    "",
+                )
+                .unwrap()
+        } else {
+            self.builder
+                .build_int_compare(
+                    inkwell::IntPredicate::EQ,
+                    slot_key.into_int_value(),
+                    key.into_int_value(),
+                    "",
+                )
+                .unwrap()
+        };
+
+        let _ = self.builder.build_conditional_branch(is_equal, found, next);
+
+        self.builder.position_at_end(found);
+        let _ = self.builder.build_return(Some(&index));
+
+        self.builder.position_at_end(next);
+
+        let next_index = self
+            .builder
+            .build_int_add(index, i64_type.const_int(1, false), "")
+            .unwrap();
+        let next_index = self.builder.build_and(next_index, mask, "").unwrap();
+
+        let _ = self.builder.build_store(index_ptr, next_index);
+        let _ = self.builder.build_unconditional_branch(probe);
+
+        self.builder.position_at_end(empty);
+
+        let first_tombstone = self
+            .builder
+            .build_load(i64_type, tombstone_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let has_tombstone = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::NE,
+                first_tombstone,
+                i64_type.const_all_ones(),
+                "",
+            )
+            .unwrap();
+        let insert_index = self
+            .builder
+            .build_select(has_tombstone, first_tombstone, index, "")
+            .unwrap();
+
+        let _ = self.builder.build_return(Some(&insert_index));
+
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self.built_functions.insert(function_name, function);
+
+        function
+    }
+
+    fn __runtime_map_reserve(&mut self, string_keys: bool) -> FunctionValue<'ctx> {
+        let function_name = format!(
+            "{}_{}",
+            MAP_RESERVE_NAME,
+            if string_keys { "str" } else { "int" }
+        );
+
+        if let Some(function_value) = self.built_functions.get(&function_name) {
+            return *function_value;
+        }
+
+        // void __tpl_map_reserve_str(map<K, V> map, int64 value_size)
+        // makes room for one more key (load factor is kept under 3/4)
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i8_type = self.context.i8_type();
+        let i64_type = self.context.i64_type();
+        let key_type = self.__map_key_type(string_keys);
+        let fn_type = self
+            .context
+            .void_type()
+            .fn_type(&[ptr_type.into(), i64_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(&function_name, fn_type, Some(Linkage::Private));
+        let alloc_fn = self.__runtime_map_alloc();
+        let find_fn = self.__runtime_map_find(string_keys);
+        let free_fn = self.__c_free();
+        let header_type = self.__map_header_type();
+
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, "entry");
+        let grow = self.context.append_basic_block(function, "grow");
+        let rehash_cond = self.context.append_basic_block(function, "rehash_cond");
+        let rehash_body = self.context.append_basic_block(function, "rehash_body");
+        let rehash_move = self.context.append_basic_block(function, "rehash_move");
+        let rehash_next = self.context.append_basic_block(function, "rehash_next");
+        let rehash_done = self.context.append_basic_block(function, "rehash_done");
+        let done = self.context.append_basic_block(function, "done");
+
+        self.builder.position_at_end(entry);
+
+        let map = function.get_nth_param(0).unwrap().into_pointer_value();
+        let value_size = function.get_nth_param(1).unwrap().into_int_value();
+
+        let slot_ptr = self.builder.build_alloca(i64_type, "slot").unwrap();
+
+        let field_ptr = |field: u32| {
+            self.builder
+                .build_struct_gep(header_type, map, field, "")
+                .unwrap()
+        };
+        let load_field = |field: u32, field_type: BasicTypeEnum<'ctx>| {
+            self.builder
+                .build_load(field_type, field_ptr(field), "")
+                .unwrap()
+        };
+
+        let len = load_field(LEN_FIELD, i64_type.into()).into_int_value();
+        let tombstones = load_field(TOMBSTONES_FIELD, i64_type.into()).into_int_value();
+        let capacity = load_field(CAPACITY_FIELD, i64_type.into()).into_int_value();
+
+        let used = self.builder.build_int_add(len, tombstones, "").unwrap();
+        let used = self
+            .builder
+            .build_int_add(used, i64_type.const_int(1, false), "")
+            .unwrap();
+        let used = self
+            .builder
+            .build_int_mul(used, i64_type.const_int(4, false), "")
+            .unwrap();
+        let limit = self
+            .builder
+            .build_int_mul(capacity, i64_type.const_int(3, false), "")
+            .unwrap();
+        let is_enough = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULE, used, limit, "")
+            .unwrap();
+
+        let _ = self.builder.build_conditional_branch(is_enough, done, grow);
+
+        // table is doubled only if live keys take half of it, otherwise tombstones are dropped
+        self.builder.position_at_end(grow);
+
+        let old_states = load_field(STATES_FIELD, ptr_type.into()).into_pointer_value();
+        let old_keys = load_field(KEYS_FIELD, ptr_type.into()).into_pointer_value();
+        let old_values = load_field(VALUES_FIELD, ptr_type.into()).into_pointer_value();
+
+        let live = self
+            .builder
+            .build_int_add(len, i64_type.const_int(1, false), "")
+            .unwrap();
+        let live = self
+            .builder
+            .build_int_mul(live, i64_type.const_int(2, false), "")
+            .unwrap();
+        let is_crowded = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::UGT, live, capacity, "")
+            .unwrap();
+        let doubled = self
+            .builder
+            .build_int_mul(capacity, i64_type.const_int(2, false), "")
+            .unwrap();
+        let new_capacity = self
+            .builder
+            .build_select(is_crowded, doubled, capacity, "")
+            .unwrap();
+
+        let _ = self.builder.build_call(
+            alloc_fn,
+            &[map.into(), new_capacity.into(), value_size.into()],
+            "",
+        );
+        let _ = self.builder.build_store(slot_ptr, i64_type.const_zero());
+        let _ = self.builder.build_unconditional_branch(rehash_cond);
+
+        // moving used slots to the new table
+        self.builder.position_at_end(rehash_cond);
+
+        let slot = self
+            .builder
+            .build_load(i64_type, slot_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let has_slots = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULT, slot, capacity, "")
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(has_slots, rehash_body, rehash_done);
+        self.builder.position_at_end(rehash_body);
+
+        let state_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(i8_type, old_states, &[slot], "")
+                .unwrap()
+        };
+        let state = self
+            .builder
+            .build_load(i8_type, state_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let is_used = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                state,
+                i8_type.const_int(USED_SLOT, false),
+                "",
+            )
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_used, rehash_move, rehash_next);
+        self.builder.position_at_end(rehash_move);
+
+        let key_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(key_type, old_keys, &[slot], "")
+                .unwrap()
+        };
+        let key = self.builder.build_load(key_type, key_ptr, "").unwrap();
+        let new_slot = self
+            .builder
+            .build_call(find_fn, &[map.into(), key.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+
+        let new_states = load_field(STATES_FIELD, ptr_type.into()).into_pointer_value();
+        let new_keys = load_field(KEYS_FIELD, ptr_type.into()).into_pointer_value();
+        let new_values = load_field(VALUES_FIELD, ptr_type.into()).into_pointer_value();
+
+        let new_state_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(i8_type, new_states, &[new_slot], "")
+                .unwrap()
+        };
+        let new_key_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(key_type, new_keys, &[new_slot], "")
+                .unwrap()
+        };
+
+        let _ = self
+            .builder
+            .build_store(new_state_ptr, i8_type.const_int(USED_SLOT, false));
+        let _ = self.builder.build_store(new_key_ptr, key);
+
+        // values have any type, so they are copied as bytes
+        let old_offset = self.builder.build_int_mul(slot, value_size, "").unwrap();
+        let new_offset = self
+            .builder
+            .build_int_mul(new_slot, value_size, "")
+            .unwrap();
+        let old_value_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(i8_type, old_values, &[old_offset], "")
+                .unwrap()
+        };
+        let new_value_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(i8_type, new_values, &[new_offset], "")
+                .unwrap()
+        };
+
+        let _ = self
+            .builder
+            .build_memcpy(new_value_ptr, 1, old_value_ptr, 1, value_size);
+
+        let new_len = load_field(LEN_FIELD, i64_type.into()).into_int_value();
+        let new_len = self
+            .builder
+            .build_int_add(new_len, i64_type.const_int(1, false), "")
+            .unwrap();
+
+        let _ = self.builder.build_store(field_ptr(LEN_FIELD), new_len);
+        let _ = self.builder.build_unconditional_branch(rehash_next);
+
+        self.builder.position_at_end(rehash_next);
+
+        let next_slot = self
+            .builder
+            .build_int_add(slot, i64_type.const_int(1, false), "")
+            .unwrap();
+
+        let _ = self.builder.build_store(slot_ptr, next_slot);
+        let _ = self.builder.build_unconditional_branch(rehash_cond);
+
+        self.builder.position_at_end(rehash_done);
+
+        for old_array in [old_states, old_keys, old_values] {
+            let _ = self.builder.build_call(free_fn, &[old_array.into()], "");
+        }
+
+        let _ = self.builder.build_unconditional_branch(done);
+
+        self.builder.position_at_end(done);
+        let _ = self.builder.build_return(None);
+
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self.built_functions.insert(function_name, function);
+
+        function
+    }
+
+    fn map_field_pointer(
+        &self,
+        map: PointerValue<'ctx>,
+        field: u32,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        self.builder
+            .build_struct_gep(self.__map_header_type(), map, field, "")
+            .map_err(|_| self.error("Unable to get map field!", ErrorType::BuildError, line))
+    }
+
+    fn map_field_value(
+        &self,
+        map: PointerValue<'ctx>,
+        field: u32,
+        line: usize,
+    ) -> CompileResult<BasicValueEnum<'ctx>> {
+        let field_type: BasicTypeEnum = match field {
+            STATES_FIELD | KEYS_FIELD | VALUES_FIELD => {
+                self.context.ptr_type(AddressSpace::default()).into()
+            }
+            _ => self.context.i64_type().into(),
+        };
+        let field_ptr = self.map_field_pointer(map, field, line)?;
+
+        self.builder
+            .build_load(field_type, field_ptr, "")
+            .map_err(|_| self.error("Unable to load map field!", ErrorType::MemoryError, line))
+    }
+
+    fn map_value_size(&self, value_type: &str, line: usize) -> CompileResult<IntValue<'ctx>> {
+        self.get_storage_type(value_type, line)?
+            .size_of()
+            .ok_or_else(|| {
+                self.error(
+                    format!("Unable to get size of `{}` type!", value_type),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })
+    }
+
+    fn map_find(
+        &mut self,
+        map: PointerValue<'ctx>,
+        key: BasicValueEnum<'ctx>,
+        string_keys: bool,
+        line: usize,
+    ) -> CompileResult<(IntValue<'ctx>, PointerValue<'ctx>, IntValue<'ctx>)> {
+        // slot index, pointer to its state and the state itself
+        let find_fn = self.__runtime_map_find(string_keys);
+        let index = self
+            .builder
+            .build_call(find_fn, &[map.into(), key.into()], "")
+            .map_err(|_| self.error("Unable to find map key!", ErrorType::BuildError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+
+        let i8_type = self.context.i8_type();
+        let states = self
+            .map_field_value(map, STATES_FIELD, line)?
+            .into_pointer_value();
+        let state_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(i8_type, states, &[index], "")
+                .map_err(|_| self.error("Unable to get map slot!", ErrorType::BuildError, line))?
+        };
+        let state = self
+            .builder
+            .build_load(i8_type, state_ptr, "")
+            .map_err(|_| self.error("Unable to load map slot!", ErrorType::MemoryError, line))?
+            .into_int_value();
+
+        Ok((index, state_ptr, state))
+    }
+
+    fn map_value_pointer(
+        &self,
+        map: PointerValue<'ctx>,
+        value_type: &str,
+        index: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        let values = self
+            .map_field_value(map, VALUES_FIELD, line)?
+            .into_pointer_value();
+
+        unsafe {
+            self.builder
+                .build_in_bounds_gep(
+                    self.get_storage_type(value_type, line)?,
+                    values,
+                    &[index],
+                    "",
+                )
+                .map_err(|_| {
+                    self.error(
+                        "Unable to get pointer to map value!",
+                        ErrorType::BuildError,
+                        line,
+                    )
+                })
+        }
+    }
+
+    fn map_key_argument(
+        &mut self,
+        argument: Expressions,
+        key_type: &str,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<BasicValueEnum<'ctx>> {
+        // integer literals take key type, strings are compiled as they are
+        let expectation = (key_type != "str").then(|| key_type.to_string());
+        let key = self.compile_expression(argument, line, function, expectation)?;
+
+        if key.0 != key_type {
+            return Err(self.error(
+                format!("Map key must be `{}`, but found `{}`!", key_type, key.0),
+                ErrorType::TypeError,
+                line,
+            ));
+        }
+
+        if key_type == "str" {
+            return Ok(key.1);
+        }
+
+        // integer keys are stored as `int64`
+        self.builder
+            .build_int_cast_sign_flag(key.1.into_int_value(), self.context.i64_type(), true, "")
+            .map(|key| key.into())
+            .map_err(|_| self.error("Unable to cast map key!", ErrorType::BuildError, line))
+    }
+
+    fn build_map_new(
+        &mut self,
+        datatype: &str,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let (_, value_type) = Compiler::__map_types(datatype);
+        let value_size = self.map_value_size(&value_type, line)?;
+
+        let malloc_fn = self.__c_malloc();
+        let alloc_fn = self.__runtime_map_alloc();
+
+        let header_size = self
+            .__map_header_type()
+            .size_of()
+            .ok_or_else(|| self.error("Unable to get size of map!", ErrorType::BuildError, line))?;
+        let map = self
+            .builder
+            .build_call(malloc_fn, &[header_size.into()], "map")
+            .map_err(|_| self.error("Unable to allocate map!", ErrorType::MemoryError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+
+        let _ = self.builder.build_call(
+            alloc_fn,
+            &[
+                map.into(),
+                self.context
+                    .i64_type()
+                    .const_int(MAP_MIN_CAPACITY, false)
+                    .into(),
+                value_size.into(),
+            ],
+            "",
+        );
+
+        Ok((datatype.to_string(), map))
+    }
+
+    fn build_map_len(&self, map: PointerValue<'ctx>, line: usize) -> CompileResult<IntValue<'ctx>> {
+        Ok(self.map_field_value(map, LEN_FIELD, line)?.into_int_value())
+    }
+
+    fn build_map_keys(
+        &mut self,
+        datatype: &str,
+        map: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let (key_type, _) = Compiler::__map_types(datatype);
+        let string_keys = key_type == "str";
+
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                self.error(
+                    "Map keys are collected outside of function!",
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?;
+
+        // keys are collected into the new list with exact length
+        let map_len = self.build_map_len(map, line)?;
+        let keys_list = self.build_list_with_len(&format!("list<{}>", key_type), map_len, line)?;
+        let list = keys_list.1.into_pointer_value();
+
+        let i8_type = self.context.i8_type();
+        let i64_type = self.context.i64_type();
+        let storage_key_type = self.__map_key_type(string_keys);
+
+        let capacity = self
+            .map_field_value(map, CAPACITY_FIELD, line)?
+            .into_int_value();
+        let states = self
+            .map_field_value(map, STATES_FIELD, line)?
+            .into_pointer_value();
+        let keys = self
+            .map_field_value(map, KEYS_FIELD, line)?
+            .into_pointer_value();
+
+        let slot_ptr = self.builder.build_alloca(i64_type, "slot").unwrap();
+        let position_ptr = self.builder.build_alloca(i64_type, "position").unwrap();
+
+        let _ = self.builder.build_store(slot_ptr, i64_type.const_zero());
+        let _ = self
+            .builder
+            .build_store(position_ptr, i64_type.const_zero());
+
+        let cond_basic_block = self.context.append_basic_block(function, "keys_cond");
+        let body_basic_block = self.context.append_basic_block(function, "keys_body");
+        let push_basic_block = self.context.append_basic_block(function, "keys_push");
+        let next_basic_block = self.context.append_basic_block(function, "keys_next");
+        let done_basic_block = self.context.append_basic_block(function, "keys_done");
+
+        let _ = self.builder.build_unconditional_branch(cond_basic_block);
+        self.switch_block(cond_basic_block);
+
+        let slot = self
+            .builder
+            .build_load(i64_type, slot_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let has_slots = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULT, slot, capacity, "")
+            .unwrap();
+
+        let _ =
+            self.builder
+                .build_conditional_branch(has_slots, body_basic_block, done_basic_block);
+        self.switch_block(body_basic_block);
+
+        let state_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(i8_type, states, &[slot], "")
+                .unwrap()
+        };
+        let state = self
+            .builder
+            .build_load(i8_type, state_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let is_used = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                state,
+                i8_type.const_int(USED_SLOT, false),
+                "",
+            )
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_used, push_basic_block, next_basic_block);
+        self.switch_block(push_basic_block);
+
+        let key_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(storage_key_type, keys, &[slot], "")
+                .unwrap()
+        };
+        let key = self
+            .builder
+            .build_load(storage_key_type, key_ptr, "")
+            .unwrap();
+        let key = if string_keys {
+            key
+        } else {
+            self.builder
+                .build_int_cast_sign_flag(
+                    key.into_int_value(),
+                    self.get_basic_type(&key_type, line)?.into_int_type(),
+                    true,
+                    "",
+                )
+                .unwrap()
+                .into()
+        };
+
+        let position = self
+            .builder
+            .build_load(i64_type, position_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let element_ptr = self.list_data_pointer(list, &key_type, position, line)?;
+        let _ = self.builder.build_store(element_ptr, key);
+
+        let next_position = self
+            .builder
+            .build_int_add(position, i64_type.const_int(1, false), "")
+            .unwrap();
+        let _ = self.builder.build_store(position_ptr, next_position);
+        let _ = self.builder.build_unconditional_branch(next_basic_block);
+
+        self.switch_block(next_basic_block);
+
+        let next_slot = self
+            .builder
+            .build_int_add(slot, i64_type.const_int(1, false), "")
+            .unwrap();
+        let _ = self.builder.build_store(slot_ptr, next_slot);
+        let _ = self.builder.build_unconditional_branch(cond_basic_block);
+
+        self.switch_block(done_basic_block);
+        Ok(keys_list)
+    }
+
+    fn build_map_free(&mut self, map: PointerValue<'ctx>, line: usize) -> CompileResult<()> {
+        let free_fn = self.__c_free();
+
+        for field in [STATES_FIELD, KEYS_FIELD, VALUES_FIELD] {
+            let array = self.map_field_value(map, field, line)?;
+            let _ = self.builder.build_call(free_fn, &[array.into()], "");
+        }
+
+        let _ = self.builder.build_call(free_fn, &[map.into()], "");
+        Ok(())
+    }
+
+    fn build_map_call(
+        &mut self,
+        method: String,
+        compiled_map: (String, BasicValueEnum<'ctx>),
+        arguments: Vec<Expressions>,
+        line: usize,
+        function: FunctionValue<'ctx>,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        // arguments go after the map, but it's counted too: `set(m, key, value)`
+        let required_arguments = match method.as_str() {
+            "keys" => 0,
+            "has" | "remove" => 1,
+            "get" | "set" => 2,
+            _ => {
+                return Err(self.error(
+                    format!(
+                        "Function `{}()` is not supported for `{}` type!",
+                        method, compiled_map.0
+                    ),
+                    ErrorType::NotSupported,
+                    line,
+                ));
+            }
+        };
+
+        if arguments.len() != required_arguments {
+            return Err(self.arguments_count_error(
+                &method,
+                required_arguments + 1,
+                arguments.len() + 1,
+                line,
+            ));
+        }
+
+        let (key_type, value_type) = Compiler::__map_types(&compiled_map.0);
+        let string_keys = key_type == "str";
+        let map = compiled_map.1.into_pointer_value();
+
+        let i8_type = self.context.i8_type();
+        let i64_type = self.context.i64_type();
+        let used_state = i8_type.const_int(USED_SLOT, false);
+        let tombstone_state = i8_type.const_int(TOMBSTONE_SLOT, false);
+
+        match method.as_str() {
+            "set" => {
+                let key = self.map_key_argument(arguments[0].clone(), &key_type, line, function)?;
+                let value = self.list_value_argument(
+                    &method,
+                    arguments[1].clone(),
+                    &value_type,
+                    line,
+                    function,
+                )?;
+
+                let reserve_fn = self.__runtime_map_reserve(string_keys);
+                let value_size = self.map_value_size(&value_type, line)?;
+                let _ = self
+                    .builder
+                    .build_call(reserve_fn, &[map.into(), value_size.into()], "");
+
+                let (index, state_ptr, state) = self.map_find(map, key, string_keys, line)?;
+
+                // new key increases length, reused tombstone decreases their count
+                let is_new = self
+                    .builder
+                    .build_int_compare(inkwell::IntPredicate::NE, state, used_state, "")
+                    .unwrap();
+                let is_tombstone = self
+                    .builder
+                    .build_int_compare(inkwell::IntPredicate::EQ, state, tombstone_state, "")
+                    .unwrap();
+
+                for (field, flag, is_increment) in [
+                    (LEN_FIELD, is_new, true),
+                    (TOMBSTONES_FIELD, is_tombstone, false),
+                ] {
+                    let counter = self.map_field_value(map, field, line)?.into_int_value();
+                    let delta = self.builder.build_int_z_extend(flag, i64_type, "").unwrap();
+                    let counter = if is_increment {
+                        self.builder.build_int_add(counter, delta, "").unwrap()
+                    } else {
+                        self.builder.build_int_sub(counter, delta, "").unwrap()
+                    };
+
+                    let field_ptr = self.map_field_pointer(map, field, line)?;
+                    let _ = self.builder.build_store(field_ptr, counter);
+                }
+
+                let keys = self
+                    .map_field_value(map, KEYS_FIELD, line)?
+                    .into_pointer_value();
+                let key_ptr = unsafe {
+                    self.builder
+                        .build_in_bounds_gep(self.__map_key_type(string_keys), keys, &[index], "")
+                        .unwrap()
+                };
+
+                let _ = self.builder.build_store(state_ptr, used_state);
+                let _ = self.builder.build_store(key_ptr, key);
+
+                let value_ptr = self.map_value_pointer(map, &value_type, index, line)?;
+                self.build_value_store(value_ptr, &value, line)?;
+
+                Ok((
+                    String::from("void"),
+                    self.context.bool_type().const_zero().into(),
+                ))
+            }
+            "get" => {
+                let key = self.map_key_argument(arguments[0].clone(), &key_type, line, function)?;
+                let default_value = self.list_value_argument(
+                    &method,
+                    arguments[1].clone(),
+                    &value_type,
+                    line,
+                    function,
+                )?;
+
+                let (index, _, state) = self.map_find(map, key, string_keys, line)?;
+                let is_found = self
+                    .builder
+                    .build_int_compare(inkwell::IntPredicate::EQ, state, used_state, "")
+                    .unwrap();
+
+                // missing key slot has zeroed value, so it's safe to load it
+                let value_ptr = self.map_value_pointer(map, &value_type, index, line)?;
+                let value = self.list_element_value(&value_type, value_ptr, line)?;
+                let value = self
+                    .builder
+                    .build_select(is_found, value, default_value.1, "")
+                    .unwrap();
+
+                Ok((value_type, value))
+            }
+            "has" => {
+                let key = self.map_key_argument(arguments[0].clone(), &key_type, line, function)?;
+                let (_, _, state) = self.map_find(map, key, string_keys, line)?;
+                let is_found = self
+                    .builder
+                    .build_int_compare(inkwell::IntPredicate::EQ, state, used_state, "")
+                    .unwrap();
+
+                Ok((String::from("bool"), is_found.into()))
+            }
+            "remove" => {
+                let key = self.map_key_argument(arguments[0].clone(), &key_type, line, function)?;
+                let (_, state_ptr, state) = self.map_find(map, key, string_keys, line)?;
+                let is_found = self
+                    .builder
+                    .build_int_compare(inkwell::IntPredicate::EQ, state, used_state, "")
+                    .unwrap();
+
+                // removed slot becomes tombstone, so probing goes through it
+                let new_state = self
+                    .builder
+                    .build_select(is_found, tombstone_state, state, "")
+                    .unwrap();
+                let _ = self.builder.build_store(state_ptr, new_state);
+
+                for (field, is_increment) in [(LEN_FIELD, false), (TOMBSTONES_FIELD, true)] {
+                    let counter = self.map_field_value(map, field, line)?.into_int_value();
+                    let delta = self
+                        .builder
+                        .build_int_z_extend(is_found, i64_type, "")
+                        .unwrap();
+                    let counter = if is_increment {
+                        self.builder.build_int_add(counter, delta, "").unwrap()
+                    } else {
+                        self.builder.build_int_sub(counter, delta, "").unwrap()
+                    };
+
+                    let field_ptr = self.map_field_pointer(map, field, line)?;
+                    let _ = self.builder.build_store(field_ptr, counter);
+                }
+
+                Ok((String::from("bool"), is_found.into()))
+            }
+            "keys" => self.build_map_keys(&compiled_map.0, map, line),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,474 @@
+// Toy Programming Language | by mealet
+// https://github.com/mealet/tpl-lang
+// =========================================
+// Project licensed under the BSD-3 LICENSE.
+// Check the `LICENSE` file to more info.
+
+use inkwell::{
+    attributes::{Attribute, AttributeLoc},
+    module::Linkage,
+    types::{BasicMetadataTypeEnum, BasicTypeEnum},
+    values::{BasicMetadataValueEnum, FunctionValue, GlobalValue, IntValue, PointerValue},
+    AddressSpace,
+};
+
+use crate::{error::ErrorType, libc::Libc, strings::Strings, CompileResult, Compiler};
+
+// NOTE: runtime routines are generated right into the module,
+// so compiled programs don't need any extra library to be linked
+
+const BOUNDS_PANIC_NAME: &str = "__tpl_bounds_panic";
+const ALLOC_PANIC_NAME: &str = "__tpl_alloc_panic";
+const PANIC_EXIT_CODE: u64 = 101;
+
+// `main` parameters are kept in globals, so every function can reach them
+const ARGC_NAME: &str = "__tpl_argc";
+const ARGV_NAME: &str = "__tpl_argv";
+const ARGUMENTS_NAME: &str = "__tpl_arguments";
+
+pub trait Runtime<'ctx> {
+    fn __runtime_panic(
+        &mut self,
+        name: &str,
+        message: &str,
+        parameters: &[BasicMetadataTypeEnum<'ctx>],
+    ) -> FunctionValue<'ctx>;
+    fn __runtime_bounds_panic(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_alloc_panic(&mut self) -> FunctionValue<'ctx>;
+    fn build_bounds_check(
+        &mut self,
+        index: IntValue<'ctx>,
+        len: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<()>;
+    fn build_alloc_check(&mut self, buffer: PointerValue<'ctx>, size: IntValue<'ctx>);
+
+    fn __runtime_global(
+        &mut self,
+        name: &str,
+        value_type: BasicTypeEnum<'ctx>,
+    ) -> GlobalValue<'ctx>;
+    fn __runtime_arguments(&mut self) -> FunctionValue<'ctx>;
+    fn build_arguments_store(&mut self);
+    fn build_arguments_load(
+        &mut self,
+        line: usize,
+    ) -> CompileResult<(IntValue<'ctx>, PointerValue<'ctx>)>;
+}
+
+impl<'ctx> Runtime<'ctx> for Compiler<'ctx> {
+    fn __runtime_panic(
+        &mut self,
+        name: &str,
+        message: &str,
+        parameters: &[BasicMetadataTypeEnum<'ctx>],
+    ) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(name) {
+            return *function_value;
+        }
+
+        // void __tpl_*_panic(...)
+        // prints formatted message with routine parameters to stderr and exits
+        let fn_type = self.context.void_type().fn_type(parameters, false);
+        let function = self
+            .module
+            .add_function(name, fn_type, Some(Linkage::Private));
+
+        for attribute in ["noreturn", "cold", "noinline"] {
+            let kind = Attribute::get_named_enum_kind_id(attribute);
+            function.add_attribute(
+                AttributeLoc::Function,
+                self.context.create_enum_attribute(kind, 0),
+            );
+        }
+
+        let dprintf_fn = self.__c_dprintf();
+        let exit_fn = self.__c_exit();
+
+        // routine body is built aside from current position
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, "entry");
+        self.builder.position_at_end(entry);
+
+        let message = self
+            .builder
+            .build_global_string_ptr(message, "panic_fmt")
+            .unwrap()
+            .as_pointer_value();
+
+        let mut arguments: Vec<BasicMetadataValueEnum> = vec![
+            self.context.i32_type().const_int(2, false).into(), // stderr
+            message.into(),
+        ];
+        arguments.extend(function.get_param_iter().map(BasicMetadataValueEnum::from));
+
+        let _ = self.builder.build_call(dprintf_fn, &arguments, "");
+        let _ = self.builder.build_call(
+            exit_fn,
+            &[self
+                .context
+                .i32_type()
+                .const_int(PANIC_EXIT_CODE, false)
+                .into()],
+            "",
+        );
+        let _ = self.builder.build_unreachable();
+
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self.built_functions.insert(name.to_string(), function);
+
+        function
+    }
+
+    fn __runtime_bounds_panic(&mut self) -> FunctionValue<'ctx> {
+        // void __tpl_bounds_panic(str file, int64 line, int64 index, int64 len)
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+
+        self.__runtime_panic(
+            BOUNDS_PANIC_NAME,
+            "panic at %s:%lld: index %lld is out of bounds for length %lld\n",
+            &[
+                ptr_type.into(),
+                i64_type.into(),
+                i64_type.into(),
+                i64_type.into(),
+            ],
+        )
+    }
+
+    fn __runtime_alloc_panic(&mut self) -> FunctionValue<'ctx> {
+        // void __tpl_alloc_panic(int64 size)
+        let i64_type = self.context.i64_type();
+
+        self.__runtime_panic(
+            ALLOC_PANIC_NAME,
+            "panic: unable to allocate %lld bytes\n",
+            &[i64_type.into()],
+        )
+    }
+
+    fn build_bounds_check(
+        &mut self,
+        index: IntValue<'ctx>,
+        len: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<()> {
+        if !self.bounds_checks {
+            return Ok(());
+        }
+
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or_else(|| {
+                self.error(
+                    "Bounds check is built outside of function!",
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?;
+
+        // unsigned comparison also catches negative indexes
+        let in_bounds = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULT, index, len, "in_bounds")
+            .map_err(|_| {
+                self.error("Unable to build bounds check!", ErrorType::BuildError, line)
+            })?;
+
+        let panic_basic_block = self.context.append_basic_block(function, "bounds_panic");
+        let ok_basic_block = self.context.append_basic_block(function, "bounds_ok");
+
+        let _ = self
+            .builder
+            .build_conditional_branch(in_bounds, ok_basic_block, panic_basic_block);
+
+        // calling panic routine with source location
+        self.switch_block(panic_basic_block);
+
+        let panic_fn = self.__runtime_bounds_panic();
+        let file = self
+            .builder
+            .build_global_string_ptr(&self.module_name, "panic_file")
+            .map_err(|_| {
+                self.error(
+                    "Unable to create file name for bounds check!",
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?;
+        let line_value = self.context.i64_type().const_int(line as u64 + 1, false);
+
+        let _ = self.builder.build_call(
+            panic_fn,
+            &[
+                file.as_pointer_value().into(),
+                line_value.into(),
+                index.into(),
+                len.into(),
+            ],
+            "",
+        );
+        let _ = self.builder.build_unreachable();
+
+        self.switch_block(ok_basic_block);
+        Ok(())
+    }
+
+    fn build_alloc_check(&mut self, buffer: PointerValue<'ctx>, size: IntValue<'ctx>) {
+        // used inside of runtime routines, so current block of compiler stays untouched
+        let function = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .unwrap();
+
+        let is_null = self.builder.build_is_null(buffer, "is_null").unwrap();
+
+        let panic_basic_block = self.context.append_basic_block(function, "alloc_panic");
+        let ok_basic_block = self.context.append_basic_block(function, "alloc_ok");
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_null, panic_basic_block, ok_basic_block);
+
+        self.builder.position_at_end(panic_basic_block);
+
+        let panic_fn = self.__runtime_alloc_panic();
+
+        let _ = self.builder.build_call(panic_fn, &[size.into()], "");
+        let _ = self.builder.build_unreachable();
+
+        self.builder.position_at_end(ok_basic_block);
+    }
+
+    fn __runtime_global(
+        &mut self,
+        name: &str,
+        value_type: BasicTypeEnum<'ctx>,
+    ) -> GlobalValue<'ctx> {
+        if let Some(global) = self.module.get_global(name) {
+            return global;
+        }
+
+        let global = self.module.add_global(value_type, None, name);
+        global.set_linkage(Linkage::Private);
+        global.set_initializer(&value_type.const_zero());
+
+        global
+    }
+
+    fn __runtime_arguments(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(ARGUMENTS_NAME) {
+            return *function_value;
+        }
+
+        // str* __tpl_arguments()
+        // `argv` strings are copied into `str` once, on the first call
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i32_type = self.context.i32_type();
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[], false);
+
+        let function = self
+            .module
+            .add_function(ARGUMENTS_NAME, fn_type, Some(Linkage::Private));
+        let malloc_fn = self.__c_malloc();
+        let strlen_fn = self.__c_strlen();
+        let copy_fn = self.__runtime_str_copy();
+
+        let argc_global = self.__runtime_global(ARGC_NAME, i32_type.into());
+        let argv_global = self.__runtime_global(ARGV_NAME, ptr_type.into());
+        let arguments_global = self.__runtime_global(ARGUMENTS_NAME, ptr_type.into());
+
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, "entry");
+        let convert = self.context.append_basic_block(function, "convert");
+        let cond = self.context.append_basic_block(function, "cond");
+        let body = self.context.append_basic_block(function, "body");
+        let done = self.context.append_basic_block(function, "done");
+        let cached = self.context.append_basic_block(function, "cached");
+
+        self.builder.position_at_end(entry);
+
+        let index_ptr = self.builder.build_alloca(i64_type, "index").unwrap();
+        let arguments = self
+            .builder
+            .build_load(ptr_type, arguments_global.as_pointer_value(), "")
+            .unwrap()
+            .into_pointer_value();
+        let is_converted = self.builder.build_is_not_null(arguments, "").unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_converted, cached, convert);
+
+        self.builder.position_at_end(cached);
+        let _ = self.builder.build_return(Some(&arguments));
+
+        // array is terminated by `null` like `argv` itself
+        self.builder.position_at_end(convert);
+
+        let argc = self
+            .builder
+            .build_load(i32_type, argc_global.as_pointer_value(), "")
+            .unwrap()
+            .into_int_value();
+        let argc = self.builder.build_int_s_extend(argc, i64_type, "").unwrap();
+        let argv = self
+            .builder
+            .build_load(ptr_type, argv_global.as_pointer_value(), "")
+            .unwrap()
+            .into_pointer_value();
+
+        let slots = self
+            .builder
+            .build_int_add(argc, i64_type.const_int(1, false), "")
+            .unwrap();
+        let size = self
+            .builder
+            .build_int_mul(slots, ptr_type.size_of(), "")
+            .unwrap();
+        let buffer = self
+            .builder
+            .build_call(malloc_fn, &[size.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        self.build_alloc_check(buffer, size);
+
+        let terminator_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(ptr_type, buffer, &[argc], "")
+                .unwrap()
+        };
+
+        let _ = self
+            .builder
+            .build_store(terminator_ptr, ptr_type.const_null());
+        let _ = self.builder.build_store(index_ptr, i64_type.const_zero());
+        let _ = self.builder.build_unconditional_branch(cond);
+
+        self.builder.position_at_end(cond);
+
+        let index = self
+            .builder
+            .build_load(i64_type, index_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let in_bounds = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::SLT, index, argc, "")
+            .unwrap();
+
+        let _ = self.builder.build_conditional_branch(in_bounds, body, done);
+        self.builder.position_at_end(body);
+
+        let argument_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(ptr_type, argv, &[index], "")
+                .unwrap()
+        };
+        let argument = self
+            .builder
+            .build_load(ptr_type, argument_ptr, "")
+            .unwrap()
+            .into_pointer_value();
+        let argument_len = self
+            .builder
+            .build_call(strlen_fn, &[argument.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+        let string = self
+            .builder
+            .build_call(copy_fn, &[argument.into(), argument_len.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+        let string_ptr = unsafe {
+            self.builder
+                .build_in_bounds_gep(ptr_type, buffer, &[index], "")
+                .unwrap()
+        };
+        let next_index = self
+            .builder
+            .build_int_add(index, i64_type.const_int(1, false), "")
+            .unwrap();
+
+        let _ = self.builder.build_store(string_ptr, string);
+        let _ = self.builder.build_store(index_ptr, next_index);
+        let _ = self.builder.build_unconditional_branch(cond);
+
+        self.builder.position_at_end(done);
+
+        let _ = self
+            .builder
+            .build_store(arguments_global.as_pointer_value(), buffer);
+        let _ = self.builder.build_return(Some(&buffer));
+
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self
+            .built_functions
+            .insert(ARGUMENTS_NAME.to_string(), function);
+
+        function
+    }
+
+    fn build_arguments_store(&mut self) {
+        // int32 main(int32 argc, str* argv)
+        let argc_global = self.__runtime_global(ARGC_NAME, self.context.i32_type().into());
+        let argv_global = self.__runtime_global(
+            ARGV_NAME,
+            self.context.ptr_type(AddressSpace::default()).into(),
+        );
+
+        for (global, parameter) in [argc_global, argv_global]
+            .into_iter()
+            .zip(self.main_function.get_param_iter())
+        {
+            let _ = self
+                .builder
+                .build_store(global.as_pointer_value(), parameter);
+        }
+    }
+
+    fn build_arguments_load(
+        &mut self,
+        line: usize,
+    ) -> CompileResult<(IntValue<'ctx>, PointerValue<'ctx>)> {
+        let i32_type = self.context.i32_type();
+        let argc_global = self.__runtime_global(ARGC_NAME, i32_type.into());
+
+        let argc = self
+            .builder
+            .build_load(i32_type, argc_global.as_pointer_value(), "argc")
+            .map_err(|_| self.error("Unable to load `argc`!", ErrorType::MemoryError, line))?
+            .into_int_value();
+        // raw `argv` has no string headers, so its converted copy is returned
+        let arguments_fn = self.__runtime_arguments();
+        let argv = self
+            .builder
+            .build_call(arguments_fn, &[], "argv")
+            .map_err(|_| self.error("Unable to load `argv`!", ErrorType::MemoryError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        Ok((argc, argv))
+    }
+}
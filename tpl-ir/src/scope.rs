@@ -0,0 +1,77 @@
+// Toy Programming Language | by mealet
+// https://github.com/mealet/tpl-lang
+// =========================================
+// Project licensed under the BSD-3 LICENSE.
+// Check the `LICENSE` file to more info.
+
+use crate::variable::Variable;
+use std::collections::{HashMap, HashSet};
+
+// NOTE: every block (`if`, `while`, `for`, function body) pushes new scope,
+// variables are searched from the innermost scope to the outermost one.
+
+#[derive(Debug, Clone)]
+pub struct ScopeStack<'ctx> {
+    scopes: Vec<HashMap<String, Variable<'ctx>>>,
+    expired: HashSet<String>,
+}
+
+impl<'ctx> ScopeStack<'ctx> {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+            expired: HashSet::new(),
+        }
+    }
+
+    pub fn enter(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn exit(&mut self) {
+        // global scope is never dropped
+        if self.scopes.len() < 2 {
+            return;
+        }
+
+        if let Some(scope) = self.scopes.pop() {
+            for name in scope.into_keys() {
+                if self.get(&name).is_none() {
+                    self.expired.insert(name);
+                }
+            }
+        }
+    }
+
+    pub fn truncate(&mut self, depth: usize) {
+        while self.scopes.len() > depth.max(1) {
+            self.exit();
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Variable<'ctx>> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    pub fn insert(&mut self, name: String, variable: Variable<'ctx>) {
+        self.expired.remove(&name);
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, variable);
+        }
+    }
+
+    pub fn is_expired(&self, name: &str) -> bool {
+        self.expired.contains(name)
+    }
+}
+
+impl Default for ScopeStack<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,2063 @@
+// Toy Programming Language | by mealet
+// https://github.com/mealet/tpl-lang
+// =========================================
+// Project licensed under the BSD-3 LICENSE.
+// Check the `LICENSE` file to more info.
+
+use inkwell::{
+    basic_block::BasicBlock,
+    module::Linkage,
+    values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, IntValue, PointerValue},
+    AddressSpace,
+};
+
+use crate::{error::ErrorType, libc::Libc, list::List, runtime::Runtime, CompileResult, Compiler};
+
+// NOTE: `str` points to null-terminated characters, so it can be passed to libc as it is.
+// Characters are preceded by header `{int64 len, int64 owned}`: literals carry it
+// in their globals and runtime strings in the same heap buffer.
+// Routines take lengths from headers and never write into their arguments.
+// Only owned (heap) strings are released by `free()`, literals are left untouched.
+// Allocation failures are never returned as null strings, runtime panics instead.
+
+const STR_ALLOC_NAME: &str = "__tpl_str_alloc";
+const STR_CONCAT_NAME: &str = "__tpl_str_concat";
+const STR_READ_LINE_NAME: &str = "__tpl_str_read_line";
+const STR_COPY_NAME: &str = "__tpl_str_copy";
+const STR_COUNT_NAME: &str = "__tpl_str_count";
+const STR_SPLIT_NAME: &str = "__tpl_str_split";
+const STR_REPLACE_NAME: &str = "__tpl_str_replace";
+const STR_TRIM_NAME: &str = "__tpl_str_trim";
+const STR_UPPER_NAME: &str = "__tpl_str_upper";
+const STR_LOWER_NAME: &str = "__tpl_str_lower";
+const STR_REPEAT_NAME: &str = "__tpl_str_repeat";
+const STR_JOIN_NAME: &str = "__tpl_str_join";
+const STR_COMPARE_NAME: &str = "__tpl_str_compare";
+const STR_READ_FILE_NAME: &str = "__tpl_str_read_file";
+const STR_FREE_NAME: &str = "__tpl_str_free";
+const STR_EMPTY_NAME: &str = "__tpl_str_empty";
+const STR_HEADER_SIZE: u64 = 16;
+const STR_LEN_OFFSET: i64 = -16;
+const STR_OWNED_OFFSET: i64 = -8;
+const STR_LINE_CAPACITY: u64 = 16;
+const EOF_CHAR: i64 = -1;
+const SEEK_END: u64 = 2;
+
+pub trait Strings<'ctx> {
+    fn __runtime_str_alloc(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_concat(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_read_line(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_copy(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_count(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_split(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_replace(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_trim(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_case(&mut self, upper: bool) -> FunctionValue<'ctx>;
+    fn __runtime_str_repeat(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_join(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_compare(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_read_file(&mut self) -> FunctionValue<'ctx>;
+    fn __runtime_str_free(&mut self) -> FunctionValue<'ctx>;
+
+    fn __runtime_begin(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        name: &str,
+    ) -> Option<BasicBlock<'ctx>>;
+    fn __runtime_end(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        name: &str,
+        old_position: Option<BasicBlock<'ctx>>,
+    );
+    fn __call_value(
+        &self,
+        callee: FunctionValue<'ctx>,
+        arguments: &[BasicMetadataValueEnum<'ctx>],
+    ) -> BasicValueEnum<'ctx>;
+    fn __str_offset(
+        &self,
+        string: PointerValue<'ctx>,
+        offset: IntValue<'ctx>,
+    ) -> PointerValue<'ctx>;
+    fn __str_distance(&self, from: PointerValue<'ctx>, to: PointerValue<'ctx>) -> IntValue<'ctx>;
+    fn __str_init(&self, buffer: PointerValue<'ctx>, len: IntValue<'ctx>) -> PointerValue<'ctx>;
+    fn __str_set_len(&self, string: PointerValue<'ctx>, len: IntValue<'ctx>);
+    fn __str_len(&self, string: PointerValue<'ctx>) -> IntValue<'ctx>;
+    fn __str_empty(&self) -> PointerValue<'ctx>;
+    fn __clamp(
+        &self,
+        value: IntValue<'ctx>,
+        low: IntValue<'ctx>,
+        high: Option<IntValue<'ctx>>,
+    ) -> IntValue<'ctx>;
+
+    fn build_stdin(&mut self, line: usize) -> CompileResult<PointerValue<'ctx>>;
+    fn build_str_literal(&self, value: &str, name: &str) -> PointerValue<'ctx>;
+    fn build_str_free(&mut self, string: PointerValue<'ctx>, line: usize) -> CompileResult<()>;
+    fn build_str_alloc(
+        &mut self,
+        str_len: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn build_str_concat(
+        &mut self,
+        left: BasicValueEnum<'ctx>,
+        right: BasicValueEnum<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn build_str_read_line(
+        &mut self,
+        stream: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn build_str_read_file(
+        &mut self,
+        path: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn build_str_format(
+        &mut self,
+        format: PointerValue<'ctx>,
+        value: BasicValueEnum<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+
+    fn build_str_runtime_call(
+        &mut self,
+        callee: FunctionValue<'ctx>,
+        arguments: &[BasicMetadataValueEnum<'ctx>],
+        line: usize,
+    ) -> CompileResult<BasicValueEnum<'ctx>>;
+    fn build_str_substr(
+        &mut self,
+        string: PointerValue<'ctx>,
+        start: IntValue<'ctx>,
+        substr_len: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn build_str_find(
+        &mut self,
+        string: PointerValue<'ctx>,
+        pattern: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>>;
+    fn build_str_contains(
+        &mut self,
+        string: PointerValue<'ctx>,
+        pattern: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>>;
+    fn build_str_starts_with(
+        &mut self,
+        string: PointerValue<'ctx>,
+        prefix: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>>;
+    fn build_str_ends_with(
+        &mut self,
+        string: PointerValue<'ctx>,
+        suffix: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>>;
+    fn build_str_split(
+        &mut self,
+        string: PointerValue<'ctx>,
+        separator: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)>;
+    fn build_str_join(
+        &mut self,
+        list: PointerValue<'ctx>,
+        separator: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>>;
+    fn build_str_compare(
+        &mut self,
+        left: PointerValue<'ctx>,
+        right: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>>;
+}
+
+impl<'ctx> Strings<'ctx> for Compiler<'ctx> {
+    fn __runtime_str_alloc(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_ALLOC_NAME) {
+            return *function_value;
+        }
+
+        // str __tpl_str_alloc(int64 len)
+        // allocates header, `len` characters and terminator, which is already placed
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[i64_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_ALLOC_NAME, fn_type, Some(Linkage::Private));
+        let malloc_fn = self.__c_malloc();
+
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, "entry");
+        self.builder.position_at_end(entry);
+
+        let str_len = function.get_nth_param(0).unwrap().into_int_value();
+        let size = self
+            .builder
+            .build_int_add(str_len, i64_type.const_int(STR_HEADER_SIZE + 1, false), "")
+            .unwrap();
+        let buffer = self
+            .builder
+            .build_call(malloc_fn, &[size.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        self.build_alloc_check(buffer, size);
+
+        let string = self.__str_init(buffer, str_len);
+        let _ = self.builder.build_return(Some(&string));
+
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self
+            .built_functions
+            .insert(STR_ALLOC_NAME.to_string(), function);
+
+        function
+    }
+
+    fn __runtime_str_concat(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_CONCAT_NAME) {
+            return *function_value;
+        }
+
+        // str __tpl_str_concat(str left, str right)
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i8_type = self.context.i8_type();
+        let fn_type = ptr_type.fn_type(&[ptr_type.into(), ptr_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_CONCAT_NAME, fn_type, Some(Linkage::Private));
+        let alloc_fn = self.__runtime_str_alloc();
+
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, "entry");
+        self.builder.position_at_end(entry);
+
+        let left = function.get_nth_param(0).unwrap().into_pointer_value();
+        let right = function.get_nth_param(1).unwrap().into_pointer_value();
+
+        let lengths = [self.__str_len(left), self.__str_len(right)];
+
+        let total_len = self
+            .builder
+            .build_int_add(lengths[0], lengths[1], "")
+            .unwrap();
+        let buffer = self
+            .builder
+            .build_call(alloc_fn, &[total_len.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+        let tail = unsafe {
+            self.builder
+                .build_in_bounds_gep(i8_type, buffer, &[lengths[0]], "")
+                .unwrap()
+        };
+
+        let _ = self.builder.build_memcpy(buffer, 1, left, 1, lengths[0]);
+        let _ = self.builder.build_memcpy(tail, 1, right, 1, lengths[1]);
+        let _ = self.builder.build_return(Some(&buffer));
+
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self
+            .built_functions
+            .insert(STR_CONCAT_NAME.to_string(), function);
+
+        function
+    }
+
+    fn __runtime_str_read_line(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_READ_LINE_NAME) {
+            return *function_value;
+        }
+
+        // str __tpl_str_read_line(FILE* stream)
+        // reads characters until newline or end of file, buffer grows twice when it's full
+        // `capacity` includes header, which is filled when line is done
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i8_type = self.context.i8_type();
+        let i32_type = self.context.i32_type();
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[ptr_type.into()], false);
+
+        let function =
+            self.module
+                .add_function(STR_READ_LINE_NAME, fn_type, Some(Linkage::Private));
+        let fgetc_fn = self.__c_fgetc();
+        let malloc_fn = self.__c_malloc();
+        let realloc_fn = self.__c_realloc();
+
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, "entry");
+        let read = self.context.append_basic_block(function, "read");
+        let store_char = self.context.append_basic_block(function, "store_char");
+        let grow = self.context.append_basic_block(function, "grow");
+        let append = self.context.append_basic_block(function, "append");
+        let done = self.context.append_basic_block(function, "done");
+
+        self.builder.position_at_end(entry);
+
+        let stream = function.get_nth_param(0).unwrap().into_pointer_value();
+
+        let buffer_ptr = self.builder.build_alloca(ptr_type, "buffer").unwrap();
+        let len_ptr = self.builder.build_alloca(i64_type, "len").unwrap();
+        let capacity_ptr = self.builder.build_alloca(i64_type, "capacity").unwrap();
+
+        let capacity = i64_type.const_int(STR_HEADER_SIZE + STR_LINE_CAPACITY, false);
+        let buffer = self
+            .builder
+            .build_call(malloc_fn, &[capacity.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        self.build_alloc_check(buffer, capacity);
+
+        let _ = self.builder.build_store(buffer_ptr, buffer);
+        let _ = self.builder.build_store(len_ptr, i64_type.const_zero());
+        let _ = self.builder.build_store(capacity_ptr, capacity);
+        let _ = self.builder.build_unconditional_branch(read);
+
+        self.builder.position_at_end(read);
+
+        let character = self
+            .builder
+            .build_call(fgetc_fn, &[stream.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+        let is_eof = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                character,
+                i32_type.const_int(EOF_CHAR as u64, true),
+                "",
+            )
+            .unwrap();
+        let is_newline = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                character,
+                i32_type.const_int('\n' as u64, false),
+                "",
+            )
+            .unwrap();
+        let is_end = self.builder.build_or(is_eof, is_newline, "").unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_end, done, store_char);
+
+        // one byte is always left for terminator
+        self.builder.position_at_end(store_char);
+
+        let len = self
+            .builder
+            .build_load(i64_type, len_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let capacity = self
+            .builder
+            .build_load(i64_type, capacity_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let needed = self
+            .builder
+            .build_int_add(len, i64_type.const_int(1, false), "")
+            .unwrap();
+        let used = self
+            .builder
+            .build_int_add(needed, i64_type.const_int(STR_HEADER_SIZE, false), "")
+            .unwrap();
+        let is_full = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::UGE, used, capacity, "")
+            .unwrap();
+
+        let _ = self.builder.build_conditional_branch(is_full, grow, append);
+        self.builder.position_at_end(grow);
+
+        let new_capacity = self
+            .builder
+            .build_int_mul(capacity, i64_type.const_int(2, false), "")
+            .unwrap();
+        let buffer = self.builder.build_load(ptr_type, buffer_ptr, "").unwrap();
+        let new_buffer = self
+            .builder
+            .build_call(realloc_fn, &[buffer.into(), new_capacity.into()], "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        self.build_alloc_check(new_buffer, new_capacity);
+
+        let _ = self.builder.build_store(buffer_ptr, new_buffer);
+        let _ = self.builder.build_store(capacity_ptr, new_capacity);
+        let _ = self.builder.build_unconditional_branch(append);
+
+        self.builder.position_at_end(append);
+
+        let buffer = self
+            .builder
+            .build_load(ptr_type, buffer_ptr, "")
+            .unwrap()
+            .into_pointer_value();
+        let offset = self
+            .builder
+            .build_int_add(len, i64_type.const_int(STR_HEADER_SIZE, false), "")
+            .unwrap();
+        let char_ptr = self.__str_offset(buffer, offset);
+        let character = self
+            .builder
+            .build_int_truncate(character, i8_type, "")
+            .unwrap();
+
+        let _ = self.builder.build_store(char_ptr, character);
+        let _ = self.builder.build_store(len_ptr, needed);
+        let _ = self.builder.build_unconditional_branch(read);
+
+        self.builder.position_at_end(done);
+
+        let buffer = self
+            .builder
+            .build_load(ptr_type, buffer_ptr, "")
+            .unwrap()
+            .into_pointer_value();
+        let len = self
+            .builder
+            .build_load(i64_type, len_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let string = self.__str_init(buffer, len);
+
+        let _ = self.builder.build_return(Some(&string));
+
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self
+            .built_functions
+            .insert(STR_READ_LINE_NAME.to_string(), function);
+
+        function
+    }
+
+    fn __runtime_str_copy(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_COPY_NAME) {
+            return *function_value;
+        }
+
+        // str __tpl_str_copy(str source, int64 len)
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[ptr_type.into(), i64_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_COPY_NAME, fn_type, Some(Linkage::Private));
+        let alloc_fn = self.__runtime_str_alloc();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let source = function.get_nth_param(0).unwrap().into_pointer_value();
+        let str_len = function.get_nth_param(1).unwrap().into_int_value();
+        let buffer = self
+            .__call_value(alloc_fn, &[str_len.into()])
+            .into_pointer_value();
+
+        let _ = self.builder.build_memcpy(buffer, 1, source, 1, str_len);
+        let _ = self.builder.build_return(Some(&buffer));
+
+        self.__runtime_end(function, STR_COPY_NAME, old_position);
+        function
+    }
+
+    fn __runtime_str_count(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_COUNT_NAME) {
+            return *function_value;
+        }
+
+        // int64 __tpl_str_count(str string, str pattern)
+        // counts non-overlapping occurrences, empty pattern never matches
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let fn_type = i64_type.fn_type(&[ptr_type.into(), ptr_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_COUNT_NAME, fn_type, Some(Linkage::Private));
+        let strstr_fn = self.__c_strstr();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let search = self.context.append_basic_block(function, "search");
+        let found = self.context.append_basic_block(function, "found");
+        let done = self.context.append_basic_block(function, "done");
+
+        let string = function.get_nth_param(0).unwrap().into_pointer_value();
+        let pattern = function.get_nth_param(1).unwrap().into_pointer_value();
+
+        let cursor_ptr = self.builder.build_alloca(ptr_type, "cursor").unwrap();
+        let count_ptr = self.builder.build_alloca(i64_type, "count").unwrap();
+
+        let _ = self.builder.build_store(cursor_ptr, string);
+        let _ = self.builder.build_store(count_ptr, i64_type.const_zero());
+
+        let pattern_len = self.__str_len(pattern);
+        let is_empty = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                pattern_len,
+                i64_type.const_zero(),
+                "",
+            )
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_empty, done, search);
+        self.builder.position_at_end(search);
+
+        let cursor = self.builder.build_load(ptr_type, cursor_ptr, "").unwrap();
+        let occurrence = self
+            .__call_value(strstr_fn, &[cursor.into(), pattern.into()])
+            .into_pointer_value();
+        let is_missing = self.builder.build_is_null(occurrence, "").unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_missing, done, found);
+        self.builder.position_at_end(found);
+
+        let count = self
+            .builder
+            .build_load(i64_type, count_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let count = self
+            .builder
+            .build_int_add(count, i64_type.const_int(1, false), "")
+            .unwrap();
+
+        let _ = self.builder.build_store(count_ptr, count);
+        let _ = self
+            .builder
+            .build_store(cursor_ptr, self.__str_offset(occurrence, pattern_len));
+        let _ = self.builder.build_unconditional_branch(search);
+
+        self.builder.position_at_end(done);
+
+        let count = self.builder.build_load(i64_type, count_ptr, "").unwrap();
+        let _ = self.builder.build_return(Some(&count));
+
+        self.__runtime_end(function, STR_COUNT_NAME, old_position);
+        function
+    }
+
+    fn __runtime_str_split(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_SPLIT_NAME) {
+            return *function_value;
+        }
+
+        // void __tpl_str_split(str string, str separator, str* pieces)
+        // `pieces` must have place for every occurrence of separator and one more
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let fn_type = self
+            .context
+            .void_type()
+            .fn_type(&[ptr_type.into(), ptr_type.into(), ptr_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_SPLIT_NAME, fn_type, Some(Linkage::Private));
+        let strstr_fn = self.__c_strstr();
+        let copy_fn = self.__runtime_str_copy();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let search = self.context.append_basic_block(function, "search");
+        let found = self.context.append_basic_block(function, "found");
+        let last = self.context.append_basic_block(function, "last");
+
+        let string = function.get_nth_param(0).unwrap().into_pointer_value();
+        let separator = function.get_nth_param(1).unwrap().into_pointer_value();
+        let pieces = function.get_nth_param(2).unwrap().into_pointer_value();
+
+        let cursor_ptr = self.builder.build_alloca(ptr_type, "cursor").unwrap();
+        let index_ptr = self.builder.build_alloca(i64_type, "index").unwrap();
+
+        let _ = self.builder.build_store(cursor_ptr, string);
+        let _ = self.builder.build_store(index_ptr, i64_type.const_zero());
+
+        let string_len = self.__str_len(string);
+        let separator_len = self.__str_len(separator);
+        let is_empty = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                separator_len,
+                i64_type.const_zero(),
+                "",
+            )
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_empty, last, search);
+        self.builder.position_at_end(search);
+
+        let cursor = self
+            .builder
+            .build_load(ptr_type, cursor_ptr, "")
+            .unwrap()
+            .into_pointer_value();
+        let occurrence = self
+            .__call_value(strstr_fn, &[cursor.into(), separator.into()])
+            .into_pointer_value();
+        let is_missing = self.builder.build_is_null(occurrence, "").unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_missing, last, found);
+
+        // every piece is copied, so pieces don't depend on the source string
+        for block in [found, last] {
+            self.builder.position_at_end(block);
+
+            let cursor = self
+                .builder
+                .build_load(ptr_type, cursor_ptr, "")
+                .unwrap()
+                .into_pointer_value();
+            let piece_len = if block == last {
+                let consumed = self.__str_distance(string, cursor);
+                self.builder
+                    .build_int_sub(string_len, consumed, "")
+                    .unwrap()
+            } else {
+                self.__str_distance(cursor, occurrence)
+            };
+            let piece = self.__call_value(copy_fn, &[cursor.into(), piece_len.into()]);
+
+            let index = self
+                .builder
+                .build_load(i64_type, index_ptr, "")
+                .unwrap()
+                .into_int_value();
+            let piece_ptr = unsafe {
+                self.builder
+                    .build_in_bounds_gep(ptr_type, pieces, &[index], "")
+                    .unwrap()
+            };
+
+            let _ = self.builder.build_store(piece_ptr, piece);
+
+            if block == last {
+                let _ = self.builder.build_return(None);
+                break;
+            }
+
+            let index = self
+                .builder
+                .build_int_add(index, i64_type.const_int(1, false), "")
+                .unwrap();
+
+            let _ = self.builder.build_store(index_ptr, index);
+            let _ = self
+                .builder
+                .build_store(cursor_ptr, self.__str_offset(occurrence, separator_len));
+            let _ = self.builder.build_unconditional_branch(search);
+        }
+
+        self.__runtime_end(function, STR_SPLIT_NAME, old_position);
+        function
+    }
+
+    fn __runtime_str_replace(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_REPLACE_NAME) {
+            return *function_value;
+        }
+
+        // str __tpl_str_replace(str string, str pattern, str replacement)
+        // result length is known before copying: len + count * (replacement - pattern)
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[ptr_type.into(), ptr_type.into(), ptr_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_REPLACE_NAME, fn_type, Some(Linkage::Private));
+        let strstr_fn = self.__c_strstr();
+        let count_fn = self.__runtime_str_count();
+        let alloc_fn = self.__runtime_str_alloc();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let search = self.context.append_basic_block(function, "search");
+        let found = self.context.append_basic_block(function, "found");
+        let tail = self.context.append_basic_block(function, "tail");
+
+        let string = function.get_nth_param(0).unwrap().into_pointer_value();
+        let pattern = function.get_nth_param(1).unwrap().into_pointer_value();
+        let replacement = function.get_nth_param(2).unwrap().into_pointer_value();
+
+        let string_len = self.__str_len(string);
+        let pattern_len = self.__str_len(pattern);
+        let replacement_len = self.__str_len(replacement);
+        let count = self
+            .__call_value(count_fn, &[string.into(), pattern.into()])
+            .into_int_value();
+
+        let added_len = self
+            .builder
+            .build_int_mul(count, replacement_len, "")
+            .unwrap();
+        let removed_len = self.builder.build_int_mul(count, pattern_len, "").unwrap();
+        let result_len = self
+            .builder
+            .build_int_add(string_len, added_len, "")
+            .unwrap();
+        let result_len = self
+            .builder
+            .build_int_sub(result_len, removed_len, "")
+            .unwrap();
+        let buffer = self
+            .__call_value(alloc_fn, &[result_len.into()])
+            .into_pointer_value();
+
+        let cursor_ptr = self.builder.build_alloca(ptr_type, "cursor").unwrap();
+        let output_ptr = self.builder.build_alloca(ptr_type, "output").unwrap();
+
+        let _ = self.builder.build_store(cursor_ptr, string);
+        let _ = self.builder.build_store(output_ptr, buffer);
+
+        let is_empty = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                pattern_len,
+                i64_type.const_zero(),
+                "",
+            )
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_empty, tail, search);
+        self.builder.position_at_end(search);
+
+        let cursor = self
+            .builder
+            .build_load(ptr_type, cursor_ptr, "")
+            .unwrap()
+            .into_pointer_value();
+        let occurrence = self
+            .__call_value(strstr_fn, &[cursor.into(), pattern.into()])
+            .into_pointer_value();
+        let is_missing = self.builder.build_is_null(occurrence, "").unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_missing, tail, found);
+        self.builder.position_at_end(found);
+
+        let piece_len = self.__str_distance(cursor, occurrence);
+        let output = self
+            .builder
+            .build_load(ptr_type, output_ptr, "")
+            .unwrap()
+            .into_pointer_value();
+
+        let _ = self.builder.build_memcpy(output, 1, cursor, 1, piece_len);
+        let output = self.__str_offset(output, piece_len);
+
+        let _ = self
+            .builder
+            .build_memcpy(output, 1, replacement, 1, replacement_len);
+        let output = self.__str_offset(output, replacement_len);
+
+        let _ = self.builder.build_store(output_ptr, output);
+        let _ = self
+            .builder
+            .build_store(cursor_ptr, self.__str_offset(occurrence, pattern_len));
+        let _ = self.builder.build_unconditional_branch(search);
+
+        self.builder.position_at_end(tail);
+
+        let cursor = self
+            .builder
+            .build_load(ptr_type, cursor_ptr, "")
+            .unwrap()
+            .into_pointer_value();
+        let output = self
+            .builder
+            .build_load(ptr_type, output_ptr, "")
+            .unwrap()
+            .into_pointer_value();
+        let consumed = self.__str_distance(string, cursor);
+        let rest_len = self
+            .builder
+            .build_int_sub(string_len, consumed, "")
+            .unwrap();
+
+        let _ = self.builder.build_memcpy(output, 1, cursor, 1, rest_len);
+        let _ = self.builder.build_return(Some(&buffer));
+
+        self.__runtime_end(function, STR_REPLACE_NAME, old_position);
+        function
+    }
+
+    fn __runtime_str_trim(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_TRIM_NAME) {
+            return *function_value;
+        }
+
+        // str __tpl_str_trim(str string)
+        // moves `start` forward and `end` backward while they point to whitespaces
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i8_type = self.context.i8_type();
+        let i32_type = self.context.i32_type();
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[ptr_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_TRIM_NAME, fn_type, Some(Linkage::Private));
+        let isspace_fn = self.__c_isspace();
+        let copy_fn = self.__runtime_str_copy();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let front = self.context.append_basic_block(function, "front");
+        let front_check = self.context.append_basic_block(function, "front_check");
+        let front_next = self.context.append_basic_block(function, "front_next");
+        let back = self.context.append_basic_block(function, "back");
+        let back_check = self.context.append_basic_block(function, "back_check");
+        let back_next = self.context.append_basic_block(function, "back_next");
+        let done = self.context.append_basic_block(function, "done");
+
+        let string = function.get_nth_param(0).unwrap().into_pointer_value();
+        let string_len = self.__str_len(string);
+
+        let start_ptr = self.builder.build_alloca(i64_type, "start").unwrap();
+        let end_ptr = self.builder.build_alloca(i64_type, "end").unwrap();
+
+        let _ = self.builder.build_store(start_ptr, i64_type.const_zero());
+        let _ = self.builder.build_store(end_ptr, string_len);
+        let _ = self.builder.build_unconditional_branch(front);
+
+        // (loop, check, skip whitespace, leave loop)
+        for (loop_block, check_block, next_block, exit_block) in [
+            (front, front_check, front_next, back),
+            (back, back_check, back_next, done),
+        ] {
+            self.builder.position_at_end(loop_block);
+
+            let start = self
+                .builder
+                .build_load(i64_type, start_ptr, "")
+                .unwrap()
+                .into_int_value();
+            let end = self
+                .builder
+                .build_load(i64_type, end_ptr, "")
+                .unwrap()
+                .into_int_value();
+            let is_not_empty = self
+                .builder
+                .build_int_compare(inkwell::IntPredicate::ULT, start, end, "")
+                .unwrap();
+
+            let _ = self
+                .builder
+                .build_conditional_branch(is_not_empty, check_block, done);
+            self.builder.position_at_end(check_block);
+
+            let index = if loop_block == front {
+                start
+            } else {
+                self.builder
+                    .build_int_sub(end, i64_type.const_int(1, false), "")
+                    .unwrap()
+            };
+            let character = self
+                .builder
+                .build_load(i8_type, self.__str_offset(string, index), "")
+                .unwrap()
+                .into_int_value();
+            let character = self
+                .builder
+                .build_int_z_extend(character, i32_type, "")
+                .unwrap();
+            let is_space = self
+                .__call_value(isspace_fn, &[character.into()])
+                .into_int_value();
+            let is_space = self
+                .builder
+                .build_int_compare(
+                    inkwell::IntPredicate::NE,
+                    is_space,
+                    i32_type.const_zero(),
+                    "",
+                )
+                .unwrap();
+
+            let _ = self
+                .builder
+                .build_conditional_branch(is_space, next_block, exit_block);
+            self.builder.position_at_end(next_block);
+
+            if loop_block == front {
+                let start = self
+                    .builder
+                    .build_int_add(start, i64_type.const_int(1, false), "")
+                    .unwrap();
+                let _ = self.builder.build_store(start_ptr, start);
+            } else {
+                let _ = self.builder.build_store(end_ptr, index);
+            }
+
+            let _ = self.builder.build_unconditional_branch(loop_block);
+        }
+
+        self.builder.position_at_end(done);
+
+        let start = self
+            .builder
+            .build_load(i64_type, start_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let end = self
+            .builder
+            .build_load(i64_type, end_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let trimmed_len = self.builder.build_int_sub(end, start, "").unwrap();
+        let trimmed = self.__call_value(
+            copy_fn,
+            &[self.__str_offset(string, start).into(), trimmed_len.into()],
+        );
+
+        let _ = self.builder.build_return(Some(&trimmed));
+
+        self.__runtime_end(function, STR_TRIM_NAME, old_position);
+        function
+    }
+
+    fn __runtime_str_case(&mut self, upper: bool) -> FunctionValue<'ctx> {
+        let name = if upper {
+            STR_UPPER_NAME
+        } else {
+            STR_LOWER_NAME
+        };
+
+        if let Some(function_value) = self.built_functions.get(name) {
+            return *function_value;
+        }
+
+        // str __tpl_str_upper(str string)
+        // str __tpl_str_lower(str string)
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i8_type = self.context.i8_type();
+        let i32_type = self.context.i32_type();
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[ptr_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(name, fn_type, Some(Linkage::Private));
+        let convert_fn = if upper {
+            self.__c_toupper()
+        } else {
+            self.__c_tolower()
+        };
+        let alloc_fn = self.__runtime_str_alloc();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let cond = self.context.append_basic_block(function, "cond");
+        let body = self.context.append_basic_block(function, "body");
+        let done = self.context.append_basic_block(function, "done");
+
+        let string = function.get_nth_param(0).unwrap().into_pointer_value();
+        let string_len = self.__str_len(string);
+        let buffer = self
+            .__call_value(alloc_fn, &[string_len.into()])
+            .into_pointer_value();
+
+        let index_ptr = self.builder.build_alloca(i64_type, "index").unwrap();
+
+        let _ = self.builder.build_store(index_ptr, i64_type.const_zero());
+        let _ = self.builder.build_unconditional_branch(cond);
+
+        self.builder.position_at_end(cond);
+
+        let index = self
+            .builder
+            .build_load(i64_type, index_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let in_bounds = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULT, index, string_len, "")
+            .unwrap();
+
+        let _ = self.builder.build_conditional_branch(in_bounds, body, done);
+        self.builder.position_at_end(body);
+
+        let character = self
+            .builder
+            .build_load(i8_type, self.__str_offset(string, index), "")
+            .unwrap()
+            .into_int_value();
+        let character = self
+            .builder
+            .build_int_z_extend(character, i32_type, "")
+            .unwrap();
+        let converted = self
+            .__call_value(convert_fn, &[character.into()])
+            .into_int_value();
+        let converted = self
+            .builder
+            .build_int_truncate(converted, i8_type, "")
+            .unwrap();
+        let next_index = self
+            .builder
+            .build_int_add(index, i64_type.const_int(1, false), "")
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_store(self.__str_offset(buffer, index), converted);
+        let _ = self.builder.build_store(index_ptr, next_index);
+        let _ = self.builder.build_unconditional_branch(cond);
+
+        self.builder.position_at_end(done);
+        let _ = self.builder.build_return(Some(&buffer));
+
+        self.__runtime_end(function, name, old_position);
+        function
+    }
+
+    fn __runtime_str_repeat(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_REPEAT_NAME) {
+            return *function_value;
+        }
+
+        // str __tpl_str_repeat(str string, int64 times)
+        // negative `times` works like zero
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[ptr_type.into(), i64_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_REPEAT_NAME, fn_type, Some(Linkage::Private));
+        let alloc_fn = self.__runtime_str_alloc();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let cond = self.context.append_basic_block(function, "cond");
+        let body = self.context.append_basic_block(function, "body");
+        let done = self.context.append_basic_block(function, "done");
+
+        let string = function.get_nth_param(0).unwrap().into_pointer_value();
+        let times = function.get_nth_param(1).unwrap().into_int_value();
+        let times = self.__clamp(times, i64_type.const_zero(), None);
+
+        let string_len = self.__str_len(string);
+        let result_len = self.builder.build_int_mul(string_len, times, "").unwrap();
+        let buffer = self
+            .__call_value(alloc_fn, &[result_len.into()])
+            .into_pointer_value();
+
+        let index_ptr = self.builder.build_alloca(i64_type, "index").unwrap();
+
+        let _ = self.builder.build_store(index_ptr, i64_type.const_zero());
+        let _ = self.builder.build_unconditional_branch(cond);
+
+        self.builder.position_at_end(cond);
+
+        let index = self
+            .builder
+            .build_load(i64_type, index_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let in_bounds = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::SLT, index, times, "")
+            .unwrap();
+
+        let _ = self.builder.build_conditional_branch(in_bounds, body, done);
+        self.builder.position_at_end(body);
+
+        let offset = self.builder.build_int_mul(index, string_len, "").unwrap();
+        let next_index = self
+            .builder
+            .build_int_add(index, i64_type.const_int(1, false), "")
+            .unwrap();
+
+        let _ =
+            self.builder
+                .build_memcpy(self.__str_offset(buffer, offset), 1, string, 1, string_len);
+        let _ = self.builder.build_store(index_ptr, next_index);
+        let _ = self.builder.build_unconditional_branch(cond);
+
+        self.builder.position_at_end(done);
+        let _ = self.builder.build_return(Some(&buffer));
+
+        self.__runtime_end(function, STR_REPEAT_NAME, old_position);
+        function
+    }
+
+    fn __runtime_str_join(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_JOIN_NAME) {
+            return *function_value;
+        }
+
+        // str __tpl_str_join(str* items, int64 count, str separator)
+        // the first pass measures result, the second one copies items
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[ptr_type.into(), i64_type.into(), ptr_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_JOIN_NAME, fn_type, Some(Linkage::Private));
+        let alloc_fn = self.__runtime_str_alloc();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let measure_cond = self.context.append_basic_block(function, "measure_cond");
+        let measure_body = self.context.append_basic_block(function, "measure_body");
+        let allocate = self.context.append_basic_block(function, "allocate");
+        let copy_cond = self.context.append_basic_block(function, "copy_cond");
+        let copy_body = self.context.append_basic_block(function, "copy_body");
+        let done = self.context.append_basic_block(function, "done");
+
+        let items = function.get_nth_param(0).unwrap().into_pointer_value();
+        let count = function.get_nth_param(1).unwrap().into_int_value();
+        let separator = function.get_nth_param(2).unwrap().into_pointer_value();
+
+        let separator_len = self.__str_len(separator);
+
+        let index_ptr = self.builder.build_alloca(i64_type, "index").unwrap();
+        let total_ptr = self.builder.build_alloca(i64_type, "total").unwrap();
+        let output_ptr = self.builder.build_alloca(ptr_type, "output").unwrap();
+
+        let _ = self.builder.build_store(index_ptr, i64_type.const_zero());
+        let _ = self.builder.build_store(total_ptr, i64_type.const_zero());
+        let _ = self.builder.build_unconditional_branch(measure_cond);
+
+        // both passes iterate over items in the same way
+        let mut items_len = Vec::new();
+        for (cond, body, exit) in [
+            (measure_cond, measure_body, allocate),
+            (copy_cond, copy_body, done),
+        ] {
+            self.builder.position_at_end(cond);
+
+            let index = self
+                .builder
+                .build_load(i64_type, index_ptr, "")
+                .unwrap()
+                .into_int_value();
+            let in_bounds = self
+                .builder
+                .build_int_compare(inkwell::IntPredicate::SLT, index, count, "")
+                .unwrap();
+
+            let _ = self.builder.build_conditional_branch(in_bounds, body, exit);
+            self.builder.position_at_end(body);
+
+            let item_ptr = unsafe {
+                self.builder
+                    .build_in_bounds_gep(ptr_type, items, &[index], "")
+                    .unwrap()
+            };
+            let item = self
+                .builder
+                .build_load(ptr_type, item_ptr, "")
+                .unwrap()
+                .into_pointer_value();
+            let item_len = self.__str_len(item);
+            let next_index = self
+                .builder
+                .build_int_add(index, i64_type.const_int(1, false), "")
+                .unwrap();
+
+            let _ = self.builder.build_store(index_ptr, next_index);
+            items_len.push((index, item, item_len));
+        }
+
+        // measuring
+        let (_, _, item_len) = items_len[0];
+        self.builder.position_at_end(measure_body);
+
+        let total = self
+            .builder
+            .build_load(i64_type, total_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let total = self.builder.build_int_add(total, item_len, "").unwrap();
+
+        let _ = self.builder.build_store(total_ptr, total);
+        let _ = self.builder.build_unconditional_branch(measure_cond);
+
+        self.builder.position_at_end(allocate);
+
+        let separators = self
+            .builder
+            .build_int_sub(count, i64_type.const_int(1, false), "")
+            .unwrap();
+        let separators = self.__clamp(separators, i64_type.const_zero(), None);
+        let separators_len = self
+            .builder
+            .build_int_mul(separators, separator_len, "")
+            .unwrap();
+        let total = self
+            .builder
+            .build_load(i64_type, total_ptr, "")
+            .unwrap()
+            .into_int_value();
+        let total = self
+            .builder
+            .build_int_add(total, separators_len, "")
+            .unwrap();
+        let buffer = self.__call_value(alloc_fn, &[total.into()]);
+
+        let _ = self.builder.build_store(output_ptr, buffer);
+        let _ = self.builder.build_store(index_ptr, i64_type.const_zero());
+        let _ = self.builder.build_unconditional_branch(copy_cond);
+
+        // copying, separator goes before every item except the first one
+        let (index, item, item_len) = items_len[1];
+        self.builder.position_at_end(copy_body);
+
+        let is_first = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::EQ, index, i64_type.const_zero(), "")
+            .unwrap();
+        let prefix_len = self
+            .builder
+            .build_select(is_first, i64_type.const_zero(), separator_len, "")
+            .unwrap()
+            .into_int_value();
+        let output = self
+            .builder
+            .build_load(ptr_type, output_ptr, "")
+            .unwrap()
+            .into_pointer_value();
+
+        let _ = self
+            .builder
+            .build_memcpy(output, 1, separator, 1, prefix_len);
+        let output = self.__str_offset(output, prefix_len);
+
+        let _ = self.builder.build_memcpy(output, 1, item, 1, item_len);
+        let output = self.__str_offset(output, item_len);
+
+        let _ = self.builder.build_store(output_ptr, output);
+        let _ = self.builder.build_unconditional_branch(copy_cond);
+
+        self.builder.position_at_end(done);
+        let _ = self.builder.build_return(Some(&buffer));
+
+        self.__runtime_end(function, STR_JOIN_NAME, old_position);
+        function
+    }
+
+    fn __runtime_str_compare(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_COMPARE_NAME) {
+            return *function_value;
+        }
+
+        // int32 __tpl_str_compare(str left, str right)
+        // `null` goes before any string, so it's never passed to `strcmp`
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i32_type = self.context.i32_type();
+        let fn_type = i32_type.fn_type(&[ptr_type.into(), ptr_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_COMPARE_NAME, fn_type, Some(Linkage::Private));
+        let strcmp_fn = self.__c_strcmp();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let nulls = self.context.append_basic_block(function, "nulls");
+        let compare = self.context.append_basic_block(function, "compare");
+
+        let left = function.get_nth_param(0).unwrap().into_pointer_value();
+        let right = function.get_nth_param(1).unwrap().into_pointer_value();
+
+        let is_left_null = self.builder.build_is_null(left, "").unwrap();
+        let is_right_null = self.builder.build_is_null(right, "").unwrap();
+        let has_null = self
+            .builder
+            .build_or(is_left_null, is_right_null, "")
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(has_null, nulls, compare);
+        self.builder.position_at_end(nulls);
+
+        // (right is null) - (left is null): both nulls are equal
+        let right_order = self
+            .builder
+            .build_int_z_extend(is_right_null, i32_type, "")
+            .unwrap();
+        let left_order = self
+            .builder
+            .build_int_z_extend(is_left_null, i32_type, "")
+            .unwrap();
+        let order = self
+            .builder
+            .build_int_sub(right_order, left_order, "")
+            .unwrap();
+
+        let _ = self.builder.build_return(Some(&order));
+        self.builder.position_at_end(compare);
+
+        let order = self.__call_value(strcmp_fn, &[left.into(), right.into()]);
+        let _ = self.builder.build_return(Some(&order));
+
+        self.__runtime_end(function, STR_COMPARE_NAME, old_position);
+        function
+    }
+
+    fn __runtime_str_read_file(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_READ_FILE_NAME) {
+            return *function_value;
+        }
+
+        // str __tpl_str_read_file(str path)
+        // returns `null` when file cannot be opened
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i32_type = self.context.i32_type();
+        let i64_type = self.context.i64_type();
+        let fn_type = ptr_type.fn_type(&[ptr_type.into()], false);
+
+        let function =
+            self.module
+                .add_function(STR_READ_FILE_NAME, fn_type, Some(Linkage::Private));
+        let fopen_fn = self.__c_fopen();
+        let fseek_fn = self.__c_fseek();
+        let ftell_fn = self.__c_ftell();
+        let rewind_fn = self.__c_rewind();
+        let fread_fn = self.__c_fread();
+        let fclose_fn = self.__c_fclose();
+        let alloc_fn = self.__runtime_str_alloc();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let missing = self.context.append_basic_block(function, "missing");
+        let read = self.context.append_basic_block(function, "read");
+
+        let path = function.get_nth_param(0).unwrap().into_pointer_value();
+        let mode = self
+            .builder
+            .build_global_string_ptr("rb", "read_mode")
+            .unwrap()
+            .as_pointer_value();
+        let stream = self
+            .__call_value(fopen_fn, &[path.into(), mode.into()])
+            .into_pointer_value();
+        let is_missing = self.builder.build_is_null(stream, "").unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_missing, missing, read);
+
+        self.builder.position_at_end(missing);
+        let _ = self.builder.build_return(Some(&ptr_type.const_null()));
+
+        // file size is taken from the end position, unknown size (-1) is read as empty
+        self.builder.position_at_end(read);
+
+        let _ = self.builder.build_call(
+            fseek_fn,
+            &[
+                stream.into(),
+                i64_type.const_zero().into(),
+                i32_type.const_int(SEEK_END, false).into(),
+            ],
+            "",
+        );
+        let file_size = self
+            .__call_value(ftell_fn, &[stream.into()])
+            .into_int_value();
+        let file_size = self.__clamp(file_size, i64_type.const_zero(), None);
+
+        let _ = self.builder.build_call(rewind_fn, &[stream.into()], "");
+
+        let buffer = self
+            .__call_value(alloc_fn, &[file_size.into()])
+            .into_pointer_value();
+        let read_len = self
+            .__call_value(
+                fread_fn,
+                &[
+                    buffer.into(),
+                    i64_type.const_int(1, false).into(),
+                    file_size.into(),
+                    stream.into(),
+                ],
+            )
+            .into_int_value();
+
+        // fewer bytes can be read than file size reported
+        self.__str_set_len(buffer, read_len);
+
+        let _ = self.builder.build_call(fclose_fn, &[stream.into()], "");
+        let _ = self.builder.build_return(Some(&buffer));
+
+        self.__runtime_end(function, STR_READ_FILE_NAME, old_position);
+        function
+    }
+
+    fn __runtime_str_free(&mut self) -> FunctionValue<'ctx> {
+        if let Some(function_value) = self.built_functions.get(STR_FREE_NAME) {
+            return *function_value;
+        }
+
+        // void __tpl_str_free(str string)
+        // `null` and literals are skipped, heap buffer starts with header
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+        let fn_type = self.context.void_type().fn_type(&[ptr_type.into()], false);
+
+        let function = self
+            .module
+            .add_function(STR_FREE_NAME, fn_type, Some(Linkage::Private));
+        let free_fn = self.__c_free();
+        let old_position = self.__runtime_begin(function, "entry");
+
+        let check_owned = self.context.append_basic_block(function, "check_owned");
+        let release = self.context.append_basic_block(function, "release");
+        let done = self.context.append_basic_block(function, "done");
+
+        let string = function.get_nth_param(0).unwrap().into_pointer_value();
+        let is_null = self.builder.build_is_null(string, "").unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_null, done, check_owned);
+        self.builder.position_at_end(check_owned);
+
+        let owned = self
+            .builder
+            .build_load(
+                i64_type,
+                self.__str_offset(string, i64_type.const_int(STR_OWNED_OFFSET as u64, true)),
+                "",
+            )
+            .unwrap()
+            .into_int_value();
+        let is_owned = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::NE, owned, i64_type.const_zero(), "")
+            .unwrap();
+
+        let _ = self
+            .builder
+            .build_conditional_branch(is_owned, release, done);
+        self.builder.position_at_end(release);
+
+        let buffer = self.__str_offset(string, i64_type.const_int(STR_LEN_OFFSET as u64, true));
+
+        let _ = self.builder.build_call(free_fn, &[buffer.into()], "");
+        let _ = self.builder.build_unconditional_branch(done);
+
+        self.builder.position_at_end(done);
+        let _ = self.builder.build_return(None);
+
+        self.__runtime_end(function, STR_FREE_NAME, old_position);
+        function
+    }
+
+    fn __runtime_begin(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        name: &str,
+    ) -> Option<BasicBlock<'ctx>> {
+        let old_position = self.builder.get_insert_block();
+        let entry = self.context.append_basic_block(function, name);
+
+        self.builder.position_at_end(entry);
+        old_position
+    }
+
+    fn __runtime_end(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        name: &str,
+        old_position: Option<BasicBlock<'ctx>>,
+    ) {
+        if let Some(block) = old_position {
+            self.builder.position_at_end(block);
+        }
+
+        let _ = self.built_functions.insert(name.to_string(), function);
+    }
+
+    fn __call_value(
+        &self,
+        callee: FunctionValue<'ctx>,
+        arguments: &[BasicMetadataValueEnum<'ctx>],
+    ) -> BasicValueEnum<'ctx> {
+        self.builder
+            .build_call(callee, arguments, "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+    }
+
+    fn __str_offset(
+        &self,
+        string: PointerValue<'ctx>,
+        offset: IntValue<'ctx>,
+    ) -> PointerValue<'ctx> {
+        unsafe {
+            self.builder
+                .build_in_bounds_gep(self.context.i8_type(), string, &[offset], "")
+                .unwrap()
+        }
+    }
+
+    fn __str_distance(&self, from: PointerValue<'ctx>, to: PointerValue<'ctx>) -> IntValue<'ctx> {
+        let i64_type = self.context.i64_type();
+
+        let from = self.builder.build_ptr_to_int(from, i64_type, "").unwrap();
+        let to = self.builder.build_ptr_to_int(to, i64_type, "").unwrap();
+
+        self.builder.build_int_sub(to, from, "").unwrap()
+    }
+
+    fn __str_init(&self, buffer: PointerValue<'ctx>, len: IntValue<'ctx>) -> PointerValue<'ctx> {
+        // fills header of fresh heap buffer and returns pointer to its characters
+        let i64_type = self.context.i64_type();
+        let string = self.__str_offset(buffer, i64_type.const_int(STR_HEADER_SIZE, false));
+        let owned_ptr =
+            self.__str_offset(string, i64_type.const_int(STR_OWNED_OFFSET as u64, true));
+
+        let _ = self
+            .builder
+            .build_store(owned_ptr, i64_type.const_int(1, false));
+        self.__str_set_len(string, len);
+
+        string
+    }
+
+    fn __str_set_len(&self, string: PointerValue<'ctx>, len: IntValue<'ctx>) {
+        let i64_type = self.context.i64_type();
+        let len_ptr = self.__str_offset(string, i64_type.const_int(STR_LEN_OFFSET as u64, true));
+
+        let _ = self.builder.build_store(len_ptr, len);
+        let _ = self.builder.build_store(
+            self.__str_offset(string, len),
+            self.context.i8_type().const_zero(),
+        );
+    }
+
+    fn __str_len(&self, string: PointerValue<'ctx>) -> IntValue<'ctx> {
+        // `null` string is read as empty one
+        let i64_type = self.context.i64_type();
+        let is_null = self.builder.build_is_null(string, "").unwrap();
+        let string = self
+            .builder
+            .build_select(is_null, self.__str_empty(), string, "")
+            .unwrap()
+            .into_pointer_value();
+        let len_ptr = self.__str_offset(string, i64_type.const_int(STR_LEN_OFFSET as u64, true));
+
+        self.builder
+            .build_load(i64_type, len_ptr, "str_len")
+            .unwrap()
+            .into_int_value()
+    }
+
+    fn __str_empty(&self) -> PointerValue<'ctx> {
+        match self.module.get_global(STR_EMPTY_NAME) {
+            Some(global) => unsafe {
+                global.as_pointer_value().const_in_bounds_gep(
+                    self.context.i8_type(),
+                    &[self.context.i64_type().const_int(STR_HEADER_SIZE, false)],
+                )
+            },
+            None => self.build_str_literal("", STR_EMPTY_NAME),
+        }
+    }
+
+    fn __clamp(
+        &self,
+        value: IntValue<'ctx>,
+        low: IntValue<'ctx>,
+        high: Option<IntValue<'ctx>>,
+    ) -> IntValue<'ctx> {
+        let is_low = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::SLT, value, low, "")
+            .unwrap();
+        let value = self
+            .builder
+            .build_select(is_low, low, value, "")
+            .unwrap()
+            .into_int_value();
+
+        let Some(high) = high else {
+            return value;
+        };
+
+        let is_high = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::SGT, value, high, "")
+            .unwrap();
+
+        self.builder
+            .build_select(is_high, high, value, "")
+            .unwrap()
+            .into_int_value()
+    }
+
+    fn build_stdin(&mut self, line: usize) -> CompileResult<PointerValue<'ctx>> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+
+        // `stdin` is a global variable of C library
+        let stdin = self.module.get_global("stdin").unwrap_or_else(|| {
+            let global = self
+                .module
+                .add_global(ptr_type, Some(AddressSpace::default()), "stdin");
+            global.set_linkage(Linkage::External);
+            global
+        });
+
+        self.builder
+            .build_load(ptr_type, stdin.as_pointer_value(), "stdin")
+            .map(|stream| stream.into_pointer_value())
+            .map_err(|_| self.error("Unable to load `stdin`!", ErrorType::MemoryError, line))
+    }
+
+    fn build_str_literal(&self, value: &str, name: &str) -> PointerValue<'ctx> {
+        // literal is stored with its header, `owned` flag stays zero
+        let i64_type = self.context.i64_type();
+        let literal = self.context.const_struct(
+            &[
+                i64_type.const_int(value.len() as u64, false).into(),
+                i64_type.const_zero().into(),
+                self.context.const_string(value.as_bytes(), true).into(),
+            ],
+            false,
+        );
+
+        let global = self.module.add_global(literal.get_type(), None, name);
+        global.set_linkage(Linkage::Private);
+        global.set_unnamed_addr(true);
+        global.set_initializer(&literal);
+
+        unsafe {
+            global.as_pointer_value().const_in_bounds_gep(
+                self.context.i8_type(),
+                &[i64_type.const_int(STR_HEADER_SIZE, false)],
+            )
+        }
+    }
+
+    fn build_str_free(&mut self, string: PointerValue<'ctx>, line: usize) -> CompileResult<()> {
+        let free_fn = self.__runtime_str_free();
+
+        self.builder
+            .build_call(free_fn, &[string.into()], "")
+            .map_err(|_| self.error("Unable to free string!", ErrorType::MemoryError, line))
+            .map(|_| ())
+    }
+
+    fn build_str_alloc(
+        &mut self,
+        str_len: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        let alloc_fn = self.__runtime_str_alloc();
+
+        self.builder
+            .build_call(alloc_fn, &[str_len.into()], "")
+            .map_err(|_| self.error("Unable to allocate string!", ErrorType::MemoryError, line))
+            .map(|call| {
+                call.try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_pointer_value()
+            })
+    }
+
+    fn build_str_concat(
+        &mut self,
+        left: BasicValueEnum<'ctx>,
+        right: BasicValueEnum<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        let concat_fn = self.__runtime_str_concat();
+
+        self.builder
+            .build_call(concat_fn, &[left.into(), right.into()], "concat")
+            .map_err(|_| {
+                self.error(
+                    "An error occured while calling `concat` function!",
+                    ErrorType::BuildError,
+                    line,
+                )
+            })
+            .map(|call| {
+                call.try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_pointer_value()
+            })
+    }
+
+    fn build_str_read_line(
+        &mut self,
+        stream: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        let read_line_fn = self.__runtime_str_read_line();
+
+        self.builder
+            .build_call(read_line_fn, &[stream.into()], "line")
+            .map_err(|_| self.error("Unable to read line!", ErrorType::BuildError, line))
+            .map(|call| {
+                call.try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_pointer_value()
+            })
+    }
+
+    fn build_str_read_file(
+        &mut self,
+        path: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        let read_file_fn = self.__runtime_str_read_file();
+
+        self.build_str_runtime_call(read_file_fn, &[path.into()], line)
+            .map(|value| value.into_pointer_value())
+    }
+
+    fn build_str_format(
+        &mut self,
+        format: PointerValue<'ctx>,
+        value: BasicValueEnum<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        let snprintf_fn = self.__c_snprintf();
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let i64_type = self.context.i64_type();
+
+        // the first call only measures formatted string
+        let arguments: Vec<BasicMetadataValueEnum> = vec![
+            ptr_type.const_null().into(),
+            i64_type.const_zero().into(),
+            format.into(),
+            value.into(),
+        ];
+        let formatted_len = self
+            .builder
+            .build_call(snprintf_fn, &arguments, "")
+            .map_err(|_| self.error("Unable to format string!", ErrorType::BuildError, line))?
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+        let formatted_len = self
+            .builder
+            .build_int_s_extend(formatted_len, i64_type, "")
+            .unwrap();
+
+        let buffer = self.build_str_alloc(formatted_len, line)?;
+        let buffer_size = self
+            .builder
+            .build_int_add(formatted_len, i64_type.const_int(1, false), "")
+            .unwrap();
+
+        let arguments: Vec<BasicMetadataValueEnum> = vec![
+            buffer.into(),
+            buffer_size.into(),
+            format.into(),
+            value.into(),
+        ];
+        let _ = self
+            .builder
+            .build_call(snprintf_fn, &arguments, "")
+            .map_err(|_| self.error("Unable to format string!", ErrorType::BuildError, line))?;
+
+        Ok(buffer)
+    }
+
+    fn build_str_runtime_call(
+        &mut self,
+        callee: FunctionValue<'ctx>,
+        arguments: &[BasicMetadataValueEnum<'ctx>],
+        line: usize,
+    ) -> CompileResult<BasicValueEnum<'ctx>> {
+        self.builder
+            .build_call(callee, arguments, "")
+            .map_err(|_| {
+                self.error(
+                    format!(
+                        "An error occured while calling `{}` function!",
+                        callee.get_name().to_str().unwrap_or_default()
+                    ),
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| {
+                self.error(
+                    "Unable to get basic value from string function!",
+                    ErrorType::BuildError,
+                    line,
+                )
+            })
+    }
+
+    fn build_str_substr(
+        &mut self,
+        string: PointerValue<'ctx>,
+        start: IntValue<'ctx>,
+        substr_len: IntValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        // bounds are clamped to the string, so `substr` never reads out of it
+        let copy_fn = self.__runtime_str_copy();
+        let zero = self.context.i64_type().const_zero();
+
+        let string_len = self.__str_len(string);
+        let start = self.__clamp(start, zero, Some(string_len));
+        let rest_len = self.builder.build_int_sub(string_len, start, "").unwrap();
+        let substr_len = self.__clamp(substr_len, zero, Some(rest_len));
+
+        let source = self.__str_offset(string, start);
+
+        self.build_str_runtime_call(copy_fn, &[source.into(), substr_len.into()], line)
+            .map(|value| value.into_pointer_value())
+    }
+
+    fn build_str_find(
+        &mut self,
+        string: PointerValue<'ctx>,
+        pattern: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>> {
+        // index of the first occurrence or -1
+        let strstr_fn = self.__c_strstr();
+        let i64_type = self.context.i64_type();
+
+        let occurrence = self
+            .build_str_runtime_call(strstr_fn, &[string.into(), pattern.into()], line)?
+            .into_pointer_value();
+        let is_missing = self.builder.build_is_null(occurrence, "").unwrap();
+        let position = self.__str_distance(string, occurrence);
+
+        Ok(self
+            .builder
+            .build_select(is_missing, i64_type.const_all_ones(), position, "find")
+            .unwrap()
+            .into_int_value())
+    }
+
+    fn build_str_contains(
+        &mut self,
+        string: PointerValue<'ctx>,
+        pattern: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>> {
+        let strstr_fn = self.__c_strstr();
+
+        let occurrence = self
+            .build_str_runtime_call(strstr_fn, &[string.into(), pattern.into()], line)?
+            .into_pointer_value();
+
+        Ok(self
+            .builder
+            .build_is_not_null(occurrence, "contains")
+            .unwrap())
+    }
+
+    fn build_str_starts_with(
+        &mut self,
+        string: PointerValue<'ctx>,
+        prefix: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>> {
+        let strncmp_fn = self.__c_strncmp();
+
+        let prefix_len = self.__str_len(prefix);
+        let compared = self
+            .build_str_runtime_call(
+                strncmp_fn,
+                &[string.into(), prefix.into(), prefix_len.into()],
+                line,
+            )?
+            .into_int_value();
+
+        Ok(self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                compared,
+                self.context.i32_type().const_zero(),
+                "starts_with",
+            )
+            .unwrap())
+    }
+
+    fn build_str_ends_with(
+        &mut self,
+        string: PointerValue<'ctx>,
+        suffix: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>> {
+        let strcmp_fn = self.__c_strcmp();
+
+        let string_len = self.__str_len(string);
+        let suffix_len = self.__str_len(suffix);
+
+        // longer suffix is compared from the start of string, but result is discarded
+        let is_fitting = self
+            .builder
+            .build_int_compare(inkwell::IntPredicate::ULE, suffix_len, string_len, "")
+            .unwrap();
+        let offset = self
+            .builder
+            .build_int_sub(string_len, suffix_len, "")
+            .unwrap();
+        let offset = self
+            .builder
+            .build_select(is_fitting, offset, self.context.i64_type().const_zero(), "")
+            .unwrap()
+            .into_int_value();
+
+        let tail = self.__str_offset(string, offset);
+        let compared = self
+            .build_str_runtime_call(strcmp_fn, &[tail.into(), suffix.into()], line)?
+            .into_int_value();
+        let is_equal = self
+            .builder
+            .build_int_compare(
+                inkwell::IntPredicate::EQ,
+                compared,
+                self.context.i32_type().const_zero(),
+                "",
+            )
+            .unwrap();
+
+        Ok(self
+            .builder
+            .build_and(is_fitting, is_equal, "ends_with")
+            .unwrap())
+    }
+
+    fn build_str_split(
+        &mut self,
+        string: PointerValue<'ctx>,
+        separator: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<(String, BasicValueEnum<'ctx>)> {
+        let count_fn = self.__runtime_str_count();
+        let split_fn = self.__runtime_str_split();
+        let i64_type = self.context.i64_type();
+
+        let count = self
+            .build_str_runtime_call(count_fn, &[string.into(), separator.into()], line)?
+            .into_int_value();
+        let pieces_len = self
+            .builder
+            .build_int_add(count, i64_type.const_int(1, false), "")
+            .unwrap();
+
+        let list = self.build_list_with_len("list<str>", pieces_len, line)?;
+        let pieces = self.list_data_pointer(
+            list.1.into_pointer_value(),
+            "str",
+            i64_type.const_zero(),
+            line,
+        )?;
+
+        let _ = self
+            .builder
+            .build_call(
+                split_fn,
+                &[string.into(), separator.into(), pieces.into()],
+                "",
+            )
+            .map_err(|_| {
+                self.error(
+                    "An error occured while calling `split` function!",
+                    ErrorType::BuildError,
+                    line,
+                )
+            })?;
+
+        Ok(list)
+    }
+
+    fn build_str_join(
+        &mut self,
+        list: PointerValue<'ctx>,
+        separator: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<PointerValue<'ctx>> {
+        let join_fn = self.__runtime_str_join();
+
+        let items_len = self.build_list_len(list, line)?;
+        let items =
+            self.list_data_pointer(list, "str", self.context.i64_type().const_zero(), line)?;
+
+        self.build_str_runtime_call(
+            join_fn,
+            &[items.into(), items_len.into(), separator.into()],
+            line,
+        )
+        .map(|value| value.into_pointer_value())
+    }
+
+    fn build_str_compare(
+        &mut self,
+        left: PointerValue<'ctx>,
+        right: PointerValue<'ctx>,
+        line: usize,
+    ) -> CompileResult<IntValue<'ctx>> {
+        let compare_fn = self.__runtime_str_compare();
+
+        self.build_str_runtime_call(compare_fn, &[left.into(), right.into()], line)
+            .map(|value| value.into_int_value())
+    }
+}
@@ -0,0 +1,23 @@
+// Toy Programming Language | by mealet
+// https://github.com/mealet/tpl-lang
+// =========================================
+// Project licensed under the BSD-3 LICENSE.
+// Check the `LICENSE` file to more info.
+
+#[derive(Debug, Clone)]
+#[allow(unused)]
+pub struct Structure<'ctx> {
+    pub name: String,
+    pub fields: Vec<(String, String)>, // ("x", "int32")
+    pub struct_type: inkwell::types::StructType<'ctx>,
+}
+
+impl Structure<'_> {
+    // index and type of the field
+    pub fn field(&self, name: &str) -> Option<(u32, String)> {
+        self.fields
+            .iter()
+            .position(|(field, _)| field == name)
+            .map(|index| (index as u32, self.fields[index].1.clone()))
+    }
+}
@@ -4,6 +4,7 @@
 // Project licensed under the BSD-3 LICENSE.
 // Check the `LICENSE` file to more info.
 
+use crate::span::Span;
 use colored::Colorize;
 
 // handler
@@ -25,6 +26,9 @@ pub struct LexerError {
     line_number: usize,
     position: usize,
     char: char,
+
+    span: Span,
+    underline: String,
 }
 
 // implementations
@@ -86,9 +90,21 @@ impl LexerError {
             line_number,
             position,
             char,
+            span: Span::default(),
+            underline: String::new(),
         }
     }
 
+    pub fn with_span(mut self, span: Span, source: &str) -> Self {
+        self.underline = span.underline(source);
+        self.span = span;
+        self
+    }
+
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
+
     pub fn get_description(&self) -> String {
         self.description.clone()
     }
@@ -101,7 +117,7 @@ impl LexerError {
         let line_number_length = self.line_number.to_string().len();
         let filename_fmt = format!("--> {}", self.filename).cyan();
         let lines_fmt = format!(
-            "{}{}\n {} {} {}\n{}{}",
+            "{}{}\n {} {} {}\n{}{} {}",
             // first line
             " ".repeat(line_number_length + 2),
             "|".cyan(),
@@ -109,9 +125,10 @@ impl LexerError {
             self.line_number,
             "|".cyan(),
             self.line,
-            // last line
+            // last line (with marked span)
             " ".repeat(line_number_length + 2),
             "|".cyan(),
+            self.underline.red(),
         );
 
         format!(
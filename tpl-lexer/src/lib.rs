@@ -6,12 +6,14 @@
 
 pub mod error;
 mod macros;
+pub mod span;
 pub mod token;
 pub mod token_type;
 
 use std::collections::HashMap;
 //
 use error::LexerErrorHandler;
+use span::Span;
 use token::Token;
 use token_type::TokenType;
 
@@ -28,6 +30,10 @@ pub struct Lexer {
     position: usize,
     line: usize,
     char: char,
+
+    file_id: usize,
+    offset: usize,      // byte offset of current char
+    next_offset: usize, // byte offset of next char
 }
 
 #[allow(unused)]
@@ -43,13 +49,16 @@ impl Lexer {
                 macros::std_symbol!('-', TokenType::Minus),
                 macros::std_symbol!('*', TokenType::Multiply),
                 macros::std_symbol!('/', TokenType::Divide),
+                macros::std_symbol!('%', TokenType::Modulo),
                 macros::std_symbol!('=', TokenType::Equal),
                 macros::std_symbol!('!', TokenType::Not),
+                macros::std_symbol!('~', TokenType::Tilde),
                 macros::std_symbol!('^', TokenType::Xor),
                 macros::std_symbol!('<', TokenType::Lt),
                 macros::std_symbol!('>', TokenType::Bt),
                 macros::std_symbol!('.', TokenType::Dot),
                 macros::std_symbol!(',', TokenType::Comma),
+                macros::std_symbol!(':', TokenType::Colon),
                 macros::std_symbol!('"', TokenType::Quote),
                 macros::std_symbol!('\'', TokenType::SingleQuote),
                 macros::std_symbol!(';', TokenType::Semicolon),
@@ -66,20 +75,30 @@ impl Lexer {
                 // Constructions
                 macros::std_keyword!("if"),
                 macros::std_keyword!("else"),
+                macros::std_keyword!("switch"),
+                macros::std_keyword!("case"),
+                macros::std_keyword!("default"),
                 macros::std_keyword!("while"),
                 macros::std_keyword!("for"),
                 macros::std_keyword!("in"),
                 macros::std_keyword!("break"),
+                macros::std_keyword!("continue"),
                 // Functions and Imports
                 macros::std_keyword!("define"),
                 macros::std_keyword!("return"),
                 macros::std_keyword!("import"),
+                // User types
+                macros::std_keyword!("struct"),
+                macros::std_keyword!("enum"),
+                macros::std_keyword!("match"),
                 // Datatypes
                 macros::std_keyword!("int8"),
                 macros::std_keyword!("int16"),
                 macros::std_keyword!("int32"),
                 macros::std_keyword!("int64"),
                 macros::std_keyword!("int128"),
+                macros::std_keyword!("float32"),
+                macros::std_keyword!("float64"),
                 macros::std_keyword!("auto"),
                 macros::std_keyword!("fn"),
                 macros::std_keyword!("void"),
@@ -87,6 +106,8 @@ impl Lexer {
                 macros::std_keyword!("char"),
                 macros::std_keyword!("bool"),
                 macros::std_keyword!("FILE"),
+                macros::std_keyword!("list"),
+                macros::std_keyword!("map"),
                 // Values
                 macros::std_token!("true", TokenType::Boolean),
                 macros::std_token!("false", TokenType::Boolean),
@@ -98,37 +119,61 @@ impl Lexer {
             position: 0,
             line: 0,
             char: ' ',
+
+            file_id: 0,
+            offset: 0,
+            next_offset: 0,
         };
 
         lexer.getc();
         lexer
     }
 
+    pub fn with_file_id(mut self, file_id: usize) -> Self {
+        self.file_id = file_id;
+        self
+    }
+
     // fundamental functions
 
     fn error<T: std::fmt::Display>(&mut self, description: T) {
-        let source_clone = self.source.clone();
-        let source_lines: Vec<&str> = source_clone.lines().collect();
+        let source_line = self.source.lines().nth(self.line).unwrap_or_default();
+        let span = Span::new(
+            self.file_id,
+            self.offset,
+            self.offset + self.char.len_utf8(),
+        );
 
-        self.errors.attach(error::LexerError::new(
-            self.filename.clone(),
-            description.to_string(),
-            source_lines[self.line].to_string(),
-            self.line,
-            self.position,
-            self.char,
-        ));
+        self.errors.attach(
+            error::LexerError::new(
+                self.filename.clone(),
+                description.to_string(),
+                source_line.to_string(),
+                self.line,
+                self.position,
+                self.char,
+            )
+            .with_span(span, &self.source),
+        );
     }
 
     fn getc(&mut self) {
+        self.offset = self.next_offset;
+
         if self.position < self.input.len() {
             self.char = self.input[self.position];
             self.position += 1;
+            self.next_offset += self.char.len_utf8();
         } else {
             self.char = '\0'
         }
     }
 
+    fn peekc(&self) -> char {
+        // next char without moving
+        self.input.get(self.position).copied().unwrap_or('\0')
+    }
+
     // filters
 
     fn is_eof(&self) -> bool {
@@ -139,8 +184,98 @@ impl Lexer {
         ['a', 'b', 'c', 'd', 'e', 'f'].contains(&value.to_ascii_lowercase())
     }
 
+    fn is_float_literal(&self) -> bool {
+        // looking ahead without consuming: digits, then `.digit` or `e[+-]digit`
+        let mut index = self.position - 1;
+
+        if self.input.get(index) == Some(&'0')
+            && matches!(self.input.get(index + 1), Some('x') | Some('b'))
+        {
+            return false;
+        }
+
+        while self
+            .input
+            .get(index)
+            .is_some_and(|ch| ch.is_ascii_digit() || *ch == '_')
+        {
+            index += 1;
+        }
+
+        match self.input.get(index) {
+            Some('.') => self
+                .input
+                .get(index + 1)
+                .is_some_and(|ch| ch.is_ascii_digit()),
+            Some('e') | Some('E') => {
+                if matches!(self.input.get(index + 1), Some('+') | Some('-')) {
+                    index += 1;
+                }
+
+                self.input
+                    .get(index + 1)
+                    .is_some_and(|ch| ch.is_ascii_digit())
+            }
+            _ => false,
+        }
+    }
+
     // helpful functions
 
+    fn get_float(&mut self) -> f64 {
+        let mut value = String::new();
+
+        // integer part and fraction (`_` separators are allowed too)
+        while self.char.is_ascii_digit() || self.char == '_' || self.char == '.' {
+            if self.char == '.' {
+                if value.contains('.') {
+                    break;
+                }
+
+                // `1..5` is a range, not a float
+                if !self
+                    .input
+                    .get(self.position)
+                    .is_some_and(|ch| ch.is_ascii_digit())
+                {
+                    break;
+                }
+            }
+
+            if self.char != '_' {
+                value.push(self.char);
+            }
+
+            self.getc();
+        }
+
+        // exponent (only if digits are following)
+        let exponent_digit = match self.input.get(self.position) {
+            Some('+') | Some('-') => self.input.get(self.position + 1),
+            other => other,
+        };
+
+        if ['e', 'E'].contains(&self.char) && exponent_digit.is_some_and(|ch| ch.is_ascii_digit()) {
+            value.push('e');
+            self.getc();
+
+            if ['+', '-'].contains(&self.char) {
+                value.push(self.char);
+                self.getc();
+            }
+
+            while self.char.is_ascii_digit() {
+                value.push(self.char);
+                self.getc();
+            }
+        }
+
+        value.parse().unwrap_or_else(|_| {
+            self.error("Error with parsing float number!");
+            0.0
+        })
+    }
+
     fn get_integer(&mut self) -> i64 {
         let mut value = String::new();
         let mut mode = 0; // 1 - binary, 2 - hexadecimal
@@ -212,12 +347,103 @@ impl Lexer {
         })
     }
 
+    fn get_escape_sequence(&mut self) -> Option<char> {
+        // skipping `\` char
+        self.getc();
+
+        let escaped = match self.char {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' => '\\',
+            '"' => '"',
+            '\'' => '\'',
+            'x' => {
+                let mut code = String::new();
+
+                for _ in 0..2 {
+                    self.getc();
+
+                    if !self.char.is_ascii_hexdigit() {
+                        self.error(format!(
+                            "Invalid escape sequence `\\x{}{}`: expected 2 hexadecimal digits!",
+                            code, self.char
+                        ));
+                        return None;
+                    }
+
+                    code.push(self.char);
+                }
+
+                let value = u8::from_str_radix(&code, 16).unwrap_or_default();
+
+                if value > 0x7F {
+                    self.error(format!(
+                        "Invalid escape sequence `\\x{}`: value must be in range `\\x00`..`\\x7F`!",
+                        code
+                    ));
+                    return None;
+                }
+
+                value as char
+            }
+            'u' => {
+                self.getc();
+
+                if self.char != '{' {
+                    self.error("Invalid unicode escape sequence: expected `{` after `\\u`!");
+                    return None;
+                }
+
+                let mut code = String::new();
+                self.getc();
+
+                while self.char != '}' {
+                    if !self.char.is_ascii_hexdigit() || code.len() >= 6 {
+                        self.error(format!(
+                            "Invalid unicode escape sequence `\\u{{{}`: expected up to 6 hexadecimal digits and `}}`!",
+                            code
+                        ));
+                        return None;
+                    }
+
+                    code.push(self.char);
+                    self.getc();
+                }
+
+                let value = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32);
+
+                match value {
+                    Some(ch) => ch,
+                    None => {
+                        self.error(format!(
+                            "Invalid unicode escape sequence `\\u{{{}}}`: not a valid unicode character!",
+                            code
+                        ));
+                        return None;
+                    }
+                }
+            }
+            _ => {
+                self.error(format!("Unknown escape sequence `\\{}` found!", self.char));
+                return None;
+            }
+        };
+
+        self.getc();
+        Some(escaped)
+    }
+
     // main function
 
     pub fn tokenize(&mut self) -> Result<Vec<Token>, LexerErrorHandler> {
-        let mut output = Vec::new();
+        let mut output: Vec<Token> = Vec::new();
 
         while !self.is_eof() {
+            let start = self.offset;
+            let tokens_count = output.len();
+
             match self.char {
                 '\0' => self.getc(),
                 '\n' => {
@@ -226,9 +452,32 @@ impl Lexer {
                 }
                 _ if self.char.is_whitespace() => self.getc(),
                 '-' => {
-                    // possibly negative number
+                    // possibly negative number, unless it follows an operand (`a-1`)
+                    let follows_operand = matches!(
+                        output.last().map(|token: &Token| token.token_type),
+                        Some(
+                            TokenType::Identifier
+                                | TokenType::Number
+                                | TokenType::Float
+                                | TokenType::String
+                                | TokenType::Char
+                                | TokenType::Boolean
+                                | TokenType::RParen
+                                | TokenType::RBrack
+                        )
+                    );
+
                     self.getc();
-                    if self.char.is_ascii_digit() {
+                    if follows_operand {
+                        output.push(Token::new(TokenType::Minus, String::from("-"), self.line));
+                    } else if self.char.is_ascii_digit() && self.is_float_literal() {
+                        let value = -self.get_float();
+                        output.push(Token::new(
+                            TokenType::Float,
+                            format!("{:?}", value),
+                            self.line,
+                        ));
+                    } else if self.char.is_ascii_digit() {
                         let value = -self.get_integer();
 
                         // formatting value and matching stringify mode
@@ -238,11 +487,8 @@ impl Lexer {
                         // pushing token
 
                         output.push(Token::new(token_type, token_value, self.line));
-
-                        self.getc();
                     } else {
-                        output.push(Token::new(TokenType::Minus, String::from("-"), 0));
-                        self.getc();
+                        output.push(Token::new(TokenType::Minus, String::from("-"), self.line));
                     }
                 }
                 _ if self.std_symbols.contains_key(&self.char) => {
@@ -251,48 +497,181 @@ impl Lexer {
                     match matched_token.token_type {
                         TokenType::Quote => {
                             self.getc();
+
+                            let start_line = self.line;
                             let mut captured_string = String::new();
 
                             while self.char != '"' {
-                                captured_string.push(self.char);
-                                self.getc();
+                                match self.char {
+                                    _ if self.is_eof() => break,
+                                    '\\' => {
+                                        if let Some(escaped) = self.get_escape_sequence() {
+                                            captured_string.push(escaped);
+                                        }
+                                    }
+                                    '\n' => {
+                                        self.line += 1;
+                                        captured_string.push(self.char);
+                                        self.getc();
+                                    }
+                                    _ => {
+                                        captured_string.push(self.char);
+                                        self.getc();
+                                    }
+                                }
+                            }
+
+                            if self.is_eof() {
+                                // pointing error to the line where string starts
+                                self.line = start_line;
+                                self.error("Unterminated string literal found!");
+                                continue;
                             }
 
                             // pushing token
-                            output.push(Token::new(TokenType::String, captured_string, self.line));
+                            output.push(Token::new(TokenType::String, captured_string, start_line));
                             self.getc();
                         }
                         TokenType::SingleQuote => {
                             self.getc();
 
-                            let char = self.char;
+                            let char = match self.char {
+                                '\\' => self.get_escape_sequence(),
+                                '\'' => {
+                                    self.error("Empty char literal found!");
+                                    None
+                                }
+                                _ if self.char == '\n' || self.is_eof() => {
+                                    self.error("Unterminated char literal found!");
+                                    continue;
+                                }
+                                _ => {
+                                    let char = self.char;
+                                    self.getc();
 
-                            self.getc();
+                                    Some(char)
+                                }
+                            };
+
+                            // `char` is a single byte, so only ASCII fits into it
+                            let char = match char {
+                                Some(char) if !char.is_ascii() => {
+                                    self.error(format!(
+                                        "Char `{}` doesn't fit in a single byte! For non-ASCII text use `str` type!",
+                                        char
+                                    ));
+                                    None
+                                }
+                                char => char,
+                            };
 
                             if self.char != '\'' {
-                                self.error("Wrong char found! For strings use `str` type!");
-                                self.getc();
+                                // invalid escape sequence is already reported
+                                if char.is_some() {
+                                    self.error("Wrong char found! For strings use `str` type!");
+                                }
+
+                                // skipping the rest of literal
+                                while !['\'', '\n'].contains(&self.char) && !self.is_eof() {
+                                    self.getc();
+                                }
                             }
 
-                            output.push(Token::new(TokenType::Char, char.to_string(), self.line));
-                            self.getc();
+                            if let Some(char) = char {
+                                output.push(Token::new(
+                                    TokenType::Char,
+                                    char.to_string(),
+                                    self.line,
+                                ));
+                            }
+
+                            if self.char == '\'' {
+                                self.getc();
+                            }
                         }
-                        TokenType::Equal => {
-                            // checking if next symbol is `equal`
+                        TokenType::Divide if self.peekc() == '/' => {
+                            // line comment (`///` is a doc comment, `////` is not)
+                            self.getc();
                             self.getc();
 
-                            if self.char == '=' {
+                            let is_doc = self.char == '/' && self.peekc() != '/';
+                            if is_doc {
+                                self.getc();
+                            }
+
+                            let mut captured_comment = String::new();
+
+                            while self.char != '\n' && !self.is_eof() {
+                                captured_comment.push(self.char);
+                                self.getc();
+                            }
+
+                            if is_doc {
                                 output.push(Token::new(
-                                    TokenType::Eq,
-                                    String::from("=="),
+                                    TokenType::DocComment,
+                                    captured_comment.trim().to_string(),
                                     self.line,
                                 ));
+                            }
+                        }
+                        TokenType::Divide if self.peekc() == '*' => {
+                            // block comment (might be nested)
+                            self.getc();
+                            self.getc();
+
+                            let start_line = self.line;
+                            let mut depth = 1;
+
+                            while depth > 0 && !self.is_eof() {
+                                match (self.char, self.peekc()) {
+                                    ('/', '*') => {
+                                        depth += 1;
+                                        self.getc();
+                                    }
+                                    ('*', '/') => {
+                                        depth -= 1;
+                                        self.getc();
+                                    }
+                                    ('\n', _) => self.line += 1,
+                                    _ => {}
+                                }
+
                                 self.getc();
-                            } else {
-                                let mut formatted_token = matched_token;
-                                formatted_token.line = self.line;
+                            }
 
-                                output.push(formatted_token);
+                            if depth > 0 {
+                                // pointing error to the line where comment starts
+                                self.line = start_line;
+                                self.error("Unterminated block comment found!");
+                            }
+                        }
+                        TokenType::Equal => {
+                            // checking if next symbol is `equal` or `bigger than`
+                            self.getc();
+
+                            match self.char {
+                                '=' => {
+                                    output.push(Token::new(
+                                        TokenType::Eq,
+                                        String::from("=="),
+                                        self.line,
+                                    ));
+                                    self.getc();
+                                }
+                                '>' => {
+                                    output.push(Token::new(
+                                        TokenType::Arrow,
+                                        String::from("=>"),
+                                        self.line,
+                                    ));
+                                    self.getc();
+                                }
+                                _ => {
+                                    let mut formatted_token = matched_token;
+                                    formatted_token.line = self.line;
+
+                                    output.push(formatted_token);
+                                }
                             }
                         }
                         TokenType::Lt => {
@@ -308,6 +687,14 @@ impl Lexer {
                                     ));
                                     self.getc();
                                 }
+                                '=' => {
+                                    output.push(Token::new(
+                                        TokenType::Le,
+                                        String::from("<="),
+                                        self.line,
+                                    ));
+                                    self.getc();
+                                }
                                 _ => {
                                     let mut formatted_token = matched_token;
                                     formatted_token.line = self.line;
@@ -329,6 +716,14 @@ impl Lexer {
                                     ));
                                     self.getc();
                                 }
+                                '=' => {
+                                    output.push(Token::new(
+                                        TokenType::Be,
+                                        String::from(">="),
+                                        self.line,
+                                    ));
+                                    self.getc();
+                                }
                                 _ => {
                                     let mut formatted_token = matched_token;
                                     formatted_token.line = self.line;
@@ -386,7 +781,8 @@ impl Lexer {
                                     ));
                                     self.getc()
                                 }
-                                ' ' => {
+                                ' ' | '=' => {
+                                    // `&=` is compound assignment
                                     let mut formatted_token = matched_token;
                                     formatted_token.line = self.line;
 
@@ -410,12 +806,21 @@ impl Lexer {
                         }
                     }
                 }
+                _ if self.char.is_ascii_digit() && self.is_float_literal() => {
+                    let value = self.get_float();
+
+                    output.push(Token::new(
+                        TokenType::Float,
+                        format!("{:?}", value),
+                        self.line,
+                    ));
+                }
                 _ if self.char.is_ascii_digit() => {
                     let value = self.get_integer();
 
                     output.push(Token::new(TokenType::Number, value.to_string(), self.line));
                 }
-                _ if self.char.is_alphabetic() => {
+                _ if self.char.is_alphabetic() || self.char == '_' => {
                     let allowed_identifier_chars = ['_'];
 
                     let mut id = String::new();
@@ -427,7 +832,9 @@ impl Lexer {
                     }
 
                     if self.std_words.contains_key(&id) {
-                        let matched_token = self.std_words.get(&id).unwrap().clone();
+                        let mut matched_token = self.std_words.get(&id).unwrap().clone();
+                        matched_token.line = self.line;
+
                         output.push(matched_token);
                     } else {
                         output.push(Token::new(TokenType::Identifier, id, self.line));
@@ -443,10 +850,21 @@ impl Lexer {
                     self.getc();
                 }
             }
+
+            // token covers everything consumed since its first char
+            if output.len() > tokens_count {
+                if let Some(token) = output.last_mut() {
+                    token.span = Span::new(self.file_id, start, self.offset);
+                }
+            }
         }
 
-        if !output.contains(&Token::new(TokenType::EOF, String::new(), 0)) {
-            output.push(Token::new(TokenType::EOF, String::new(), 0));
+        if !output
+            .iter()
+            .any(|token| token.token_type == TokenType::EOF)
+        {
+            let eof_span = Span::new(self.file_id, self.source.len(), self.source.len());
+            output.push(Token::new(TokenType::EOF, String::new(), self.line).with_span(eof_span));
         };
 
         if !self.errors.is_empty() {
@@ -460,15 +878,23 @@ impl Lexer {
 mod tests {
     use super::*;
 
+    // most of tests are checking only types, values and lines
+    fn without_spans(tokens: Vec<Token>) -> Vec<Token> {
+        tokens
+            .into_iter()
+            .map(|token| token.with_span(Span::default()))
+            .collect()
+    }
+
     #[test]
     fn std_symbols_lexing() {
-        let input = String::from("+ - * / = ! < > . , ; ( ) [ ] { }");
+        let input = String::from("+ - * / = ! ~ < > . , : ; ( ) [ ] { }");
         let mut lexer = Lexer::new(input, "tests".to_string());
 
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 macros::std_symbol!('+', TokenType::Plus).1,
                 macros::std_symbol!('-', TokenType::Minus).1,
@@ -476,10 +902,12 @@ mod tests {
                 macros::std_symbol!('/', TokenType::Divide).1,
                 macros::std_symbol!('=', TokenType::Equal).1,
                 macros::std_symbol!('!', TokenType::Not).1,
+                macros::std_symbol!('~', TokenType::Tilde).1,
                 macros::std_symbol!('<', TokenType::Lt).1,
                 macros::std_symbol!('>', TokenType::Bt).1,
                 macros::std_symbol!('.', TokenType::Dot).1,
                 macros::std_symbol!(',', TokenType::Comma).1,
+                macros::std_symbol!(':', TokenType::Colon).1,
                 macros::std_symbol!(';', TokenType::Semicolon).1,
                 macros::std_symbol!('(', TokenType::LParen).1,
                 macros::std_symbol!(')', TokenType::RParen).1,
@@ -503,6 +931,82 @@ mod tests {
         assert_eq!(result[0].value, expected);
     }
 
+    #[test]
+    fn escape_sequences_lexing() {
+        let input = String::from(r#" "a\nb\t\"c\"\\ \0 \x41 \u{1F600}" "#);
+        let expected = String::from("a\nb\t\"c\"\\ \0 A \u{1F600}");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let result = lexer.tokenize().unwrap();
+
+        assert_eq!(result[0].token_type, TokenType::String);
+        assert_eq!(result[0].value, expected);
+    }
+
+    #[test]
+    fn char_escape_sequences_lexing() {
+        let input = String::from(r#" 'a' '\n' '\'' '\\' '\x7F' "#);
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let result = lexer.tokenize().unwrap();
+
+        assert_eq!(
+            without_spans(result),
+            vec![
+                Token::new(TokenType::Char, String::from("a"), 0),
+                Token::new(TokenType::Char, String::from("\n"), 0),
+                Token::new(TokenType::Char, String::from("'"), 0),
+                Token::new(TokenType::Char, String::from("\\"), 0),
+                Token::new(TokenType::Char, String::from("\x7F"), 0),
+                Token::new(TokenType::EOF, String::new(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_escape_sequences_test() {
+        let inputs = [
+            r#" "\q" "#,
+            r#" "\x4" "#,
+            r#" "\x80" "#,
+            r#" "\u{110000}" "#,
+            r#" '\u41' "#,
+        ];
+
+        for input in inputs {
+            let mut lexer = Lexer::new(input.to_string(), "tests".to_string());
+            let result = lexer.tokenize();
+
+            assert!(result.is_err(), "escape in {} must be rejected", input);
+        }
+    }
+
+    #[test]
+    fn non_ascii_char_test() {
+        for input in ["char a = 'é';", r"char a = '\u{E9}';"] {
+            let mut lexer = Lexer::new(input.to_string(), "tests".to_string());
+            let result = lexer.tokenize();
+
+            assert!(result
+                .unwrap_err()
+                .format_all()
+                .contains("Char `é` doesn't fit in a single byte!"));
+        }
+    }
+
+    #[test]
+    fn unterminated_string_test() {
+        let input = String::from("str a = \"never closed;\nprint(a);");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let result = lexer.tokenize();
+
+        assert!(result
+            .unwrap_err()
+            .format_all()
+            .contains("Unterminated string literal found!"));
+    }
+
     #[test]
     fn test_std_functions_lexing() {
         let input = String::from("print concat");
@@ -511,7 +1015,7 @@ mod tests {
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Identifier, String::from("print"), 0),
                 Token::new(TokenType::Identifier, String::from("concat"), 0),
@@ -522,20 +1026,24 @@ mod tests {
 
     #[test]
     fn test_constructions() {
-        let input = String::from("if else while for in break");
+        let input = String::from("if else switch case default while for in break continue");
         let mut lexer = Lexer::new(input, "tests".to_string());
 
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Keyword, String::from("if"), 0),
                 Token::new(TokenType::Keyword, String::from("else"), 0),
+                Token::new(TokenType::Keyword, String::from("switch"), 0),
+                Token::new(TokenType::Keyword, String::from("case"), 0),
+                Token::new(TokenType::Keyword, String::from("default"), 0),
                 Token::new(TokenType::Keyword, String::from("while"), 0),
                 Token::new(TokenType::Keyword, String::from("for"), 0),
                 Token::new(TokenType::Keyword, String::from("in"), 0),
                 Token::new(TokenType::Keyword, String::from("break"), 0),
+                Token::new(TokenType::Keyword, String::from("continue"), 0),
                 Token::new(TokenType::EOF, String::from(""), 0),
             ]
         );
@@ -543,17 +1051,20 @@ mod tests {
 
     #[test]
     fn test_functional_keywords() {
-        let input = String::from("define return import");
+        let input = String::from("define return import struct enum match");
         let mut lexer = Lexer::new(input, "tests".to_string());
 
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Keyword, String::from("define"), 0),
                 Token::new(TokenType::Keyword, String::from("return"), 0),
                 Token::new(TokenType::Keyword, String::from("import"), 0),
+                Token::new(TokenType::Keyword, String::from("struct"), 0),
+                Token::new(TokenType::Keyword, String::from("enum"), 0),
+                Token::new(TokenType::Keyword, String::from("match"), 0),
                 Token::new(TokenType::EOF, String::from(""), 0),
             ]
         );
@@ -561,13 +1072,13 @@ mod tests {
 
     #[test]
     fn test_datatypes() {
-        let input = String::from("int8 int16 int32 int64 auto void bool str fn");
+        let input = String::from("int8 int16 int32 int64 auto void bool str fn list map");
         let mut lexer = Lexer::new(input, "tests".to_string());
 
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Keyword, String::from("int8"), 0),
                 Token::new(TokenType::Keyword, String::from("int16"), 0),
@@ -578,6 +1089,8 @@ mod tests {
                 Token::new(TokenType::Keyword, String::from("bool"), 0),
                 Token::new(TokenType::Keyword, String::from("str"), 0),
                 Token::new(TokenType::Keyword, String::from("fn"), 0),
+                Token::new(TokenType::Keyword, String::from("list"), 0),
+                Token::new(TokenType::Keyword, String::from("map"), 0),
                 Token::new(TokenType::EOF, String::from(""), 0),
             ]
         );
@@ -591,7 +1104,7 @@ mod tests {
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Identifier, String::from("id1"), 0),
                 Token::new(TokenType::Identifier, String::from("id2"), 0),
@@ -613,7 +1126,7 @@ mod tests {
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Number, String::from("1"), 0),
                 Token::new(TokenType::Number, String::from("2"), 0),
@@ -627,24 +1140,46 @@ mod tests {
 
     #[test]
     fn test_negative_numbers() {
-        let input = String::from("-1 -2 -3 -1000 -1_000_000");
+        let input = String::from("-1, -2, -3, -1000, -1_000_000");
         let mut lexer = Lexer::new(input, "tests".to_string());
 
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Number, String::from("-1"), 0),
+                Token::new(TokenType::Comma, String::from(","), 0),
                 Token::new(TokenType::Number, String::from("-2"), 0),
+                Token::new(TokenType::Comma, String::from(","), 0),
                 Token::new(TokenType::Number, String::from("-3"), 0),
+                Token::new(TokenType::Comma, String::from(","), 0),
                 Token::new(TokenType::Number, String::from("-1000"), 0),
+                Token::new(TokenType::Comma, String::from(","), 0),
                 Token::new(TokenType::Number, String::from("-1000000"), 0),
                 Token::new(TokenType::EOF, String::from(""), 0),
             ]
         );
     }
 
+    #[test]
+    fn minus_after_operand_test() {
+        let input = String::from("a-1 b[0]-2 f()-3 4-5");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let result = lexer.tokenize().unwrap();
+        let minus_count = result
+            .iter()
+            .filter(|token| token.token_type == TokenType::Minus)
+            .count();
+
+        assert_eq!(minus_count, 4);
+        assert!(result
+            .iter()
+            .filter(|token| token.token_type == TokenType::Number)
+            .all(|token| !token.value.starts_with('-')));
+    }
+
     #[test]
     fn test_lines() {
         let input = String::from("line0 \n line1 \n line2");
@@ -653,12 +1188,12 @@ mod tests {
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Identifier, String::from("line0"), 0),
                 Token::new(TokenType::Identifier, String::from("line1"), 1),
                 Token::new(TokenType::Identifier, String::from("line2"), 2),
-                Token::new(TokenType::EOF, String::from(""), 0),
+                Token::new(TokenType::EOF, String::from(""), 2),
             ]
         );
     }
@@ -671,7 +1206,7 @@ mod tests {
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Boolean, String::from("true"), 0),
                 Token::new(TokenType::Boolean, String::from("false"), 0),
@@ -682,18 +1217,20 @@ mod tests {
 
     #[test]
     fn test_compare_operators() {
-        let input = String::from("> < == !=");
+        let input = String::from("> < == != <= >=");
         let mut lexer = Lexer::new(input, "tests".to_string());
 
         let result = lexer.tokenize().unwrap();
 
         assert_eq!(
-            result,
+            without_spans(result),
             vec![
                 Token::new(TokenType::Bt, String::from(">"), 0),
                 Token::new(TokenType::Lt, String::from("<"), 0),
                 Token::new(TokenType::Eq, String::from("=="), 0),
                 Token::new(TokenType::Ne, String::from("!="), 0),
+                Token::new(TokenType::Le, String::from("<="), 0),
+                Token::new(TokenType::Be, String::from(">="), 0),
                 Token::new(TokenType::EOF, String::from(""), 0),
             ]
         );
@@ -749,7 +1286,7 @@ mod tests {
         let tokens = lexer.tokenize().unwrap();
 
         assert_eq!(
-            tokens,
+            without_spans(tokens),
             vec![
                 Token::new(TokenType::Identifier, String::from("a"), 0),
                 Token::new(TokenType::Or, String::from("||"), 0),
@@ -767,7 +1304,7 @@ mod tests {
         let tokens = lexer.tokenize().unwrap();
 
         assert_eq!(
-            tokens,
+            without_spans(tokens),
             vec![
                 Token::new(TokenType::Identifier, String::from("a"), 0),
                 Token::new(TokenType::And, String::from("&&"), 0),
@@ -785,7 +1322,7 @@ mod tests {
         let tokens = lexer.tokenize().unwrap();
 
         assert_eq!(
-            tokens,
+            without_spans(tokens),
             vec![
                 Token::new(TokenType::Ampersand, String::from("&"), 0),
                 Token::new(TokenType::Verbar, String::from("|"), 0),
@@ -796,4 +1333,258 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn float_literals_test() {
+        let input = String::from("-0.5 3.14 1e-9 2.5E3 1_000.5");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(
+            without_spans(tokens),
+            vec![
+                Token::new(TokenType::Float, String::from("-0.5"), 0),
+                Token::new(TokenType::Float, String::from("3.14"), 0),
+                Token::new(TokenType::Float, String::from("1e-9"), 0),
+                Token::new(TokenType::Float, String::from("2500.0"), 0),
+                Token::new(TokenType::Float, String::from("1000.5"), 0),
+                Token::new(TokenType::EOF, String::from(""), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn float_keywords_and_ranges_test() {
+        let input = String::from("float32 float64 0..10 a.b");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(
+            without_spans(tokens),
+            vec![
+                Token::new(TokenType::Keyword, String::from("float32"), 0),
+                Token::new(TokenType::Keyword, String::from("float64"), 0),
+                Token::new(TokenType::Number, String::from("0"), 0),
+                Token::new(TokenType::Dot, String::from("."), 0),
+                Token::new(TokenType::Dot, String::from("."), 0),
+                Token::new(TokenType::Number, String::from("10"), 0),
+                Token::new(TokenType::Identifier, String::from("a"), 0),
+                Token::new(TokenType::Dot, String::from("."), 0),
+                Token::new(TokenType::Identifier, String::from("b"), 0),
+                Token::new(TokenType::EOF, String::from(""), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn token_spans_test() {
+        let input = String::from("int32 a = -5;\nb - c;");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(TokenType::Keyword, String::from("int32"), 0)
+                    .with_span(Span::new(0, 0, 5)),
+                Token::new(TokenType::Identifier, String::from("a"), 0)
+                    .with_span(Span::new(0, 6, 7)),
+                Token::new(TokenType::Equal, String::from("="), 0).with_span(Span::new(0, 8, 9)),
+                Token::new(TokenType::Number, String::from("-5"), 0)
+                    .with_span(Span::new(0, 10, 12)),
+                Token::new(TokenType::Semicolon, String::from(";"), 0)
+                    .with_span(Span::new(0, 12, 13)),
+                Token::new(TokenType::Identifier, String::from("b"), 1)
+                    .with_span(Span::new(0, 14, 15)),
+                Token::new(TokenType::Minus, String::from("-"), 1).with_span(Span::new(0, 16, 17)),
+                Token::new(TokenType::Identifier, String::from("c"), 1)
+                    .with_span(Span::new(0, 18, 19)),
+                Token::new(TokenType::Semicolon, String::from(";"), 1)
+                    .with_span(Span::new(0, 19, 20)),
+                Token::new(TokenType::EOF, String::from(""), 1).with_span(Span::new(0, 20, 20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn multibyte_token_spans_test() {
+        let input = String::from("\"привет\" x");
+        let mut lexer = Lexer::new(input, "tests".to_string()).with_file_id(3);
+
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[0].span, Span::new(3, 0, 14));
+        assert_eq!(tokens[1].span, Span::new(3, 15, 16));
+        assert_eq!(tokens[2].span, Span::new(3, 16, 16));
+    }
+
+    #[test]
+    fn span_location_test() {
+        let source = "int32 a = 5;\nprint(a);";
+
+        assert_eq!(Span::new(0, 0, 5).location(source), (0, 0));
+        assert_eq!(Span::new(0, 19, 20).location(source), (1, 6));
+        assert_eq!(Span::new(0, 22, 22).location(source), (1, 9));
+    }
+
+    #[test]
+    fn span_underline_test() {
+        let source = "int32 a = 5;\nprint(a);";
+
+        assert_eq!(Span::new(0, 6, 7).underline(source), "      ^");
+        assert_eq!(Span::new(0, 13, 21).underline(source), "^^^^^^^^");
+        assert_eq!(Span::new(0, 10, 20).underline(source), "          ^^");
+        assert_eq!(Span::new(0, 22, 22).underline(source), "         ^");
+    }
+
+    #[test]
+    fn span_to_test() {
+        let left = Span::new(0, 4, 8);
+        let right = Span::new(0, 10, 15);
+
+        assert_eq!(left.to(right), Span::new(0, 4, 15));
+        assert_eq!(right.to(left), Span::new(0, 4, 15));
+        assert_eq!(left.len(), 4);
+        assert!(Span::default().is_empty());
+    }
+
+    #[test]
+    fn line_comments_test() {
+        let input =
+            String::from("a / b; // comment \"with\" quotes\nstr c = \"http://x\"; ////\n'/'");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(
+            without_spans(tokens),
+            vec![
+                Token::new(TokenType::Identifier, String::from("a"), 0),
+                Token::new(TokenType::Divide, String::from("/"), 0),
+                Token::new(TokenType::Identifier, String::from("b"), 0),
+                Token::new(TokenType::Semicolon, String::from(";"), 0),
+                Token::new(TokenType::Keyword, String::from("str"), 1),
+                Token::new(TokenType::Identifier, String::from("c"), 1),
+                Token::new(TokenType::Equal, String::from("="), 1),
+                Token::new(TokenType::String, String::from("http://x"), 1),
+                Token::new(TokenType::Semicolon, String::from(";"), 1),
+                Token::new(TokenType::Char, String::from("/"), 2),
+                Token::new(TokenType::EOF, String::from(""), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_comments_test() {
+        let input = String::from("a /* one /* two */\n still comment */ b /**/ c");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(
+            without_spans(tokens),
+            vec![
+                Token::new(TokenType::Identifier, String::from("a"), 0),
+                Token::new(TokenType::Identifier, String::from("b"), 1),
+                Token::new(TokenType::Identifier, String::from("c"), 1),
+                Token::new(TokenType::EOF, String::from(""), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn unterminated_block_comment_test() {
+        let input = String::from("a /* /* */\nb");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let result = lexer.tokenize();
+
+        assert!(result
+            .unwrap_err()
+            .format_all()
+            .contains("Unterminated block comment found!"));
+    }
+
+    #[test]
+    fn doc_comments_test() {
+        let input = String::from("/// Adds numbers\n///\ndefine");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(TokenType::DocComment, String::from("Adds numbers"), 0)
+                    .with_span(Span::new(0, 0, 16)),
+                Token::new(TokenType::DocComment, String::new(), 1).with_span(Span::new(0, 17, 20)),
+                Token::new(TokenType::Keyword, String::from("define"), 2)
+                    .with_span(Span::new(0, 21, 27)),
+                Token::new(TokenType::EOF, String::from(""), 2).with_span(Span::new(0, 27, 27)),
+            ]
+        );
+    }
+
+    #[test]
+    fn compound_assignment_operators_test() {
+        let input = String::from("a %= 2 % b; c <<= 1; d &= e; f >>= 1; g |= 1; h ^= 1;");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let tokens = lexer.tokenize().unwrap();
+        let operators: Vec<TokenType> = tokens
+            .iter()
+            .map(|token| token.token_type)
+            .filter(|token_type| {
+                !matches!(
+                    token_type,
+                    TokenType::Identifier
+                        | TokenType::Number
+                        | TokenType::Semicolon
+                        | TokenType::EOF
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            operators,
+            vec![
+                TokenType::Modulo,
+                TokenType::Equal,
+                TokenType::Modulo,
+                TokenType::LShift,
+                TokenType::Equal,
+                TokenType::Ampersand,
+                TokenType::Equal,
+                TokenType::RShift,
+                TokenType::Equal,
+                TokenType::Verbar,
+                TokenType::Equal,
+                TokenType::Xor,
+                TokenType::Equal,
+            ]
+        );
+    }
+
+    #[test]
+    fn match_arms_test() {
+        let input = String::from("_ => _tmp == a");
+        let mut lexer = Lexer::new(input, "tests".to_string());
+
+        let result = lexer.tokenize().unwrap();
+
+        assert_eq!(
+            without_spans(result),
+            vec![
+                Token::new(TokenType::Identifier, String::from("_"), 0),
+                Token::new(TokenType::Arrow, String::from("=>"), 0),
+                Token::new(TokenType::Identifier, String::from("_tmp"), 0),
+                Token::new(TokenType::Eq, String::from("=="), 0),
+                Token::new(TokenType::Identifier, String::from("a"), 0),
+                Token::new(TokenType::EOF, String::from(""), 0),
+            ]
+        );
+    }
 }
@@ -0,0 +1,70 @@
+// Toy Programming Language | by mealet
+// https://github.com/mealet/tpl-lang
+// =========================================
+// Project licensed under the BSD-3 LICENSE.
+// Check the `LICENSE` file to more info.
+
+// NOTE: `start` and `end` are byte offsets in the source (`end` is exclusive),
+// `file_id` tells which source they belong to (0 is the main file)
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct Span {
+    pub file_id: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(file_id: usize, start: usize, end: usize) -> Self {
+        Span {
+            file_id,
+            start,
+            end,
+        }
+    }
+
+    // span which covers both `self` and `other`
+    pub fn to(&self, other: Span) -> Span {
+        Span::new(
+            self.file_id,
+            self.start.min(other.start),
+            self.end.max(other.end),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // zero-based line and column (in chars) of the span start
+    pub fn location(&self, source: &str) -> (usize, usize) {
+        let start = self.start.min(source.len());
+        let before = source.get(..start).unwrap_or_default();
+        let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+
+        (
+            before.matches('\n').count(),
+            before[line_start..].chars().count(),
+        )
+    }
+
+    // `^^^` marks under the span (only the first line of it is marked)
+    pub fn underline(&self, source: &str) -> String {
+        let (_, column) = self.location(source);
+
+        let marked = source
+            .get(self.start.min(source.len())..self.end.min(source.len()))
+            .unwrap_or_default()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count();
+
+        format!("{}{}", " ".repeat(column), "^".repeat(marked.max(1)))
+    }
+}
@@ -4,13 +4,14 @@
 // Project licensed under the BSD-3 LICENSE.
 // Check the `LICENSE` file to more info.
 
-use crate::token_type::TokenType;
+use crate::{span::Span, token_type::TokenType};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Token {
     pub value: String,
     pub token_type: TokenType,
     pub line: usize,
+    pub span: Span,
 }
 
 impl Token {
@@ -19,6 +20,12 @@ impl Token {
             value,
             token_type,
             line,
+            span: Span::default(),
         }
     }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
 }
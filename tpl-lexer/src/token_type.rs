@@ -9,6 +9,7 @@ pub enum TokenType {
     Identifier, // abc
 
     Number,  // 123
+    Float,   // 3.14
     String,  // "asd"
     Char,    // 'a'
     Boolean, // true/false
@@ -18,10 +19,14 @@ pub enum TokenType {
     Minus,    // -
     Multiply, // *
     Divide,   // /
+    Modulo,   // %
     Not,      // !
+    Tilde,    // ~
 
     Lt,  // <
     Bt,  // >
+    Le,  // <=
+    Be,  // >=
     Eq,  // ==
     Ne,  // !=
     Or,  // ||
@@ -33,6 +38,7 @@ pub enum TokenType {
     Verbar,      // |
     Dot,         // .
     Comma,       // ,
+    Colon,       // :
     Quote,       // "
     SingleQuote, // '
 
@@ -40,6 +46,8 @@ pub enum TokenType {
     RShift, // >>
     Xor,    // ^
 
+    Arrow, // =>
+
     LParen, // (
     RParen, // )
 
@@ -51,6 +59,7 @@ pub enum TokenType {
 
     Function,
     Keyword,
+    DocComment, // /// text
 
     EOF,
 }
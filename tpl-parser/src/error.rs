@@ -5,6 +5,7 @@
 // Check the `LICENSE` file to more info.
 
 use colored::Colorize;
+use tpl_lexer::span::Span;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(unused)]
@@ -15,6 +16,9 @@ pub struct ParseError {
     line: String,
     line_number: usize,
     position: usize,
+
+    span: Span,
+    underline: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -81,9 +85,21 @@ impl ParseError {
             line,
             line_number,
             position,
+            span: Span::default(),
+            underline: String::new(),
         }
     }
 
+    pub fn with_span(mut self, span: Span, source: &str) -> Self {
+        self.underline = span.underline(source);
+        self.span = span;
+        self
+    }
+
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
+
     pub fn get_description(&self) -> String {
         self.description.clone()
     }
@@ -96,7 +112,7 @@ impl ParseError {
         let line_number_length = self.line_number.to_string().len();
         let filename_fmt = format!("--> {}", self.filename).cyan();
         let lines_fmt = format!(
-            "{}{}\n {} {} {}\n{}{}",
+            "{}{}\n {} {} {}\n{}{} {}",
             // first line
             " ".repeat(line_number_length + 2),
             "|".cyan(),
@@ -104,9 +120,10 @@ impl ParseError {
             self.line_number,
             "|".cyan(),
             self.line,
-            // last line
+            // last line (with marked span)
             " ".repeat(line_number_length + 2),
             "|".cyan(),
+            self.underline.red(),
         );
 
         format!(
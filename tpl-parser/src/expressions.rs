@@ -4,11 +4,13 @@
 // Project licensed under the BSD-3 LICENSE.
 // Check the `LICENSE` file to more info.
 
-// NOTE: `line` field added for error handling on IR stage
+// NOTE: `line` field added for error handling on IR stage,
+// `span` marks the exact source range of the node
 
 use crate::{statements::Statements, value::Value};
+use tpl_lexer::span::Span;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 #[allow(unused)]
 pub enum Expressions {
     Binary {
@@ -16,62 +18,117 @@ pub enum Expressions {
         lhs: Box<Expressions>,
         rhs: Box<Expressions>,
         line: usize,
+        span: Span,
     },
     Boolean {
         operand: String,
         lhs: Box<Expressions>,
         rhs: Box<Expressions>,
         line: usize,
+        span: Span,
     },
     Bitwise {
         operand: String,
         lhs: Box<Expressions>,
         rhs: Box<Expressions>,
         line: usize,
+        span: Span,
+    },
+    Unary {
+        operand: String, // `-`, `!` or `~`
+        object: Box<Expressions>,
+        line: usize,
+        span: Span,
     },
 
     Argument {
         name: String,
         datatype: String,
+        span: Span,
     },
     SubElement {
         parent: Box<Expressions>,
         child: Box<Expressions>,
         line: usize,
+        span: Span,
     },
 
     Call {
         function_name: String,
         arguments: Vec<Expressions>,
         line: usize,
+        span: Span,
     },
     Lambda {
         arguments: Vec<(String, String)>,
         statements: Vec<Statements>,
         ftype: String,
         line: usize,
+        span: Span,
     },
 
     Reference {
         object: Box<Expressions>,
         line: usize,
+        span: Span,
     },
     Dereference {
         object: Box<Expressions>,
         line: usize,
+        span: Span,
     },
 
     Array {
         values: Vec<Expressions>,
         len: usize,
         line: usize,
+        span: Span,
     },
     Slice {
         object: Box<Expressions>,
         index: Box<Expressions>,
         line: usize,
+        span: Span,
+    },
+
+    Range {
+        start: Box<Expressions>,
+        end: Box<Expressions>,
+        inclusive: bool, // `..=`
+        line: usize,
+        span: Span,
     },
 
-    Value(Value),
+    Struct {
+        name: String,
+        fields: Vec<(String, Expressions)>, // ("x", value)
+        line: usize,
+        span: Span,
+    },
+
+    Value(Value, Span),
     None,
 }
+
+impl Expressions {
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Expressions::Binary { span, .. }
+            | Expressions::Boolean { span, .. }
+            | Expressions::Bitwise { span, .. }
+            | Expressions::Unary { span, .. }
+            | Expressions::Argument { span, .. }
+            | Expressions::SubElement { span, .. }
+            | Expressions::Call { span, .. }
+            | Expressions::Lambda { span, .. }
+            | Expressions::Reference { span, .. }
+            | Expressions::Dereference { span, .. }
+            | Expressions::Array { span, .. }
+            | Expressions::Slice { span, .. }
+            | Expressions::Range { span, .. }
+            | Expressions::Struct { span, .. }
+            | Expressions::Value(_, span) => Some(*span),
+            Expressions::None => None,
+        }
+    }
+}
@@ -10,7 +10,7 @@ pub mod statements;
 pub mod value;
 
 use error::ParseErrorHandler;
-use tpl_lexer::{token::Token, token_type::TokenType};
+use tpl_lexer::{span::Span, token::Token, token_type::TokenType};
 
 use expressions::Expressions;
 use statements::Statements;
@@ -18,19 +18,23 @@ use value::Value;
 
 // globals
 
-static DATATYPES: [&str; 12] = [
-    "int8", "int16", "int32", "int64", "int128", "str", "char", "bool", "auto", "void", "fn", "FILE"
+static DATATYPES: [&str; 16] = [
+    "int8", "int16", "int32", "int64", "int128", "float32", "float64", "str", "char", "bool",
+    "auto", "void", "fn", "FILE", "list", "map",
 ];
-static BINARY_OPERATORS: [TokenType; 4] = [
+static BINARY_OPERATORS: [TokenType; 5] = [
     TokenType::Plus,     // +
     TokenType::Minus,    // -
     TokenType::Divide,   // /
     TokenType::Multiply, // *
+    TokenType::Modulo,   // %
 ];
 
-static BOOLEAN_OPERATORS: [TokenType; 6] = [
+static BOOLEAN_OPERATORS: [TokenType; 8] = [
     TokenType::Lt,  // <
     TokenType::Bt,  // >
+    TokenType::Le,  // <=
+    TokenType::Be,  // >=
     TokenType::Eq,  // ==
     TokenType::Ne,  // !
     TokenType::Or,  // ||
@@ -45,8 +49,41 @@ static BITWISE_OPERATORS: [TokenType; 5] = [
     TokenType::Xor,       // ^
 ];
 
-static PRIORITY_BINARY_OPERATORS: [TokenType; 2] = [TokenType::Multiply, TokenType::Divide];
-static PRIORITY_BOOLEAN_OPERATORS: [TokenType; 2] = [TokenType::Or, TokenType::And];
+// NOTE: Operators precedence table (higher number binds tighter).
+// Every binary operator is left-associative: `a - b - c` is `(a - b) - c`.
+//
+// | precedence | operators                    | expression |
+// |------------|------------------------------|------------|
+// | 1          | `||`                         | Boolean    |
+// | 2          | `&&`                         | Boolean    |
+// | 3          | `==` `!=` `<` `>` `<=` `>=`  | Boolean    |
+// | 4          | `|`                          | Bitwise    |
+// | 5          | `^`                          | Bitwise    |
+// | 6          | `&`                          | Bitwise    |
+// | 7          | `<<` `>>`                    | Bitwise    |
+// | 8          | `+` `-`                      | Binary     |
+// | 9          | `*` `/` `%`                  | Binary     |
+
+static OPERATORS_PRECEDENCE: [(TokenType, u8); 18] = [
+    (TokenType::Or, 1),
+    (TokenType::And, 2),
+    (TokenType::Eq, 3),
+    (TokenType::Ne, 3),
+    (TokenType::Lt, 3),
+    (TokenType::Bt, 3),
+    (TokenType::Le, 3),
+    (TokenType::Be, 3),
+    (TokenType::Verbar, 4),
+    (TokenType::Xor, 5),
+    (TokenType::Ampersand, 6),
+    (TokenType::LShift, 7),
+    (TokenType::RShift, 7),
+    (TokenType::Plus, 8),
+    (TokenType::Minus, 8),
+    (TokenType::Multiply, 9),
+    (TokenType::Divide, 9),
+    (TokenType::Modulo, 9),
+];
 
 const END_STATEMENT: TokenType = TokenType::Semicolon;
 
@@ -70,10 +107,40 @@ impl Parser {
     // constructor
 
     pub fn new(tokens: Vec<Token>, filename: String, source: String) -> Self {
+        // doc comments are kept only where definition starts, other ones are plain comments
+        let mut filtered_tokens: Vec<Token> = Vec::with_capacity(tokens.len());
+
+        for (index, token) in tokens.iter().enumerate() {
+            if token.token_type == TokenType::DocComment {
+                let is_statement_start = filtered_tokens.last().is_none_or(|previous| {
+                    matches!(
+                        previous.token_type,
+                        END_STATEMENT
+                            | TokenType::LBrace
+                            | TokenType::RBrace
+                            | TokenType::DocComment
+                    )
+                });
+                let is_definition_next = tokens[index + 1..]
+                    .iter()
+                    .find(|next| next.token_type != TokenType::DocComment)
+                    .is_some_and(|next| {
+                        next.token_type == TokenType::Keyword
+                            && matches!(next.value.as_str(), "define" | "struct" | "enum")
+                    });
+
+                if !is_statement_start || !is_definition_next {
+                    continue;
+                }
+            }
+
+            filtered_tokens.push(token.clone());
+        }
+
         Parser {
             filename,
             source,
-            tokens,
+            tokens: filtered_tokens,
             position: 0,
             errors: ParseErrorHandler::new(),
             eof: false,
@@ -83,18 +150,19 @@ impl Parser {
     // error
 
     fn error<T: std::fmt::Display>(&mut self, description: T) {
-        let source_clone = self.source.clone();
-        let source_lines: Vec<&str> = source_clone.lines().collect();
-
-        let current_line = self.current().line;
-
-        self.errors.attach(error::ParseError::new(
-            self.filename.clone(),
-            description.to_string(),
-            source_lines[current_line].to_string(),
-            current_line,
-            self.position,
-        ));
+        let current = self.current();
+        let source_line = self.source.lines().nth(current.line).unwrap_or_default();
+
+        self.errors.attach(
+            error::ParseError::new(
+                self.filename.clone(),
+                description.to_string(),
+                source_line.to_string(),
+                current.line,
+                self.position,
+            )
+            .with_span(current.span, &self.source),
+        );
 
         // skipping whole statement
         while !self.expect(END_STATEMENT) {
@@ -122,6 +190,19 @@ impl Parser {
         self.current().token_type == expected
     }
 
+    fn lookahead(&self, step: usize) -> TokenType {
+        // type of token after current without moving
+        self.tokens
+            .get(self.position + step)
+            .map(|token| token.token_type)
+            .unwrap_or(TokenType::EOF)
+    }
+
+    fn is_datatype(&self) -> bool {
+        // builtin type keyword or user type (struct) name
+        self.expect(TokenType::Identifier) || DATATYPES.contains(&self.current().value.as_str())
+    }
+
     fn is_bitwise_operand(&self, token_type: TokenType) -> bool {
         BITWISE_OPERATORS.contains(&token_type)
     }
@@ -134,12 +215,25 @@ impl Parser {
         BOOLEAN_OPERATORS.contains(&token_type)
     }
 
-    fn is_priority_binary_operand(&self, operand: TokenType) -> bool {
-        PRIORITY_BINARY_OPERATORS.contains(&operand)
+    fn operator_precedence(&self, operand: TokenType) -> Option<u8> {
+        OPERATORS_PRECEDENCE
+            .iter()
+            .find(|(token_type, _)| *token_type == operand)
+            .map(|(_, precedence)| *precedence)
     }
 
-    fn is_priority_boolean_operand(&self, operand: TokenType) -> bool {
-        PRIORITY_BOOLEAN_OPERATORS.contains(&operand)
+    fn span_from(&self, start: Span) -> Span {
+        // from `start` to the last consumed token (trailing semicolons are skipped)
+        let last_token = self.tokens[..self.position]
+            .iter()
+            .rev()
+            .take_while(|token| token.span.start >= start.start)
+            .find(|token| token.token_type != END_STATEMENT);
+
+        match last_token {
+            Some(token) => start.to(token.span),
+            None => start,
+        }
     }
 
     fn skip_eos(&mut self) {
@@ -190,6 +284,29 @@ impl Parser {
                         // function definition
                         self.define_statement()
                     }
+                    "struct" => {
+                        // structure definition
+                        self.struct_statement()
+                    }
+                    "enum" => {
+                        // enumeration (tagged union) definition
+                        self.enum_statement()
+                    }
+                    "match" => {
+                        // matching enumeration variants
+                        self.match_statement()
+                    }
+                    "switch" => {
+                        // `switch` construction on integers and chars
+                        self.switch_statement()
+                    }
+                    "case" | "default" => {
+                        self.error(format!(
+                            "Unexpected `{}` usage. Please use it in `switch` construction!",
+                            current.value
+                        ));
+                        Statements::None
+                    }
                     "return" => {
                         // returning value
                         self.return_statement()
@@ -199,7 +316,19 @@ impl Parser {
                         // `break` keyword
                         let _ = self.next();
                         self.skip_eos();
-                        Statements::BreakStatement { line: current.line }
+                        Statements::BreakStatement {
+                            line: current.line,
+                            span: current.span,
+                        }
+                    }
+                    "continue" => {
+                        // `continue` keyword
+                        let _ = self.next();
+                        self.skip_eos();
+                        Statements::ContinueStatement {
+                            line: current.line,
+                            span: current.span,
+                        }
                     }
                     _ => Statements::None,
                 }
@@ -217,17 +346,14 @@ impl Parser {
                                 identifier,
                                 value,
                                 line,
+                                span,
                             } => Statements::DerefAssignStatement {
                                 identifier,
                                 value,
                                 line,
+                                span: current.span.to(span),
                             },
-                            Statements::BinaryAssignStatement {
-                                identifier,
-                                operand,
-                                value,
-                                line,
-                            } => {
+                            Statements::BinaryAssignStatement { .. } => {
                                 // i'll implement it in future
                                 self.error("Binary Assignment isn't supported for dereference!");
                                 Statements::None
@@ -244,29 +370,60 @@ impl Parser {
                     }
                 }
             }
-            TokenType::Function => self.function_call_statement(current.value),
+            TokenType::Function => self.function_call_statement(current.value, current.span),
+            TokenType::Identifier
+                if matches!(
+                    (self.lookahead(1), self.lookahead(2)),
+                    (TokenType::Identifier, _) | (TokenType::Multiply, TokenType::Identifier)
+                ) =>
+            {
+                // annotation with user type: `Point p` or `Point* p`
+                self.annotation_statement()
+            }
             TokenType::Identifier => {
                 let next = self.next();
 
                 match next.token_type {
-                    TokenType::Equal => self.assign_statement(current.value),
+                    TokenType::Equal => self.assign_statement(current.value, current.span),
                     TokenType::Dot => {
                         // subelement
                         let sub_expr = self.subelement_expression(
-                            Expressions::Value(Value::Identifier(current.value)),
+                            Expressions::Value(Value::Identifier(current.value), current.span),
                             TokenType::Dot,
                         );
 
+                        if self.expect(TokenType::Equal) {
+                            // field assignment: `a.b = value`
+                            let _ = self.next();
+
+                            let value = Box::new(self.expression());
+                            let span = self.span_from(current.span);
+
+                            self.skip_eos();
+
+                            return Statements::FieldAssignStatement {
+                                target: Box::new(sub_expr),
+                                value,
+                                line: current.line,
+                                span,
+                            };
+                        }
+
+                        self.skip_eos();
                         Statements::Expression(sub_expr)
                     }
-                    TokenType::LParen => self.call_statement(current.value),
-                    TokenType::LBrack => self.slice_assign_statement(current.value),
+                    TokenType::LParen => self.call_statement(current.value, current.span),
+                    TokenType::LBrack => self.slice_assign_statement(current.value, current.span),
 
                     _ if BINARY_OPERATORS.contains(&next.token_type) => {
                         match self.next().token_type {
                             TokenType::Equal => {
                                 // parsing binary assignment
-                                self.binary_assign_statement(current.value, next.value)
+                                self.binary_assign_statement(
+                                    current.value,
+                                    next.value,
+                                    current.span,
+                                )
                             }
                             TokenType::Plus | TokenType::Minus => {
                                 // getting operands
@@ -282,6 +439,8 @@ impl Parser {
                                     return Statements::None;
                                 }
 
+                                let operator_span = next.span.to(self.current().span);
+
                                 let _ = self.next();
                                 self.skip_eos();
 
@@ -290,8 +449,12 @@ impl Parser {
                                 Statements::BinaryAssignStatement {
                                     identifier: current.value,
                                     operand: first_operand,
-                                    value: Box::new(Expressions::Value(Value::Integer(1))),
+                                    value: Box::new(Expressions::Value(
+                                        Value::Integer(1),
+                                        operator_span,
+                                    )),
                                     line: current.line,
+                                    span: current.span.to(operator_span),
                                 }
                             }
                             _ => {
@@ -300,9 +463,19 @@ impl Parser {
                             }
                         }
                     }
-                    END_STATEMENT => {
-                        Statements::Expression(Expressions::Value(Value::Identifier(current.value)))
+                    _ if BITWISE_OPERATORS.contains(&next.token_type) => {
+                        // only compound assignment (`a <<= 1`) can start statement
+                        if self.next().token_type != TokenType::Equal {
+                            self.error("Unexpected Bitwise Operation in statement found!");
+                            return Statements::None;
+                        }
+
+                        self.binary_assign_statement(current.value, next.value, current.span)
                     }
+                    END_STATEMENT => Statements::Expression(Expressions::Value(
+                        Value::Identifier(current.value),
+                        current.span,
+                    )),
                     _ => {
                         self.error("Unexpected expression/statement after identifier");
                         self.next();
@@ -310,6 +483,35 @@ impl Parser {
                     }
                 }
             }
+            TokenType::DocComment => {
+                // collecting doc lines for the next definition
+                let mut docs = Vec::new();
+
+                while self.expect(TokenType::DocComment) {
+                    docs.push(self.current().value);
+                    let _ = self.next();
+                }
+
+                let mut statement = self.statement();
+
+                match statement {
+                    Statements::FunctionDefineStatement {
+                        docs: ref mut definition_docs,
+                        ..
+                    }
+                    | Statements::StructDefineStatement {
+                        docs: ref mut definition_docs,
+                        ..
+                    }
+                    | Statements::EnumDefineStatement {
+                        docs: ref mut definition_docs,
+                        ..
+                    } => *definition_docs = docs,
+                    _ => {}
+                }
+
+                statement
+            }
             TokenType::EOF => {
                 self.eof = true;
                 Statements::None
@@ -324,45 +526,93 @@ impl Parser {
 
         match current.token_type {
             TokenType::Number => {
-                output = Expressions::Value(Value::Integer(current.value.trim().parse().unwrap()))
+                output = Expressions::Value(
+                    Value::Integer(current.value.trim().parse().unwrap()),
+                    current.span,
+                )
+            }
+            TokenType::Float => {
+                output = Expressions::Value(
+                    Value::Float(current.value.trim().parse().unwrap()),
+                    current.span,
+                )
+            }
+            TokenType::String => {
+                output = Expressions::Value(Value::String(current.value), current.span)
             }
-            TokenType::String => output = Expressions::Value(Value::String(current.value)),
             TokenType::Char => {
                 let ch = current.value.chars().nth(0).unwrap();
-                output = Expressions::Value(Value::Char(ch));
+                output = Expressions::Value(Value::Char(ch), current.span);
             }
             TokenType::Boolean => {
-                output = Expressions::Value(Value::Boolean(current.value == "true"))
+                output = Expressions::Value(Value::Boolean(current.value == "true"), current.span)
             }
             TokenType::Ref => {
                 let _ = self.next();
+                let object = Box::new(self.term());
 
                 return Expressions::Reference {
-                    object: Box::new(self.term()),
+                    object,
                     line: current.line,
+                    span: self.span_from(current.span),
                 };
             }
             TokenType::Multiply => {
                 let _ = self.next();
+                let object = Box::new(self.term());
+
                 return Expressions::Dereference {
-                    object: Box::new(self.term()),
+                    object,
                     line: current.line,
+                    span: self.span_from(current.span),
                 };
             }
+            TokenType::Minus | TokenType::Not | TokenType::Tilde => {
+                // prefix operator binds tighter than any binary one
+                let _ = self.next();
+                let object = Box::new(self.postfix_expression());
+
+                return Expressions::Unary {
+                    operand: current.value,
+                    object,
+                    line: current.line,
+                    span: self.span_from(current.span),
+                };
+            }
+            TokenType::LParen => {
+                // parenthesized expression
+                let _ = self.next();
+                let expression = self.operator_expression(0);
+
+                if !self.expect(TokenType::RParen) {
+                    self.error("Parentheses in expression is not closed!");
+                    return Expressions::None;
+                }
+
+                let _ = self.next();
+                return expression;
+            }
             TokenType::Identifier => {
-                output = Expressions::Value(Value::Identifier(current.value.clone()));
+                output = Expressions::Value(Value::Identifier(current.value.clone()), current.span);
 
                 let next = self.next();
 
                 match next.token_type {
                     TokenType::LParen => {
                         // calling function
-                        return self.call_expression(current.value);
+                        return self.call_expression(current.value, current.span);
                     }
                     TokenType::LBrack => {
                         // slicing from object
                         return self.slice_expression(output);
                     }
+                    TokenType::LBrace
+                        if self.lookahead(1) == TokenType::Identifier
+                            && self.lookahead(2) == TokenType::Colon =>
+                    {
+                        // structure literal
+                        return self.struct_expression(current.value, current.span);
+                    }
                     _ => {}
                 }
 
@@ -375,7 +625,12 @@ impl Parser {
                     self.expressions_enum(TokenType::LBrack, TokenType::RBrack, TokenType::Comma);
                 let len = values.len();
 
-                return Expressions::Array { values, len, line };
+                return Expressions::Array {
+                    values,
+                    len,
+                    line,
+                    span: self.span_from(current.span),
+                };
             }
             _ if DATATYPES.contains(&current.value.as_str()) => {
                 // parsing argument
@@ -383,7 +638,7 @@ impl Parser {
                 let identifier = self.next();
 
                 if !self.expect(TokenType::Identifier) {
-                    return Expressions::Value(Value::Keyword(datatype));
+                    return Expressions::Value(Value::Keyword(datatype), current.span);
                 }
 
                 let _ = self.next();
@@ -391,13 +646,14 @@ impl Parser {
                 return Expressions::Argument {
                     name: identifier.value,
                     datatype,
+                    span: current.span.to(identifier.span),
                 };
             }
             TokenType::Function => {
-                return self.call_expression(current.value);
+                return self.call_expression(current.value, current.span);
             }
             TokenType::Keyword => {
-                output = Expressions::Value(Value::Keyword(current.value));
+                output = Expressions::Value(Value::Keyword(current.value), current.span);
             }
             _ => {
                 self.error(format!(
@@ -414,20 +670,72 @@ impl Parser {
     }
 
     fn expression(&mut self) -> Expressions {
+        let node = self.operator_expression(0);
+
+        // semicolon after expression ends the statement
+        if self.expect(END_STATEMENT) {
+            let _ = self.next();
+        }
+
+        node
+    }
+
+    // expressions
+
+    fn operator_expression(&mut self, min_precedence: u8) -> Expressions {
+        // precedence climbing: operators with lower precedence than `min_precedence`
+        // are left for the caller, so equal ones are grouped from the left
+
+        let mut node = self.postfix_expression();
+
+        while let Some(precedence) = self.operator_precedence(self.current().token_type) {
+            if precedence < min_precedence {
+                break;
+            }
+
+            let operator = self.current();
+            let start = node.span().unwrap_or(operator.span);
+
+            let _ = self.next();
+
+            let lhs = Box::new(node);
+            let rhs = Box::new(self.operator_expression(precedence + 1));
+            let span = self.span_from(start);
+
+            node = match operator.token_type {
+                op if self.is_binary_operand(op) => Expressions::Binary {
+                    operand: operator.value,
+                    lhs,
+                    rhs,
+                    line: operator.line,
+                    span,
+                },
+                op if self.is_bitwise_operand(op) => Expressions::Bitwise {
+                    operand: operator.value,
+                    lhs,
+                    rhs,
+                    line: operator.line,
+                    span,
+                },
+                _ => Expressions::Boolean {
+                    operand: operator.value,
+                    lhs,
+                    rhs,
+                    line: operator.line,
+                    span,
+                },
+            };
+        }
+
+        node
+    }
+
+    fn postfix_expression(&mut self) -> Expressions {
+        let start = self.current().span;
         let mut node = self.term();
         let current = self.current();
 
         match current.token_type {
-            _ if self.is_binary_operand(current.token_type) => {
-                node = self.binary_expression(node);
-            }
-            _ if self.is_boolean_operand(current.token_type) => {
-                node = self.boolean_expression(node);
-            }
-            _ if self.is_bitwise_operand(current.token_type) => {
-                node = self.bitwise_expression(node);
-            }
-
             TokenType::LBrack => {
                 let _ = self.next();
 
@@ -448,40 +756,27 @@ impl Parser {
                     object: Box::new(node),
                     index: Box::new(slice_index),
                     line: current.line,
+                    span: self.span_from(start),
+                };
+
+                // nested arrays: `a[1][2]`
+                if self.expect(TokenType::LBrack) {
+                    node = self.slice_expression(node);
                 }
             }
             TokenType::LParen => {
-                if let Expressions::Value(Value::Keyword(keyword)) = node.clone() {
+                if let Expressions::Value(Value::Keyword(keyword), _) = node.clone() {
                     if !DATATYPES.contains(&keyword.as_str()) {
                         self.error(format!("Unexpected keyword `{}` in expression", keyword));
                         return Expressions::None;
                     }
 
-                    let lambda_arguments = self.expressions_enum(
-                        TokenType::LParen,
-                        TokenType::RParen,
-                        TokenType::Comma,
-                    );
+                    let Some(arguments_tuples) = self.arguments_definition() else {
+                        return Expressions::None;
+                    };
                     let lambda_type = keyword;
                     let mut function_statements: Vec<Statements> = Vec::new();
 
-                    let mut arguments_tuples = Vec::new();
-
-                    // checking for right arguments definition
-                    if !lambda_arguments.is_empty() {
-                        for arg in lambda_arguments {
-                            match arg {
-                                Expressions::Argument { name, datatype } => {
-                                    arguments_tuples.push((name, datatype));
-                                }
-                                _ => {
-                                    self.error("All arguments in definition must be `type name` (example: `int32 a`)");
-                                    return Expressions::None;
-                                }
-                            }
-                        }
-                    }
-
                     if !self.expect(TokenType::LBrace) {
                         self.error("Expected block after lambda function definition!");
                         return Expressions::None;
@@ -506,167 +801,68 @@ impl Parser {
                         statements: function_statements,
                         ftype: lambda_type,
                         line: current.line,
+                        span: self.span_from(start),
                     };
                 }
 
                 self.error("Unexpected parentheses in expression found".to_string());
                 return Expressions::None;
             }
-            TokenType::Dot => {
+            // `..` is a range, not a subelement
+            TokenType::Dot if self.lookahead(1) != TokenType::Dot => {
                 node = self.subelement_expression(node, TokenType::Dot);
             }
-            END_STATEMENT => {
-                self.next();
-            }
             _ => {}
         }
 
         node
     }
 
-    // expressions
-
-    fn binary_expression(&mut self, node: Expressions) -> Expressions {
-        let current_token = self.current();
-        let current_line = current_token.line;
-
-        match current_token.token_type {
-            _ if self.is_binary_operand(current_token.token_type) => {
-                let _ = self.next();
-
-                let lhs = node;
-                let rhs = self.expression();
-
-                if self.is_priority_binary_operand(current_token.token_type) {
-                    let mut new_node = rhs.clone();
-                    let old_lhs = lhs.clone();
-
-                    if let Expressions::Binary {
-                        lhs,
-                        rhs,
-                        operand,
-                        line,
-                    } = new_node
-                    {
-                        let lhs_new = old_lhs;
-                        let rhs_new = lhs;
-
-                        // creating new expression
-
-                        let priority_node = Expressions::Binary {
-                            lhs: Box::new(lhs_new),
-                            rhs: rhs_new,
-                            operand: current_token.clone().value,
-                            line: current_line,
-                        };
-
-                        let output_node = Expressions::Binary {
-                            lhs: Box::new(priority_node),
-                            rhs,
-                            operand,
-                            line: current_line,
-                        };
-
-                        return output_node;
-                    }
-                }
+    fn range_expression(&mut self) -> Expressions {
+        // `start..end`, `start..=end` or any other expression
+        let line = self.current().line;
+        let range_start = self.operator_expression(0);
 
-                Expressions::Binary {
-                    operand: current_token.value,
-                    lhs: Box::new(lhs),
-                    rhs: Box::new(rhs),
-                    line: current_line,
-                }
-            }
-            _ => {
-                self.error("Unexpected token at binary expression!");
-                Expressions::None
-            }
+        if !(self.expect(TokenType::Dot) && self.lookahead(1) == TokenType::Dot) {
+            return range_start;
         }
-    }
-
-    fn bitwise_expression(&mut self, node: Expressions) -> Expressions {
-        let current_token = self.current();
-        let current_line = current_token.line;
-
-        match current_token.token_type {
-            ttype if self.is_bitwise_operand(ttype) => {
-                let _ = self.next();
 
-                let lhs = node;
-                let rhs = self.expression();
+        let _ = self.peek(2);
+        let inclusive = self.expect(TokenType::Equal);
 
-                Expressions::Bitwise {
-                    operand: current_token.value,
-                    lhs: Box::new(lhs),
-                    rhs: Box::new(rhs),
-                    line: current_line,
-                }
-            }
-            _ => {
-                self.error("Unexpected token at binary expression!");
-                Expressions::None
-            }
+        if inclusive {
+            let _ = self.next();
         }
-    }
-
-    fn boolean_expression(&mut self, node: Expressions) -> Expressions {
-        let current_token = self.current();
-        let current_line = current_token.line;
-
-        match current_token.token_type {
-            op if self.is_priority_boolean_operand(op) => node,
-            op if self.is_boolean_operand(op) => {
-                let _ = self.next();
-
-                let lhs = node;
-                let rhs = self.expression();
 
-                if self.is_priority_boolean_operand(self.current().token_type) {
-                    let operand = self.current().value;
-                    let lhs_node = Expressions::Boolean {
-                        operand: current_token.value.clone(),
-                        lhs: Box::new(lhs),
-                        rhs: Box::new(rhs),
-                        line: current_line,
-                    };
+        let range_end = self.operator_expression(0);
 
-                    let _ = self.next();
-                    let rhs_node = self.expression();
+        if let Expressions::None = range_end {
+            self.error("Expected end of range after `..`!");
+            return Expressions::None;
+        }
 
-                    return Expressions::Boolean {
-                        operand,
-                        lhs: Box::new(lhs_node),
-                        rhs: Box::new(rhs_node),
-                        line: current_line,
-                    };
-                }
+        let start = range_start.span().unwrap_or(self.current().span);
 
-                Expressions::Boolean {
-                    operand: current_token.value,
-                    lhs: Box::new(lhs),
-                    rhs: Box::new(rhs),
-                    line: current_line,
-                }
-            }
-            _ => {
-                self.error("Unexpected token at binary expression!");
-                Expressions::None
-            }
+        Expressions::Range {
+            start: Box::new(range_start),
+            end: Box::new(range_end),
+            inclusive,
+            line,
+            span: self.span_from(start),
         }
     }
 
-    fn call_expression(&mut self, function_name: String) -> Expressions {
+    fn call_expression(&mut self, function_name: String, start: Span) -> Expressions {
         let line = self.current().line;
 
         match self.current().token_type {
             TokenType::Identifier => {
                 let _ = self.next();
-                return self.call_expression(function_name);
+                return self.call_expression(function_name, start);
             }
             TokenType::Function => {
                 let _ = self.next();
-                return self.call_expression(function_name);
+                return self.call_expression(function_name, start);
             }
             TokenType::LParen => {}
             _ => {
@@ -679,16 +875,20 @@ impl Parser {
         let arguments =
             self.expressions_enum(TokenType::LParen, TokenType::RParen, TokenType::Comma);
 
+        let span = self.span_from(start);
         self.skip_eos();
 
         Expressions::Call {
             function_name,
             arguments,
             line,
+            span,
         }
     }
 
     fn slice_expression(&mut self, object: Expressions) -> Expressions {
+        let start = object.span().unwrap_or(self.current().span);
+
         if let TokenType::LBrack = self.current().token_type {
             let _ = self.next();
         }
@@ -704,23 +904,31 @@ impl Parser {
 
         let _ = self.next();
 
-        Expressions::Slice {
+        let slice = Expressions::Slice {
             object,
             index,
             line,
+            span: self.span_from(start),
+        };
+
+        // nested arrays: `a[1][2]`
+        if self.expect(TokenType::LBrack) {
+            return self.slice_expression(slice);
         }
+
+        slice
     }
 
     // statements
 
-    fn function_call_statement(&mut self, function_name: String) -> Statements {
+    fn function_call_statement(&mut self, function_name: String, start: Span) -> Statements {
         let mut current = self.current();
         let line = current.line;
 
         match current.token_type {
             TokenType::Function => {
                 current = self.next();
-                return self.function_call_statement(function_name);
+                return self.function_call_statement(function_name, start);
             }
             TokenType::LParen => {}
             _ => {
@@ -731,6 +939,7 @@ impl Parser {
 
         let arguments =
             self.expressions_enum(TokenType::LParen, TokenType::RParen, TokenType::Comma);
+        let span = self.span_from(start);
 
         if self.current().token_type == END_STATEMENT {
             let _ = self.next();
@@ -740,6 +949,7 @@ impl Parser {
             function_name,
             arguments,
             line,
+            span,
         }
     }
 
@@ -747,60 +957,113 @@ impl Parser {
         let line = self.current().line;
         let current = self.current();
 
-        if DATATYPES.contains(&current.value.as_str()) {
+        if self.is_datatype() {
             let mut datatype = self.current().value;
             let _ = self.next();
 
             match self.current().token_type {
                 TokenType::Lt => {
-                    // example: fn<int32>
+                    // example: fn<int32(int32)>, list<list<int32>> or map<str, int32>
                     let _ = self.next();
 
-                    if !self.expect(TokenType::Keyword) {
+                    if !self.is_datatype() {
                         self.error("Unexpected nested datatype found!");
 
                         return String::new();
                     }
 
-                    let subtype = self.parse_datatype();
+                    let mut subtype = self.parse_datatype();
+
+                    if datatype == "fn" && self.expect(TokenType::LParen) {
+                        // function signature: fn<int32(int32, str)>
+                        let _ = self.next();
+                        let mut arguments = Vec::new();
 
-                    if !self.expect(TokenType::Bt) {
-                        self.error("Wrong nested type definition! Must be like: fn<int32>");
+                        while !self.expect(TokenType::RParen) {
+                            if !self.is_datatype() {
+                                self.error("Unexpected argument type in function signature!");
 
-                        return String::new();
+                                return String::new();
+                            }
+
+                            arguments.push(self.parse_datatype());
+
+                            if self.expect(TokenType::Comma) {
+                                let _ = self.next();
+                            } else if !self.expect(TokenType::RParen) {
+                                self.error("Expected `,` or `)` in function signature!");
+
+                                return String::new();
+                            }
+                        }
+
+                        let _ = self.next();
+                        subtype = format!("{}({})", subtype, arguments.join(", "));
+                    } else if self.expect(TokenType::Comma) {
+                        let _ = self.next();
+
+                        if !self.is_datatype() {
+                            self.error("Unexpected nested datatype found!");
+
+                            return String::new();
+                        }
+
+                        subtype = format!("{}, {}", subtype, self.parse_datatype());
+                    }
+
+                    match self.current().token_type {
+                        TokenType::Bt => {
+                            let _ = self.next();
+                        }
+                        TokenType::RShift => {
+                            // `>>` closes two nested types, so the second `>` is left for parent
+                            let mut token = self.current();
+                            token.token_type = TokenType::Bt;
+                            token.value = String::from(">");
+                            token.span.start += 1;
+
+                            self.tokens[self.position] = token;
+                        }
+                        _ => {
+                            self.error("Wrong nested type definition! Must be like: fn<int32>");
+
+                            return String::new();
+                        }
                     }
 
-                    let _ = self.next();
                     datatype = format!("{}<{}>", datatype, subtype);
                 }
                 TokenType::LBrack => {
-                    // example: int32[] or int32[1]
+                    // example: int32[] or int32[1] or int32[3][4]
                     //                           ↑
                     //                    array's length
+                    // (empty brackets mean slice of any length)
 
-                    let mut array_len = String::from("auto");
-                    let _ = self.next();
+                    while self.expect(TokenType::LBrack) {
+                        let mut array_len = String::new();
+                        let _ = self.next();
 
-                    match self.current().token_type {
-                        TokenType::Number => {
-                            array_len = self.current().value;
-                            let _ = self.next();
+                        match self.current().token_type {
+                            TokenType::Number => {
+                                array_len = self.current().value;
+                                let _ = self.next();
+                            }
+                            TokenType::RBrack => {}
+                            _ => {
+                                self.error("Unexpected array annotation found!");
+                                return String::new();
+                            }
                         }
-                        TokenType::RBrack => {}
-                        _ => {
-                            self.error("Unexpected array annotation found!");
+
+                        if !self.expect(TokenType::RBrack) {
+                            self.error("Unexpected brackets end at annoation found!");
+                            let _ = self.next();
                             return String::new();
                         }
-                    }
 
-                    if !self.expect(TokenType::RBrack) {
-                        self.error("Unexpected brackets end at annoation found!");
                         let _ = self.next();
-                        return String::new();
+                        datatype = format!("{}[{}]", datatype, array_len);
                     }
-
-                    let _ = self.next();
-                    datatype = format!("{}[{}]", datatype, array_len);
                 }
                 _ => {}
             }
@@ -820,8 +1083,9 @@ impl Parser {
 
     fn annotation_statement(&mut self) -> Statements {
         let line = self.current().line;
+        let start = self.current().span;
 
-        if DATATYPES.contains(&self.current().value.as_str()) {
+        if self.is_datatype() {
             let mut datatype = self.parse_datatype();
 
             if !self.expect(TokenType::Identifier) {
@@ -836,6 +1100,7 @@ impl Parser {
                 TokenType::Equal => {
                     let _ = self.next();
                     let value = self.expression();
+                    let span = self.span_from(start);
 
                     self.skip_eos(); // skipping semicolon if it exists
 
@@ -844,9 +1109,11 @@ impl Parser {
                         datatype,
                         value: Some(Box::new(value)),
                         line,
+                        span,
                     }
                 }
                 END_STATEMENT => {
+                    let span = self.span_from(start);
                     self.skip_eos();
 
                     Statements::AnnotationStatement {
@@ -854,6 +1121,7 @@ impl Parser {
                         datatype,
                         value: None,
                         line,
+                        span,
                     }
                 }
                 _ => {
@@ -867,13 +1135,13 @@ impl Parser {
         }
     }
 
-    fn assign_statement(&mut self, identifier: String) -> Statements {
+    fn assign_statement(&mut self, identifier: String, start: Span) -> Statements {
         let line = self.current().line;
 
         match self.current().token_type {
             TokenType::Equal => {
                 self.next();
-                self.assign_statement(identifier)
+                self.assign_statement(identifier, start)
             }
             END_STATEMENT => {
                 self.error("Expressions expected in assign statement, but `;` found!");
@@ -883,31 +1151,47 @@ impl Parser {
                 identifier,
                 value: Box::new(self.expression()),
                 line,
+                span: self.span_from(start),
             },
         }
     }
 
-    fn slice_assign_statement(&mut self, identifier: String) -> Statements {
+    fn slice_assign_statement(&mut self, identifier: String, start: Span) -> Statements {
         let line = self.current().line;
+        let mut object = Expressions::Value(Value::Identifier(identifier), start);
 
-        match self.current().token_type {
-            TokenType::LBrack => {
-                let _ = self.next();
+        let index = loop {
+            match self.current().token_type {
+                TokenType::LBrack => {
+                    let _ = self.next();
+                }
+                _ => {
+                    self.error("Unexpected slice end found in statement!");
+                    return Statements::None;
+                }
             }
-            _ => {
+
+            let index = self.expression();
+
+            if !self.expect(TokenType::RBrack) {
                 self.error("Unexpected slice end found in statement!");
                 return Statements::None;
             }
-        }
 
-        let index = Box::new(self.expression());
+            let _ = self.next();
 
-        if !self.expect(TokenType::RBrack) {
-            self.error("Unexpected slice end found in statement!");
-            return Statements::None;
-        }
+            if !self.expect(TokenType::LBrack) {
+                break Box::new(index);
+            }
 
-        let _ = self.next();
+            // nested arrays: `a[1][2] = value`
+            object = Expressions::Slice {
+                object: Box::new(object),
+                index: Box::new(index),
+                line,
+                span: self.span_from(start),
+            };
+        };
 
         if !self.expect(TokenType::Equal) {
             self.error("Unexpected slice-assign statement found!");
@@ -916,24 +1200,31 @@ impl Parser {
 
         let _ = self.next();
         let value = Box::new(self.expression());
+        let span = self.span_from(start);
 
         self.skip_eos();
 
         Statements::SliceAssignStatement {
-            identifier,
+            object: Box::new(object),
             index,
             value,
             line,
+            span,
         }
     }
 
-    fn binary_assign_statement(&mut self, identifier: String, operand: String) -> Statements {
+    fn binary_assign_statement(
+        &mut self,
+        identifier: String,
+        operand: String,
+        start: Span,
+    ) -> Statements {
         let line = self.current().line;
 
         match self.current().token_type {
             TokenType::Equal => {
                 self.next();
-                self.binary_assign_statement(identifier, operand)
+                self.binary_assign_statement(identifier, operand, start)
             }
             END_STATEMENT => {
                 self.error("Expressions expected in binary assignment, but `;` found!");
@@ -944,17 +1235,18 @@ impl Parser {
                 operand,
                 value: Box::new(self.expression()),
                 line,
+                span: self.span_from(start),
             },
         }
     }
 
     fn if_statement(&mut self) -> Statements {
         let line = self.current().line;
+        let start = self.current().span;
 
         if self.current().token_type == TokenType::Keyword {
             // skipping keyword
             let _ = self.next();
-            return self.if_statement();
         }
 
         // parsing condition
@@ -1000,6 +1292,23 @@ impl Parser {
 
                 let _ = self.next();
 
+                // `else if` chain is nested `if` statement in `else` block
+                if self.current().token_type == TokenType::Keyword && self.current().value == "if" {
+                    let else_if = self.if_statement();
+
+                    if else_if == Statements::None {
+                        return Statements::None;
+                    }
+
+                    return Statements::IfStatement {
+                        condition,
+                        then_block: stmts,
+                        else_block: Some(vec![else_if]),
+                        line,
+                        span: self.span_from(start),
+                    };
+                }
+
                 // checking for opening new block
                 if !self.expect(TokenType::LBrace) {
                     self.error("New block expected after `else` keyword!");
@@ -1034,6 +1343,7 @@ impl Parser {
                     return Statements::None;
                 }
 
+                let span = self.span_from(start);
                 self.skip_eos();
 
                 Statements::IfStatement {
@@ -1041,9 +1351,11 @@ impl Parser {
                     then_block: stmts,
                     else_block: Some(else_stmts),
                     line,
+                    span,
                 }
             }
             _ => {
+                let span = self.span_from(start);
                 // skipping semicolon if we have
                 self.skip_eos();
                 // returning statement
@@ -1052,36 +1364,113 @@ impl Parser {
                     then_block: stmts,
                     else_block: None,
                     line,
+                    span,
                 }
             }
         }
     }
 
-    fn while_statement(&mut self) -> Statements {
+    fn switch_statement(&mut self) -> Statements {
         let line = self.current().line;
+        let start = self.current().span;
 
         if self.current().token_type == TokenType::Keyword {
             // skipping keyword
             let _ = self.next();
-            return self.while_statement();
         }
 
-        // parsing condition
-        let condition = self.expression();
+        let value = self.operator_expression(0);
 
-        // searching for opening block
-        if self.current().token_type != TokenType::LBrace {
-            self.error("New block expected after condition!");
+        if !self.expect(TokenType::LBrace) {
+            self.error("New block with cases expected after `switch` value!");
             return Statements::None;
         }
 
         let _ = self.next();
 
-        // parsing statements
-        let mut stmts = Vec::new();
+        // parsing `case 1, 2 { ... }` and `default { ... }` arms
+        let mut cases = Vec::new();
+        let mut default_block = None;
 
-        while self.current().token_type != TokenType::RBrace {
-            if self.current().token_type == TokenType::EOF {
+        while !self.expect(TokenType::RBrace) {
+            let current = self.current();
+
+            match (current.token_type, current.value.as_str()) {
+                (TokenType::Keyword, "case") => {
+                    let _ = self.next();
+                    let mut values = vec![self.operator_expression(0)];
+
+                    while self.expect(TokenType::Comma) {
+                        let _ = self.next();
+                        values.push(self.operator_expression(0));
+                    }
+
+                    let Some(block) = self.block_statements("case") else {
+                        return Statements::None;
+                    };
+
+                    cases.push((values, block));
+                }
+                (TokenType::Keyword, "default") => {
+                    if default_block.is_some() {
+                        self.error("Only one `default` arm can be in `switch` construction!");
+                        return Statements::None;
+                    }
+
+                    let _ = self.next();
+
+                    let Some(block) = self.block_statements("default") else {
+                        return Statements::None;
+                    };
+
+                    default_block = Some(block);
+                }
+                _ => {
+                    self.error("Expected `case` or `default` in `switch` block!");
+                    return Statements::None;
+                }
+            }
+        }
+
+        let _ = self.next();
+
+        let span = self.span_from(start);
+        self.skip_eos();
+
+        Statements::SwitchStatement {
+            value,
+            cases,
+            default_block,
+            line,
+            span,
+        }
+    }
+
+    fn while_statement(&mut self) -> Statements {
+        let line = self.current().line;
+        let start = self.current().span;
+
+        if self.current().token_type == TokenType::Keyword {
+            // skipping keyword
+            let _ = self.next();
+        }
+
+        // parsing condition
+        let condition = self.expression();
+
+        // searching for opening block
+        if self.current().token_type != TokenType::LBrace {
+            self.error("New block expected after condition!");
+            return Statements::None;
+        }
+
+        let _ = self.next();
+
+        // parsing statements
+        let mut stmts = Vec::new();
+
+        while self.current().token_type != TokenType::RBrace {
+            if self.current().token_type == TokenType::EOF {
                 self.error(
                     "Unexpected end-of-file in block after `while` statement. Please add '}'!",
                 );
@@ -1097,6 +1486,8 @@ impl Parser {
             let _ = self.next();
         }
 
+        let span = self.span_from(start);
+
         // skiping semicolon
         self.skip_eos();
 
@@ -1104,17 +1495,29 @@ impl Parser {
             condition,
             block: stmts,
             line,
+            span,
         }
     }
 
     fn for_statement(&mut self) -> Statements {
         let line = self.current().line;
+        let start = self.current().span;
 
         if self.current().token_type == TokenType::Keyword {
             // skipping keyword
             let _ = self.next();
         }
 
+        // range-based form: `for i in 0..10 { }`
+        if self.expect(TokenType::Identifier)
+            && self
+                .tokens
+                .get(self.position + 1)
+                .is_some_and(|token| token.token_type == TokenType::Keyword && token.value == "in")
+        {
+            return self.for_in_statement(line, start);
+        }
+
         // parsing params
 
         if !self.expect(TokenType::LParen) {
@@ -1162,6 +1565,7 @@ impl Parser {
             let _ = self.next();
         }
 
+        let span = self.span_from(start);
         self.skip_eos();
 
         Statements::ForStatement {
@@ -1170,16 +1574,45 @@ impl Parser {
             iterator,
             block,
             line,
+            span,
+        }
+    }
+
+    fn for_in_statement(&mut self, line: usize, start: Span) -> Statements {
+        let variable = self.current().value;
+
+        // skipping variable and `in` keyword
+        let _ = self.peek(2);
+
+        let iterable = self.range_expression();
+
+        if let Expressions::None = iterable {
+            return Statements::None;
+        }
+
+        let Some(block) = self.block_statements("for") else {
+            return Statements::None;
+        };
+
+        let span = self.span_from(start);
+        self.skip_eos();
+
+        Statements::ForInStatement {
+            variable,
+            iterable,
+            block,
+            line,
+            span,
         }
     }
 
-    fn call_statement(&mut self, function_name: String) -> Statements {
+    fn call_statement(&mut self, function_name: String, start: Span) -> Statements {
         let line = self.current().line;
 
         match self.current().token_type {
             TokenType::Identifier => {
                 let _ = self.next();
-                return self.call_statement(function_name);
+                return self.call_statement(function_name, start);
             }
             TokenType::LParen => {}
             _ => {
@@ -1191,17 +1624,20 @@ impl Parser {
         // parsing arguments
         let arguments =
             self.expressions_enum(TokenType::LParen, TokenType::RParen, TokenType::Comma);
+        let span = self.span_from(start);
         self.skip_eos();
 
         Statements::FunctionCallStatement {
             function_name,
             arguments,
             line,
+            span,
         }
     }
 
     fn define_statement(&mut self) -> Statements {
         let line = self.current().line;
+        let start = self.current().span;
 
         match self.current().token_type {
             TokenType::Keyword => {
@@ -1209,16 +1645,16 @@ impl Parser {
                     let _ = self.next();
                 }
 
-                if !DATATYPES.contains(&self.current().value.as_str()) {
+                if !self.is_datatype() {
                     self.error("Unexpected keyword found after `define`!");
                     return Statements::None;
                 }
 
-                // keeping datatype
-                let function_type = self.current().value;
+                // keeping datatype: `int32`, `list<int32>` or `fn<int32(int32)>`
+                let function_type = self.parse_datatype();
 
                 // searching for the function name
-                let identifier = self.next();
+                let identifier = self.current();
 
                 if !self.expect(TokenType::Identifier) {
                     self.error("Identifier for function expected, but found anything else!");
@@ -1230,25 +1666,9 @@ impl Parser {
 
                 // getting arguments
                 let _ = self.next();
-                let args =
-                    self.expressions_enum(TokenType::LParen, TokenType::RParen, TokenType::Comma);
-
-                let mut arguments_tuples = Vec::new();
-
-                // checking for right arguments definition
-                if !args.is_empty() {
-                    for arg in args {
-                        match arg {
-                            Expressions::Argument { name, datatype } => {
-                                arguments_tuples.push((name, datatype));
-                            }
-                            _ => {
-                                self.error("All arguments in definition must be `type name` (example: `int32 a`)");
-                                return Statements::None;
-                            }
-                        }
-                    }
-                }
+                let Some(arguments_tuples) = self.arguments_definition() else {
+                    return Statements::None;
+                };
 
                 // parsing block
                 if !self.expect(TokenType::LBrace) {
@@ -1277,6 +1697,7 @@ impl Parser {
                     let _ = self.next();
                 }
 
+                let span = self.span_from(start);
                 self.skip_eos();
 
                 // returning function
@@ -1286,7 +1707,9 @@ impl Parser {
                     function_type,
                     arguments: arguments_tuples,
                     block: stmts,
+                    docs: Vec::new(),
                     line,
+                    span,
                 }
             }
             _ => {
@@ -1296,32 +1719,272 @@ impl Parser {
         }
     }
 
+    fn struct_statement(&mut self) -> Statements {
+        let line = self.current().line;
+        let start = self.current().span;
+
+        if self.current().token_type == TokenType::Keyword {
+            let _ = self.next();
+        }
+
+        if !self.expect(TokenType::Identifier) {
+            self.error("Identifier for structure expected after `struct` keyword!");
+            return Statements::None;
+        }
+
+        let struct_name = self.current().value;
+
+        if self.next().token_type != TokenType::LBrace {
+            self.error("Expected block with fields after structure declaration!");
+            return Statements::None;
+        }
+
+        let _ = self.next();
+
+        // parsing fields: `type name;`
+        let mut fields = Vec::new();
+
+        while !self.expect(TokenType::RBrace) {
+            if self.expect(TokenType::EOF) {
+                self.error("Unexpected end-of-file in structure declaration. Please add '}'!");
+                return Statements::None;
+            }
+
+            if !self.is_datatype() {
+                self.error("All fields in structure must be `type name;` (example: `int32 x;`)");
+                return Statements::None;
+            }
+
+            let datatype = self.parse_datatype();
+
+            if !self.expect(TokenType::Identifier) {
+                self.error("Identifier expected after field type!");
+                return Statements::None;
+            }
+
+            fields.push((self.current().value, datatype));
+
+            if self.next().token_type != END_STATEMENT {
+                self.error("Expected `;` after structure field!");
+                return Statements::None;
+            }
+
+            let _ = self.next();
+        }
+
+        let _ = self.next();
+
+        let span = self.span_from(start);
+        self.skip_eos();
+
+        Statements::StructDefineStatement {
+            struct_name,
+            fields,
+            docs: Vec::new(),
+            line,
+            span,
+        }
+    }
+
+    fn enum_statement(&mut self) -> Statements {
+        let line = self.current().line;
+        let start = self.current().span;
+
+        if self.current().token_type == TokenType::Keyword {
+            let _ = self.next();
+        }
+
+        if !self.expect(TokenType::Identifier) {
+            self.error("Identifier for enumeration expected after `enum` keyword!");
+            return Statements::None;
+        }
+
+        let enum_name = self.current().value;
+
+        if self.next().token_type != TokenType::LBrace {
+            self.error("Expected block with variants after enumeration declaration!");
+            return Statements::None;
+        }
+
+        let _ = self.next();
+
+        // parsing variants: `Name` or `Name(type, type)`
+        let mut variants = Vec::new();
+
+        while !self.expect(TokenType::RBrace) {
+            if !self.expect(TokenType::Identifier) {
+                self.error("Enumeration variant must be `Name` or `Name(type, ...)`!");
+                return Statements::None;
+            }
+
+            let variant = self.current().value;
+            let mut payload = Vec::new();
+
+            if self.next().token_type == TokenType::LParen {
+                let _ = self.next();
+
+                while !self.expect(TokenType::RParen) {
+                    if !self.is_datatype() {
+                        self.error("Datatype expected in enumeration variant payload!");
+                        return Statements::None;
+                    }
+
+                    payload.push(self.parse_datatype());
+
+                    if self.expect(TokenType::Comma) {
+                        let _ = self.next();
+                    } else if !self.expect(TokenType::RParen) {
+                        self.error("Unexpected variant payload end found!");
+                        return Statements::None;
+                    }
+                }
+
+                let _ = self.next();
+            }
+
+            variants.push((variant, payload));
+
+            match self.current().token_type {
+                TokenType::Comma => {
+                    let _ = self.next();
+                }
+                TokenType::RBrace => {}
+                _ => {
+                    self.error("Expected `,` between enumeration variants!");
+                    return Statements::None;
+                }
+            }
+        }
+
+        let _ = self.next();
+
+        let span = self.span_from(start);
+        self.skip_eos();
+
+        Statements::EnumDefineStatement {
+            enum_name,
+            variants,
+            docs: Vec::new(),
+            line,
+            span,
+        }
+    }
+
+    fn match_statement(&mut self) -> Statements {
+        let line = self.current().line;
+        let start = self.current().span;
+
+        if self.current().token_type == TokenType::Keyword {
+            let _ = self.next();
+        }
+
+        let value = self.operator_expression(0);
+
+        if !self.expect(TokenType::LBrace) {
+            self.error("New block with arms expected after `match` value!");
+            return Statements::None;
+        }
+
+        let _ = self.next();
+
+        // parsing arms: `Variant(a, b) => { ... }` or `_ => { ... }`
+        let mut arms = Vec::new();
+
+        while !self.expect(TokenType::RBrace) {
+            if !self.expect(TokenType::Identifier) {
+                self.error("Match arm must start with variant name or `_`!");
+                return Statements::None;
+            }
+
+            let variant = self.current().value;
+            let mut bindings = Vec::new();
+
+            if self.next().token_type == TokenType::LParen {
+                let _ = self.next();
+
+                while !self.expect(TokenType::RParen) {
+                    if !self.expect(TokenType::Identifier) {
+                        self.error("Only identifiers can bind variant payload!");
+                        return Statements::None;
+                    }
+
+                    bindings.push(self.current().value);
+
+                    if self.next().token_type == TokenType::Comma {
+                        let _ = self.next();
+                    } else if !self.expect(TokenType::RParen) {
+                        self.error("Unexpected arm bindings end found!");
+                        return Statements::None;
+                    }
+                }
+
+                let _ = self.next();
+            }
+
+            if !self.expect(TokenType::Arrow) {
+                self.error("Expected `=>` after match arm pattern!");
+                return Statements::None;
+            }
+
+            let _ = self.next();
+
+            let Some(stmts) = self.block_statements("match arm") else {
+                return Statements::None;
+            };
+
+            // arms might be separated with comma
+            if self.expect(TokenType::Comma) {
+                let _ = self.next();
+            }
+
+            arms.push((variant, bindings, stmts));
+        }
+
+        let _ = self.next();
+
+        let span = self.span_from(start);
+        self.skip_eos();
+
+        Statements::MatchStatement {
+            value,
+            arms,
+            line,
+            span,
+        }
+    }
+
     fn return_statement(&mut self) -> Statements {
+        let start = self.current().span;
+
         if self.current().token_type == TokenType::Keyword {
             let _ = self.next();
         }
 
         let line = self.current().line;
         let value = self.expression();
+        let span = self.span_from(start);
 
         self.skip_eos();
 
-        Statements::ReturnStatement { value, line }
+        Statements::ReturnStatement { value, line, span }
     }
 
     fn import_statement(&mut self) -> Statements {
+        let start = self.current().span;
+
         if self.current().token_type == TokenType::Keyword {
             let _ = self.next();
         }
 
         let line = self.current().line;
         let path = self.expression();
+        let span = self.span_from(start);
 
         self.skip_eos();
 
         // checking if path is string
-        if let Expressions::Value(Value::String(_)) = path {
-            Statements::ImportStatement { path, line }
+        if let Expressions::Value(Value::String(_), _) = path {
+            Statements::ImportStatement { path, line, span }
         } else {
             self.error("Unexpected import value found!");
             Statements::None
@@ -1368,19 +2031,128 @@ impl Parser {
         output
     }
 
-    fn subelement_expression(&mut self, parent: Expressions, separator: TokenType) -> Expressions {
-        let line = self.current().line;
-
-        if self.expect(separator) {
-            let _ = self.next();
+    fn block_statements(&mut self, owner: &str) -> Option<Vec<Statements>> {
+        // `{ statements }` block after construction's head
+        if !self.expect(TokenType::LBrace) {
+            self.error(format!("New block expected after `{}`!", owner));
+            return None;
         }
 
-        let child = self.expression();
+        let _ = self.next();
+        let mut stmts = Vec::new();
 
-        Expressions::SubElement {
-            parent: Box::new(parent),
-            child: Box::new(child),
+        while !self.expect(TokenType::RBrace) {
+            if self.expect(TokenType::EOF) {
+                self.error(format!(
+                    "Unexpected end-of-file in block after `{}`. Please add '}}'!",
+                    owner
+                ));
+                return None;
+            }
+
+            stmts.push(self.statement());
+        }
+
+        let _ = self.next();
+        Some(stmts)
+    }
+
+    fn arguments_definition(&mut self) -> Option<Vec<(String, String)>> {
+        // `(type name, type name)` list for functions and lambdas
+        if !self.expect(TokenType::LParen) {
+            self.error("Expected arguments definition in parentheses!");
+            return None;
+        }
+
+        let _ = self.next();
+        let mut arguments = Vec::new();
+
+        while !self.expect(TokenType::RParen) {
+            if !self.is_datatype() {
+                self.error("All arguments in definition must be `type name` (example: `int32 a`)");
+                return None;
+            }
+
+            let datatype = self.parse_datatype();
+
+            if !self.expect(TokenType::Identifier) {
+                self.error("All arguments in definition must be `type name` (example: `int32 a`)");
+                return None;
+            }
+
+            arguments.push((self.current().value, datatype));
+
+            match self.next().token_type {
+                TokenType::Comma => {
+                    let _ = self.next();
+                }
+                TokenType::RParen => {}
+                _ => {
+                    self.error("Unexpected arguments definition end found!");
+                    return None;
+                }
+            }
+        }
+
+        let _ = self.next();
+        Some(arguments)
+    }
+
+    fn struct_expression(&mut self, name: String, start: Span) -> Expressions {
+        // `Name { field: value, ... }`
+        let line = self.current().line;
+        let _ = self.next();
+
+        let mut fields = Vec::new();
+
+        while !self.expect(TokenType::RBrace) {
+            if !self.expect(TokenType::Identifier) || self.lookahead(1) != TokenType::Colon {
+                self.error("Structure fields must be initialized like `name: value`!");
+                return Expressions::None;
+            }
+
+            let field = self.current().value;
+            let _ = self.peek(2);
+
+            fields.push((field, self.operator_expression(0)));
+
+            match self.current().token_type {
+                TokenType::Comma => {
+                    let _ = self.next();
+                }
+                TokenType::RBrace => {}
+                _ => {
+                    self.error("Unexpected structure literal end found!");
+                    return Expressions::None;
+                }
+            }
+        }
+
+        let _ = self.next();
+
+        Expressions::Struct {
+            name,
+            fields,
+            line,
+            span: self.span_from(start),
+        }
+    }
+
+    fn subelement_expression(&mut self, parent: Expressions, separator: TokenType) -> Expressions {
+        let line = self.current().line;
+
+        if self.expect(separator) {
+            let _ = self.next();
+        }
+
+        let start = parent.span().unwrap_or(self.current().span);
+        let child = self.postfix_expression();
+
+        Expressions::SubElement {
+            parent: Box::new(parent),
+            child: Box::new(child),
             line,
+            span: self.span_from(start),
         }
     }
 
@@ -1408,7 +2180,42 @@ impl Parser {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tpl_lexer::{token::Token, token_type::TokenType, Lexer};
+    use tpl_lexer::{span::Span, token::Token, token_type::TokenType, Lexer};
+
+    fn parse_expression(input: &str) -> Expressions {
+        let tokens = Lexer::new(input.to_string(), "test".to_string())
+            .tokenize()
+            .unwrap();
+        let mut parser = Parser::new(tokens, "test".to_string(), input.to_string());
+
+        parser.expression()
+    }
+
+    fn expression_shape(expression: &Expressions) -> String {
+        // renders operators tree with explicit parentheses
+        match expression {
+            Expressions::Binary {
+                operand, lhs, rhs, ..
+            }
+            | Expressions::Bitwise {
+                operand, lhs, rhs, ..
+            }
+            | Expressions::Boolean {
+                operand, lhs, rhs, ..
+            } => format!(
+                "({} {} {})",
+                expression_shape(lhs),
+                operand,
+                expression_shape(rhs)
+            ),
+            Expressions::Unary {
+                operand, object, ..
+            } => format!("({}{})", operand, expression_shape(object)),
+            Expressions::Value(Value::Integer(value), _) => value.to_string(),
+            Expressions::Value(Value::Identifier(name), _) => name.clone(),
+            _ => String::from("?"),
+        }
+    }
 
     #[test]
     fn subelement_expr_test() {
@@ -1426,9 +2233,16 @@ mod tests {
         assert_eq!(
             ast[0],
             Statements::Expression(Expressions::SubElement {
-                parent: Box::new(Expressions::Value(Value::Identifier("a".to_string()))),
-                child: Box::new(Expressions::Value(Value::Identifier("b".to_string()))),
-                line: 0
+                parent: Box::new(Expressions::Value(
+                    Value::Identifier("a".to_string()),
+                    Span::new(0, 0, 1)
+                )),
+                child: Box::new(Expressions::Value(
+                    Value::Identifier("b".to_string()),
+                    Span::new(0, 2, 3)
+                )),
+                line: 0,
+                span: Span::new(0, 0, 3)
             })
         );
     }
@@ -1449,13 +2263,18 @@ mod tests {
         assert_eq!(
             ast[0],
             Statements::Expression(Expressions::SubElement {
-                parent: Box::new(Expressions::Value(Value::Identifier("a".to_string()))),
+                parent: Box::new(Expressions::Value(
+                    Value::Identifier("a".to_string()),
+                    Span::new(0, 0, 1)
+                )),
                 child: Box::new(Expressions::Call {
                     function_name: String::from("b"),
                     arguments: Vec::new(),
-                    line: 0
+                    line: 0,
+                    span: Span::new(0, 2, 5)
                 }),
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 5)
             })
         );
     }
@@ -1476,17 +2295,32 @@ mod tests {
         assert_eq!(
             ast[0],
             Statements::Expression(Expressions::SubElement {
-                parent: Box::new(Expressions::Value(Value::Identifier("a".to_string()))),
+                parent: Box::new(Expressions::Value(
+                    Value::Identifier("a".to_string()),
+                    Span::new(0, 0, 1)
+                )),
                 child: Box::new(Expressions::SubElement {
-                    parent: Box::new(Expressions::Value(Value::Identifier("b".to_string()))),
+                    parent: Box::new(Expressions::Value(
+                        Value::Identifier("b".to_string()),
+                        Span::new(0, 2, 3)
+                    )),
                     child: Box::new(Expressions::SubElement {
-                        parent: Box::new(Expressions::Value(Value::Identifier("c".to_string()))),
-                        child: Box::new(Expressions::Value(Value::Identifier("d".to_string()))),
-                        line: 0
+                        parent: Box::new(Expressions::Value(
+                            Value::Identifier("c".to_string()),
+                            Span::new(0, 4, 5)
+                        )),
+                        child: Box::new(Expressions::Value(
+                            Value::Identifier("d".to_string()),
+                            Span::new(0, 6, 7)
+                        )),
+                        line: 0,
+                        span: Span::new(0, 4, 7)
                     }),
-                    line: 0
+                    line: 0,
+                    span: Span::new(0, 2, 7)
                 }),
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 7)
             })
         );
     }
@@ -1505,17 +2339,17 @@ mod tests {
 
         assert_eq!(
             parser.peek(0),
-            Token::new(TokenType::Identifier, String::from("a"), 0)
+            Token::new(TokenType::Identifier, String::from("a"), 0).with_span(Span::new(0, 0, 1))
         );
 
         assert_eq!(
             parser.peek(1),
-            Token::new(TokenType::Identifier, String::from("b"), 0)
+            Token::new(TokenType::Identifier, String::from("b"), 0).with_span(Span::new(0, 2, 3))
         );
 
         assert_eq!(
             parser.peek(1),
-            Token::new(TokenType::EOF, String::from(""), 0)
+            Token::new(TokenType::EOF, String::from(""), 0).with_span(Span::new(0, 3, 3))
         );
     }
 
@@ -1533,12 +2367,12 @@ mod tests {
 
         assert_eq!(
             parser.next(),
-            Token::new(TokenType::Identifier, String::from("b"), 0)
+            Token::new(TokenType::Identifier, String::from("b"), 0).with_span(Span::new(0, 2, 3))
         );
 
         assert_eq!(
             parser.next(),
-            Token::new(TokenType::EOF, String::from(""), 0)
+            Token::new(TokenType::EOF, String::from(""), 0).with_span(Span::new(0, 3, 3))
         );
     }
 
@@ -1556,21 +2390,21 @@ mod tests {
 
         assert_eq!(
             parser.current(),
-            Token::new(TokenType::Identifier, String::from("a"), 0)
+            Token::new(TokenType::Identifier, String::from("a"), 0).with_span(Span::new(0, 0, 1))
         );
 
         let _ = parser.next();
 
         assert_eq!(
             parser.current(),
-            Token::new(TokenType::Identifier, String::from("b"), 0)
+            Token::new(TokenType::Identifier, String::from("b"), 0).with_span(Span::new(0, 2, 3))
         );
 
         let _ = parser.next();
 
         assert_eq!(
             parser.current(),
-            Token::new(TokenType::EOF, String::from(""), 0)
+            Token::new(TokenType::EOF, String::from(""), 0).with_span(Span::new(0, 3, 3))
         );
     }
 
@@ -1641,10 +2475,12 @@ mod tests {
         assert!(parser.is_boolean_operand(TokenType::Ne));
         assert!(parser.is_boolean_operand(TokenType::Lt));
         assert!(parser.is_boolean_operand(TokenType::Bt));
+        assert!(parser.is_boolean_operand(TokenType::Le));
+        assert!(parser.is_boolean_operand(TokenType::Be));
     }
 
     #[test]
-    fn is_priority_bin_operand_test() {
+    fn operator_precedence_test() {
         let input = String::from("a b");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
@@ -1655,10 +2491,22 @@ mod tests {
 
         let parser = Parser::new(tokens, "test".to_string(), input);
 
-        assert!(parser.is_priority_binary_operand(TokenType::Multiply));
-        assert!(parser.is_priority_binary_operand(TokenType::Divide));
-        assert!(!parser.is_priority_binary_operand(TokenType::Plus));
-        assert!(!parser.is_priority_binary_operand(TokenType::Minus));
+        assert_eq!(
+            parser.operator_precedence(TokenType::Multiply),
+            parser.operator_precedence(TokenType::Divide)
+        );
+        assert!(
+            parser.operator_precedence(TokenType::Multiply)
+                > parser.operator_precedence(TokenType::Plus)
+        );
+        assert!(
+            parser.operator_precedence(TokenType::Ampersand)
+                > parser.operator_precedence(TokenType::Eq)
+        );
+        assert!(
+            parser.operator_precedence(TokenType::And) > parser.operator_precedence(TokenType::Or)
+        );
+        assert_eq!(parser.operator_precedence(TokenType::Dot), None);
     }
 
     #[test]
@@ -1703,12 +2551,69 @@ mod tests {
             Statements::AnnotationStatement {
                 identifier: String::from("a"),
                 datatype: String::from("int32"),
-                value: Some(Box::new(Expressions::Value(Value::Integer(5)))),
-                line: 0
+                value: Some(Box::new(Expressions::Value(
+                    Value::Integer(5),
+                    Span::new(0, 10, 11)
+                ))),
+                line: 0,
+                span: Span::new(0, 0, 11)
+            }
+        );
+    }
+
+    #[test]
+    fn float_annotation_stmt_test() {
+        let input = String::from("float64 a = 2.75;");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("float64"),
+                value: Some(Box::new(Expressions::Value(
+                    Value::Float(2.75),
+                    Span::new(0, 12, 16)
+                ))),
+                line: 0,
+                span: Span::new(0, 0, 16)
             }
         );
     }
 
+    #[test]
+    fn float_binary_operations_test() {
+        let input = String::from("1.5 * 2e3;");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::Expression(Expressions::Binary {
+                operand: String::from("*"),
+                lhs: Box::new(Expressions::Value(Value::Float(1.5), Span::new(0, 0, 3))),
+                rhs: Box::new(Expressions::Value(Value::Float(2000.0), Span::new(0, 6, 9))),
+                line: 0,
+                span: Span::new(0, 0, 9)
+            })
+        );
+    }
+
     #[test]
     fn assign_stmt_test() {
         let input = String::from("a = 5;");
@@ -1726,8 +2631,9 @@ mod tests {
             ast[0],
             Statements::AssignStatement {
                 identifier: String::from("a"),
-                value: Box::new(Expressions::Value(Value::Integer(5))),
-                line: 0
+                value: Box::new(Expressions::Value(Value::Integer(5), Span::new(0, 4, 5))),
+                line: 0,
+                span: Span::new(0, 0, 5)
             }
         );
     }
@@ -1749,13 +2655,54 @@ mod tests {
             ast[0],
             Statements::BinaryAssignStatement {
                 identifier: String::from("a"),
-                value: Box::new(Expressions::Value(Value::Integer(5))),
+                value: Box::new(Expressions::Value(Value::Integer(5), Span::new(0, 5, 6))),
                 operand: String::from("+"),
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 6)
             }
         );
     }
 
+    #[test]
+    fn compound_assign_stmt_test() {
+        let input = String::from("a %= 5; b <<= 1; c &= d; e >>= 2; f |= 1; g ^= h;");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        let operands: Vec<(String, String)> = ast
+            .iter()
+            .filter_map(|statement| match statement {
+                Statements::BinaryAssignStatement {
+                    identifier,
+                    operand,
+                    ..
+                } => Some((identifier.clone(), operand.clone())),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            operands,
+            [
+                ("a", "%"),
+                ("b", "<<"),
+                ("c", "&"),
+                ("e", ">>"),
+                ("f", "|"),
+                ("g", "^")
+            ]
+            .map(|(identifier, operand)| (identifier.to_string(), operand.to_string()))
+            .to_vec()
+        );
+    }
+
     #[test]
     fn function_define_stmt_test() {
         let input = String::from("define int8 foo() {};");
@@ -1776,7 +2723,9 @@ mod tests {
                 function_type: String::from("int8"),
                 arguments: Vec::new(),
                 block: Vec::new(),
-                line: 0
+                docs: Vec::new(),
+                line: 0,
+                span: Span::new(0, 0, 20)
             }
         );
     }
@@ -1804,7 +2753,9 @@ mod tests {
                     ("b".to_string(), "int8".to_string()),
                 ],
                 block: Vec::new(),
-                line: 0
+                docs: Vec::new(),
+                line: 0,
+                span: Span::new(0, 0, 34)
             }
         );
     }
@@ -1830,10 +2781,13 @@ mod tests {
                 arguments: Vec::new(),
                 block: vec![Statements::AssignStatement {
                     identifier: "a".to_string(),
-                    value: Box::new(Expressions::Value(Value::Integer(5))),
-                    line: 0
+                    value: Box::new(Expressions::Value(Value::Integer(5), Span::new(0, 24, 25))),
+                    line: 0,
+                    span: Span::new(0, 20, 25)
                 }],
-                line: 0
+                docs: Vec::new(),
+                line: 0,
+                span: Span::new(0, 0, 27)
             }
         );
     }
@@ -1862,10 +2816,13 @@ mod tests {
                 ],
                 block: vec![Statements::AssignStatement {
                     identifier: "a".to_string(),
-                    value: Box::new(Expressions::Value(Value::Integer(5))),
-                    line: 0
+                    value: Box::new(Expressions::Value(Value::Integer(5), Span::new(0, 38, 39))),
+                    line: 0,
+                    span: Span::new(0, 34, 39)
                 }],
-                line: 0
+                docs: Vec::new(),
+                line: 0,
+                span: Span::new(0, 0, 41)
             }
         );
     }
@@ -1888,7 +2845,8 @@ mod tests {
             Statements::FunctionCallStatement {
                 function_name: String::from("foo"),
                 arguments: Vec::new(),
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 5)
             }
         );
     }
@@ -1911,11 +2869,12 @@ mod tests {
             Statements::FunctionCallStatement {
                 function_name: String::from("foo"),
                 arguments: vec![
-                    Expressions::Value(Value::Integer(5)),
-                    Expressions::Value(Value::Integer(1)),
-                    Expressions::Value(Value::Integer(4))
+                    Expressions::Value(Value::Integer(5), Span::new(0, 4, 5)),
+                    Expressions::Value(Value::Integer(1), Span::new(0, 7, 8)),
+                    Expressions::Value(Value::Integer(4), Span::new(0, 10, 11))
                 ],
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 12)
             }
         );
     }
@@ -1940,18 +2899,21 @@ mod tests {
                 arguments: vec![
                     Expressions::Binary {
                         operand: String::from("+"),
-                        lhs: Box::new(Expressions::Value(Value::Integer(5))),
-                        rhs: Box::new(Expressions::Value(Value::Integer(6))),
-                        line: 0
+                        lhs: Box::new(Expressions::Value(Value::Integer(5), Span::new(0, 4, 5))),
+                        rhs: Box::new(Expressions::Value(Value::Integer(6), Span::new(0, 8, 9))),
+                        line: 0,
+                        span: Span::new(0, 4, 9)
                     },
                     Expressions::Binary {
                         operand: String::from("*"),
-                        lhs: Box::new(Expressions::Value(Value::Integer(2))),
-                        rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                        line: 0
+                        lhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 11, 12))),
+                        rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 15, 16))),
+                        line: 0,
+                        span: Span::new(0, 11, 16)
                     },
                 ],
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 17)
             }
         );
     }
@@ -1979,20 +2941,36 @@ mod tests {
                     arguments: vec![
                         Expressions::Binary {
                             operand: String::from("+"),
-                            lhs: Box::new(Expressions::Value(Value::Integer(5))),
-                            rhs: Box::new(Expressions::Value(Value::Integer(6))),
-                            line: 0
+                            lhs: Box::new(Expressions::Value(
+                                Value::Integer(5),
+                                Span::new(0, 14, 15)
+                            )),
+                            rhs: Box::new(Expressions::Value(
+                                Value::Integer(6),
+                                Span::new(0, 18, 19)
+                            )),
+                            line: 0,
+                            span: Span::new(0, 14, 19)
                         },
                         Expressions::Binary {
                             operand: String::from("*"),
-                            lhs: Box::new(Expressions::Value(Value::Integer(2))),
-                            rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                            line: 0
+                            lhs: Box::new(Expressions::Value(
+                                Value::Integer(2),
+                                Span::new(0, 21, 22)
+                            )),
+                            rhs: Box::new(Expressions::Value(
+                                Value::Integer(2),
+                                Span::new(0, 25, 26)
+                            )),
+                            line: 0,
+                            span: Span::new(0, 21, 26)
                         },
                     ],
-                    line: 0
+                    line: 0,
+                    span: Span::new(0, 10, 27)
                 })),
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 27)
             }
         );
     }
@@ -2015,13 +2993,15 @@ mod tests {
             Statements::IfStatement {
                 condition: Expressions::Boolean {
                     operand: String::from("<"),
-                    lhs: Box::new(Expressions::Value(Value::Integer(1))),
-                    rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                    line: 0
+                    lhs: Box::new(Expressions::Value(Value::Integer(1), Span::new(0, 3, 4))),
+                    rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 7, 8))),
+                    line: 0,
+                    span: Span::new(0, 3, 8)
                 },
                 then_block: Vec::new(),
                 else_block: None,
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 11)
             }
         );
     }
@@ -2044,13 +3024,15 @@ mod tests {
             Statements::IfStatement {
                 condition: Expressions::Boolean {
                     operand: String::from("<"),
-                    lhs: Box::new(Expressions::Value(Value::Integer(1))),
-                    rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                    line: 0
+                    lhs: Box::new(Expressions::Value(Value::Integer(1), Span::new(0, 3, 4))),
+                    rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 7, 8))),
+                    line: 0,
+                    span: Span::new(0, 3, 8)
                 },
                 then_block: Vec::new(),
                 else_block: Some(Vec::new()),
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 19)
             }
         );
     }
@@ -2073,19 +3055,23 @@ mod tests {
             Statements::IfStatement {
                 condition: Expressions::Boolean {
                     operand: String::from("<"),
-                    lhs: Box::new(Expressions::Value(Value::Integer(1))),
-                    rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                    line: 0
+                    lhs: Box::new(Expressions::Value(Value::Integer(1), Span::new(0, 3, 4))),
+                    rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 7, 8))),
+                    line: 0,
+                    span: Span::new(0, 3, 8)
                 },
                 then_block: vec![Statements::ReturnStatement {
-                    value: Expressions::Value(Value::Integer(1)),
-                    line: 0
+                    value: Expressions::Value(Value::Integer(1), Span::new(0, 18, 19)),
+                    line: 0,
+                    span: Span::new(0, 11, 19)
                 }],
                 else_block: Some(vec![Statements::ReturnStatement {
-                    value: Expressions::Value(Value::Integer(2)),
-                    line: 0
+                    value: Expressions::Value(Value::Integer(2), Span::new(0, 37, 38)),
+                    line: 0,
+                    span: Span::new(0, 30, 38)
                 }]),
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 40)
             }
         );
     }
@@ -2106,8 +3092,9 @@ mod tests {
         assert_eq!(
             ast[0],
             Statements::ReturnStatement {
-                value: Expressions::Value(Value::Integer(0)),
-                line: 0
+                value: Expressions::Value(Value::Integer(0), Span::new(0, 7, 8)),
+                line: 0,
+                span: Span::new(0, 0, 8)
             }
         );
     }
@@ -2130,11 +3117,13 @@ mod tests {
             Statements::ReturnStatement {
                 value: Expressions::Binary {
                     operand: String::from("+"),
-                    lhs: Box::new(Expressions::Value(Value::Integer(2))),
-                    rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                    line: 0
+                    lhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 7, 8))),
+                    rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 11, 12))),
+                    line: 0,
+                    span: Span::new(0, 7, 12)
                 },
-                line: 0
+                line: 0,
+                span: Span::new(0, 0, 12)
             }
         );
     }
@@ -2156,9 +3145,10 @@ mod tests {
             ast[0],
             Statements::Expression(Expressions::Binary {
                 operand: String::from("+"),
-                lhs: Box::new(Expressions::Value(Value::Integer(2))),
-                rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                line: 0
+                lhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 0, 1))),
+                rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 4, 5))),
+                line: 0,
+                span: Span::new(0, 0, 5)
             })
         );
     }
@@ -2176,25 +3166,837 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        assert_eq!(
-            ast[0],
-            Statements::Expression(Expressions::Binary {
-                operand: String::from("+"),
-                lhs: Box::new(Expressions::Value(Value::Integer(2))),
-                rhs: Box::new(Expressions::Binary {
-                    operand: String::from("*"),
-                    lhs: Box::new(Expressions::Value(Value::Integer(2))),
-                    rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                    line: 0
-                }),
-                line: 0
-            })
-        );
+        assert_eq!(
+            ast[0],
+            Statements::Expression(Expressions::Binary {
+                operand: String::from("+"),
+                lhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 0, 1))),
+                rhs: Box::new(Expressions::Binary {
+                    operand: String::from("*"),
+                    lhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 4, 5))),
+                    rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 8, 9))),
+                    line: 0,
+                    span: Span::new(0, 4, 9)
+                }),
+                line: 0,
+                span: Span::new(0, 0, 9)
+            })
+        );
+    }
+
+    #[test]
+    fn while_stmt_test() {
+        let input = String::from("while 1 < 2 {};");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::WhileStatement {
+                condition: Expressions::Boolean {
+                    operand: String::from("<"),
+                    lhs: Box::new(Expressions::Value(Value::Integer(1), Span::new(0, 6, 7))),
+                    rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 10, 11))),
+                    line: 0,
+                    span: Span::new(0, 6, 11)
+                },
+                block: Vec::new(),
+                line: 0,
+                span: Span::new(0, 0, 14)
+            }
+        );
+    }
+
+    #[test]
+    fn while_with_block_stmt_test() {
+        let input = String::from("while 1 < 2 { break };");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::WhileStatement {
+                condition: Expressions::Boolean {
+                    operand: String::from("<"),
+                    lhs: Box::new(Expressions::Value(Value::Integer(1), Span::new(0, 6, 7))),
+                    rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 10, 11))),
+                    line: 0,
+                    span: Span::new(0, 6, 11)
+                },
+                block: vec![Statements::BreakStatement {
+                    line: 0,
+                    span: Span::new(0, 14, 19)
+                }],
+                line: 0,
+                span: Span::new(0, 0, 21)
+            }
+        );
+    }
+
+    #[test]
+    fn for_stmt_test() {
+        let input = String::from("for (int8 i = 0; i < 5; i++) {};");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::ForStatement {
+                initializer: Box::new(Statements::AnnotationStatement {
+                    identifier: String::from("i"),
+                    datatype: String::from("int8"),
+                    value: Some(Box::new(Expressions::Value(
+                        Value::Integer(0),
+                        Span::new(0, 14, 15)
+                    ))),
+                    line: 0,
+                    span: Span::new(0, 5, 15)
+                }),
+                condition: Expressions::Boolean {
+                    operand: String::from("<"),
+                    lhs: Box::new(Expressions::Value(
+                        Value::Identifier(String::from("i")),
+                        Span::new(0, 17, 18)
+                    )),
+                    rhs: Box::new(Expressions::Value(Value::Integer(5), Span::new(0, 21, 22))),
+                    line: 0,
+                    span: Span::new(0, 17, 22)
+                },
+                iterator: Box::new(Statements::BinaryAssignStatement {
+                    identifier: String::from("i"),
+                    operand: String::from("+"),
+                    value: Box::new(Expressions::Value(Value::Integer(1), Span::new(0, 25, 27))),
+                    line: 0,
+                    span: Span::new(0, 24, 27)
+                }),
+                block: Vec::new(),
+                line: 0,
+                span: Span::new(0, 0, 31)
+            }
+        );
+    }
+
+    #[test]
+    fn for_stmt_with_block_test() {
+        let input = String::from("for (int8 i = 0; i < 5; i++) { print(i) };");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::ForStatement {
+                initializer: Box::new(Statements::AnnotationStatement {
+                    identifier: String::from("i"),
+                    datatype: String::from("int8"),
+                    value: Some(Box::new(Expressions::Value(
+                        Value::Integer(0),
+                        Span::new(0, 14, 15)
+                    ))),
+                    line: 0,
+                    span: Span::new(0, 5, 15)
+                }),
+                condition: Expressions::Boolean {
+                    operand: String::from("<"),
+                    lhs: Box::new(Expressions::Value(
+                        Value::Identifier(String::from("i")),
+                        Span::new(0, 17, 18)
+                    )),
+                    rhs: Box::new(Expressions::Value(Value::Integer(5), Span::new(0, 21, 22))),
+                    line: 0,
+                    span: Span::new(0, 17, 22)
+                },
+                iterator: Box::new(Statements::BinaryAssignStatement {
+                    identifier: String::from("i"),
+                    operand: String::from("+"),
+                    value: Box::new(Expressions::Value(Value::Integer(1), Span::new(0, 25, 27))),
+                    line: 0,
+                    span: Span::new(0, 24, 27)
+                }),
+                block: vec![Statements::FunctionCallStatement {
+                    function_name: String::from("print"),
+                    arguments: vec![Expressions::Value(
+                        Value::Identifier(String::from("i")),
+                        Span::new(0, 37, 38)
+                    )],
+                    line: 0,
+                    span: Span::new(0, 31, 39)
+                }],
+                line: 0,
+                span: Span::new(0, 0, 41)
+            }
+        );
+    }
+
+    #[test]
+    fn break_stmt_test() {
+        let input = String::from("break");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::BreakStatement {
+                line: 0,
+                span: Span::new(0, 0, 5)
+            }
+        );
+    }
+
+    #[test]
+    fn continue_stmt_test() {
+        let input = String::from("continue");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::ContinueStatement {
+                line: 0,
+                span: Span::new(0, 0, 8)
+            }
+        );
+    }
+
+    #[test]
+    fn while_with_continue_stmt_test() {
+        let input = String::from("while 1 < 2 { continue; break };");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::WhileStatement {
+                condition: Expressions::Boolean {
+                    operand: String::from("<"),
+                    lhs: Box::new(Expressions::Value(Value::Integer(1), Span::new(0, 6, 7))),
+                    rhs: Box::new(Expressions::Value(Value::Integer(2), Span::new(0, 10, 11))),
+                    line: 0,
+                    span: Span::new(0, 6, 11)
+                },
+                block: vec![
+                    Statements::ContinueStatement {
+                        line: 0,
+                        span: Span::new(0, 14, 22)
+                    },
+                    Statements::BreakStatement {
+                        line: 0,
+                        span: Span::new(0, 24, 29)
+                    }
+                ],
+                line: 0,
+                span: Span::new(0, 0, 31)
+            }
+        );
+    }
+
+    #[test]
+    fn import_statement() {
+        let input = String::from("import \"std.tpl\"");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::ImportStatement {
+                path: Expressions::Value(Value::String("std.tpl".to_string()), Span::new(0, 7, 16)),
+                line: 0,
+                span: Span::new(0, 0, 16)
+            }
+        );
+    }
+
+    #[test]
+    fn lambda_expr_test() {
+        let input = String::from("fn<int8> a = int8 (int8 a, int8 b) { return 0 };");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("fn<int8>"),
+                value: Some(Box::new(Expressions::Lambda {
+                    arguments: vec![
+                        ("a".to_string(), "int8".to_string()),
+                        ("b".to_string(), "int8".to_string()),
+                    ],
+                    statements: vec![Statements::ReturnStatement {
+                        value: Expressions::Value(Value::Integer(0), Span::new(0, 44, 45)),
+                        line: 0,
+                        span: Span::new(0, 37, 45)
+                    }],
+                    ftype: String::from("int8"),
+                    line: 0,
+                    span: Span::new(0, 13, 47)
+                })),
+                line: 0,
+                span: Span::new(0, 0, 47)
+            }
+        );
+    }
+
+    #[test]
+    fn expressions_enum_test() {
+        let input = String::from("(1, true, \"a\")");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.expressions_enum(TokenType::LParen, TokenType::RParen, TokenType::Comma);
+
+        assert_eq!(
+            ast,
+            vec![
+                Expressions::Value(Value::Integer(1), Span::new(0, 1, 2)),
+                Expressions::Value(Value::Boolean(true), Span::new(0, 4, 8)),
+                Expressions::Value(Value::String("a".to_string()), Span::new(0, 10, 13)),
+            ]
+        );
+    }
+
+    #[test]
+    fn expressions_enum_test_2() {
+        let input = String::from("[1; true; \"a\"]");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast =
+            parser.expressions_enum(TokenType::LBrack, TokenType::RBrack, TokenType::Semicolon);
+
+        assert_eq!(
+            ast,
+            vec![
+                Expressions::Value(Value::Integer(1), Span::new(0, 1, 2)),
+                Expressions::Value(Value::Boolean(true), Span::new(0, 4, 8)),
+                Expressions::Value(Value::String("a".to_string()), Span::new(0, 10, 13)),
+            ]
+        );
+    }
+
+    #[test]
+    fn error_test() {
+        let input = String::from("int32 a = ;");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse();
+
+        assert!(ast.is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_test() {
+        let input = String::from("int32 5;");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let _ = parser.parse().unwrap();
+    }
+
+    #[test]
+    fn array_annotation_test() {
+        let input = String::from("int32[] a;");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("int32[]"),
+                value: None,
+                line: 0,
+                span: Span::new(0, 0, 9)
+            }
+        );
+    }
+
+    #[test]
+    fn array_annotation_with_len_test() {
+        let input = String::from("int32[5] a;");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("int32[5]"),
+                value: None,
+                line: 0,
+                span: Span::new(0, 0, 10)
+            }
+        );
+    }
+
+    #[test]
+    fn array_annotation_with_values_test() {
+        let input = String::from("int32[] a = [1,2,3];");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("int32[]"),
+                value: Some(Box::new(Expressions::Array {
+                    values: vec![
+                        Expressions::Value(Value::Integer(1), Span::new(0, 13, 14)),
+                        Expressions::Value(Value::Integer(2), Span::new(0, 15, 16)),
+                        Expressions::Value(Value::Integer(3), Span::new(0, 17, 18)),
+                    ],
+                    len: 3,
+                    line: 0,
+                    span: Span::new(0, 12, 19)
+                })),
+                line: 0,
+                span: Span::new(0, 0, 19)
+            }
+        );
+    }
+
+    #[test]
+    fn array_annotation_with_empty_test() {
+        let input = String::from("int32[] a = [];");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("int32[]"),
+                value: Some(Box::new(Expressions::Array {
+                    values: vec![],
+                    len: 0,
+                    line: 0,
+                    span: Span::new(0, 12, 14)
+                })),
+                line: 0,
+                span: Span::new(0, 0, 14)
+            }
+        );
+    }
+
+    #[test]
+    fn pointer_annotation_test() {
+        let input = String::from("int32* a;");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("int32*"),
+                value: None,
+                line: 0,
+                span: Span::new(0, 0, 8)
+            }
+        );
+    }
+
+    #[test]
+    fn pointer_on_ref_annotation_test() {
+        let input = String::from("int32* a = &b;");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("int32*"),
+                value: Some(Box::new(Expressions::Reference {
+                    object: Box::new(Expressions::Value(
+                        Value::Identifier("b".to_string()),
+                        Span::new(0, 12, 13)
+                    )),
+                    line: 0,
+                    span: Span::new(0, 11, 13)
+                })),
+                line: 0,
+                span: Span::new(0, 0, 13)
+            }
+        );
+    }
+
+    #[test]
+    fn logical_or_in_condition() {
+        let input = String::from("if 1 > 2 || 2 > 1 {};");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        dbg!(&ast);
+    }
+
+    #[test]
+    fn bitwise_operation_in_annotation() {
+        let input = String::from("int32 a = 5 & 1");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            ast[0],
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("int32"),
+                value: Some(Box::new(Expressions::Bitwise {
+                    operand: String::from("&"),
+                    lhs: Box::new(Expressions::Value(Value::Integer(5), Span::new(0, 10, 11))),
+                    rhs: Box::new(Expressions::Value(Value::Integer(1), Span::new(0, 14, 15))),
+                    line: 0,
+                    span: Span::new(0, 10, 15)
+                })),
+                line: 0,
+                span: Span::new(0, 0, 15)
+            }
+        );
+    }
+
+    #[test]
+    fn multiline_statement_spans_test() {
+        let input = String::from("int32 a = 5;\nwhile a > 0 {\n  a -= 1;\n};");
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input.clone());
+        let ast = parser.parse().unwrap();
+
+        let while_span = ast[1].span().unwrap();
+        assert_eq!(while_span, Span::new(0, 13, 38));
+        assert_eq!(while_span.location(&input), (1, 0));
+
+        if let Statements::WhileStatement { block, .. } = &ast[1] {
+            let inner_span = block[0].span().unwrap();
+
+            assert_eq!(inner_span, Span::new(0, 29, 35));
+            assert_eq!(inner_span.location(&input), (2, 2));
+            assert_eq!(inner_span.underline(&input), "  ^^^^^^");
+        } else {
+            panic!("While statement expected!");
+        }
+    }
+
+    #[test]
+    fn doc_comments_attach_test() {
+        let input = String::from(
+            "/// Returns zero\n/// always\ndefine int8 foo() { return 0 };\n/// not a definition\nfoo(1, /// ignored\n 2);",
+        );
+        let mut lexer = Lexer::new(input.clone(), "test".to_string());
+
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(_) => panic!("Lexer side error occured!"),
+        };
+
+        let mut parser = Parser::new(tokens, "test".to_string(), input);
+        let ast = parser.parse().unwrap();
+
+        if let Statements::FunctionDefineStatement { docs, span, .. } = &ast[0] {
+            assert_eq!(
+                docs,
+                &vec![String::from("Returns zero"), String::from("always")]
+            );
+            assert_eq!(span.start, 28);
+        } else {
+            panic!("Function definition expected!");
+        }
+
+        if let Statements::FunctionCallStatement { arguments, .. } = &ast[1] {
+            assert_eq!(arguments.len(), 2);
+        } else {
+            panic!("Function call expected!");
+        }
+    }
+
+    #[test]
+    fn doc_comments_types_test() {
+        let input = String::from(
+            "/// 2D point\nstruct Point { int32 x; int32 y; };\n/// Figure kinds\n/// with payload\nenum Shape { Circle(int32), Empty };",
+        );
+        let tokens = Lexer::new(input.clone(), "test".to_string())
+            .tokenize()
+            .unwrap();
+        let ast = Parser::new(tokens, "test".to_string(), input)
+            .parse()
+            .unwrap();
+
+        assert!(matches!(
+            &ast[0],
+            Statements::StructDefineStatement { docs, .. } if docs == &vec![String::from("2D point")]
+        ));
+        assert!(matches!(
+            &ast[1],
+            Statements::EnumDefineStatement { docs, .. }
+                if docs == &vec![String::from("Figure kinds"), String::from("with payload")]
+        ));
+    }
+
+    #[test]
+    fn doc_comments_positions_test() {
+        // doc comments which don't precede definition are ignored like plain comments
+        let inputs = [
+            "define int32 f() { return 1; /// trailing\n};",
+            "while 1 < 2 { break; /// trailing\n};",
+            "if 1 < 2 { print(1); } /// between\nelse { print(2); };",
+            "struct Point { /// horizontal\nint32 x; /// vertical\nint32 y; /// trailing\n};",
+            "enum Shape { /// round\nCircle(int32), Empty };",
+            "/// dangling",
+        ];
+
+        for input in inputs {
+            let tokens = Lexer::new(input.to_string(), "test".to_string())
+                .tokenize()
+                .unwrap();
+            let result = Parser::new(tokens, "test".to_string(), input.to_string()).parse();
+
+            assert!(result.is_ok(), "`{}` must be parsed", input);
+        }
+    }
+
+    #[test]
+    fn left_associativity_test() {
+        let cases = [
+            ("10 - 2 - 3", "((10 - 2) - 3)"),
+            ("8 / 4 / 2", "((8 / 4) / 2)"),
+            ("1 + 2 * 3 - 4", "((1 + (2 * 3)) - 4)"),
+            ("a * b / c * d", "(((a * b) / c) * d)"),
+            ("1 << 2 << 3", "((1 << 2) << 3)"),
+            ("a || b || c", "((a || b) || c)"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(expression_shape(&parse_expression(input)), expected);
+        }
+    }
+
+    #[test]
+    fn operators_precedence_test() {
+        let cases = [
+            ("a & b == c", "((a & b) == c)"),
+            ("a | b ^ c & d", "(a | (b ^ (c & d)))"),
+            ("1 << 2 + 3", "(1 << (2 + 3))"),
+            ("a || b && c", "(a || (b && c))"),
+            ("a && b || c", "((a && b) || c)"),
+            ("x + 1 > y * 2", "((x + 1) > (y * 2))"),
+            ("a % b * c", "((a % b) * c)"),
+            ("a + b % c <= d", "((a + (b % c)) <= d)"),
+            ("a >= b == c <= d", "(((a >= b) == c) <= d)"),
+            (
+                "x == 1 || y != 2 && z > 3",
+                "((x == 1) || ((y != 2) && (z > 3)))",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(expression_shape(&parse_expression(input)), expected);
+        }
+    }
+
+    #[test]
+    fn parentheses_and_unary_test() {
+        let cases = [
+            ("(a + b) * c", "((a + b) * c)"),
+            ("a - (b - c)", "(a - (b - c))"),
+            ("((1))", "1"),
+            ("-a * b", "((-a) * b)"),
+            ("-(1 + 2)", "(-(1 + 2))"),
+            ("- -x", "(-(-x))"),
+            ("!(a > b) && c", "((!(a > b)) && c)"),
+            ("~x & 0xFF", "((~x) & 255)"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(expression_shape(&parse_expression(input)), expected);
+        }
+    }
+
+    #[test]
+    fn subtraction_without_spaces_test() {
+        let cases = ["a-1", "f(a)-1", "x[0]-1"];
+
+        for expression in cases {
+            let input = format!("int32 b = {};", expression);
+            let tokens = Lexer::new(input.clone(), "test".to_string())
+                .tokenize()
+                .unwrap();
+            let mut parser = Parser::new(tokens, "test".to_string(), input);
+            let ast = parser.parse().unwrap();
+
+            assert_eq!(
+                ast.len(),
+                1,
+                "`{}` split into several statements",
+                expression
+            );
+
+            let Statements::AnnotationStatement {
+                value: Some(value), ..
+            } = &ast[0]
+            else {
+                panic!("expected annotation for `{}`", expression);
+            };
+            let Expressions::Binary { operand, rhs, .. } = value.as_ref() else {
+                panic!("expected binary expression for `{}`", expression);
+            };
+
+            assert_eq!(operand, "-");
+            assert!(matches!(
+                rhs.as_ref(),
+                Expressions::Value(Value::Integer(1), _)
+            ));
+        }
     }
 
     #[test]
-    fn while_stmt_test() {
-        let input = String::from("while 1 < 2 {};");
+    fn unclosed_parentheses_test() {
+        let input = String::from("int32 a = (1 + 2;");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2203,26 +4005,14 @@ mod tests {
         };
 
         let mut parser = Parser::new(tokens, "test".to_string(), input);
-        let ast = parser.parse().unwrap();
+        let ast = parser.parse();
 
-        assert_eq!(
-            ast[0],
-            Statements::WhileStatement {
-                condition: Expressions::Boolean {
-                    operand: String::from("<"),
-                    lhs: Box::new(Expressions::Value(Value::Integer(1))),
-                    rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                    line: 0
-                },
-                block: Vec::new(),
-                line: 0
-            }
-        );
+        assert!(ast.is_err());
     }
 
     #[test]
-    fn while_with_block_stmt_test() {
-        let input = String::from("while 1 < 2 { break };");
+    fn user_type_annotation_test() {
+        let input = String::from("int0 a; Point* p = &b;");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2233,24 +4023,28 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
+        // unknown types are reported on IR stage (they could be imported structures)
         assert_eq!(
             ast[0],
-            Statements::WhileStatement {
-                condition: Expressions::Boolean {
-                    operand: String::from("<"),
-                    lhs: Box::new(Expressions::Value(Value::Integer(1))),
-                    rhs: Box::new(Expressions::Value(Value::Integer(2))),
-                    line: 0
-                },
-                block: vec![Statements::BreakStatement { line: 0 }],
-                line: 0
+            Statements::AnnotationStatement {
+                identifier: String::from("a"),
+                datatype: String::from("int0"),
+                value: None,
+                line: 0,
+                span: Span::new(0, 0, 6)
             }
         );
+
+        assert!(matches!(
+            &ast[1],
+            Statements::AnnotationStatement { identifier, datatype, value: Some(_), .. }
+                if identifier == "p" && datatype == "Point*"
+        ));
     }
 
     #[test]
-    fn for_stmt_test() {
-        let input = String::from("for (int8 i = 0; i < 5; i++) {};");
+    fn struct_define_stmt_test() {
+        let input = String::from("struct Point { int32 x; str* name; Point* next; };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2262,35 +4056,24 @@ mod tests {
         let ast = parser.parse().unwrap();
 
         assert_eq!(
-            ast[0],
-            Statements::ForStatement {
-                initializer: Box::new(Statements::AnnotationStatement {
-                    identifier: String::from("i"),
-                    datatype: String::from("int8"),
-                    value: Some(Box::new(Expressions::Value(Value::Integer(0)))),
-                    line: 0
-                }),
-                condition: Expressions::Boolean {
-                    operand: String::from("<"),
-                    lhs: Box::new(Expressions::Value(Value::Identifier(String::from("i"))),),
-                    rhs: Box::new(Expressions::Value(Value::Integer(5))),
-                    line: 0
-                },
-                iterator: Box::new(Statements::BinaryAssignStatement {
-                    identifier: String::from("i"),
-                    operand: String::from("+"),
-                    value: Box::new(Expressions::Value(Value::Integer(1))),
-                    line: 0
-                }),
-                block: Vec::new(),
-                line: 0
-            }
+            ast,
+            vec![Statements::StructDefineStatement {
+                struct_name: String::from("Point"),
+                fields: vec![
+                    ("x".to_string(), "int32".to_string()),
+                    ("name".to_string(), "str*".to_string()),
+                    ("next".to_string(), "Point*".to_string()),
+                ],
+                docs: Vec::new(),
+                line: 0,
+                span: Span::new(0, 0, 49)
+            }]
         );
     }
 
     #[test]
-    fn for_stmt_with_block_test() {
-        let input = String::from("for (int8 i = 0; i < 5; i++) { print(i) };");
+    fn struct_literal_test() {
+        let input = String::from("Point p = Point { x: 1 + 2, name: a, };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2301,40 +4084,36 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        assert_eq!(
-            ast[0],
-            Statements::ForStatement {
-                initializer: Box::new(Statements::AnnotationStatement {
-                    identifier: String::from("i"),
-                    datatype: String::from("int8"),
-                    value: Some(Box::new(Expressions::Value(Value::Integer(0)))),
-                    line: 0
-                }),
-                condition: Expressions::Boolean {
-                    operand: String::from("<"),
-                    lhs: Box::new(Expressions::Value(Value::Identifier(String::from("i"))),),
-                    rhs: Box::new(Expressions::Value(Value::Integer(5))),
-                    line: 0
-                },
-                iterator: Box::new(Statements::BinaryAssignStatement {
-                    identifier: String::from("i"),
-                    operand: String::from("+"),
-                    value: Box::new(Expressions::Value(Value::Integer(1))),
-                    line: 0
-                }),
-                block: vec![Statements::FunctionCallStatement {
-                    function_name: String::from("print"),
-                    arguments: vec![Expressions::Value(Value::Identifier(String::from("i")))],
-                    line: 0
-                }],
-                line: 0
+        let Statements::AnnotationStatement {
+            datatype,
+            value: Some(value),
+            ..
+        } = &ast[0]
+        else {
+            panic!("Annotation expected, but found: {:?}", ast[0]);
+        };
+
+        assert_eq!(datatype, "Point");
+
+        match value.as_ref() {
+            Expressions::Struct { name, fields, .. } => {
+                assert_eq!(name, "Point");
+                assert_eq!(
+                    fields
+                        .iter()
+                        .map(|(field, value)| format!("{}: {}", field, expression_shape(value)))
+                        .collect::<Vec<String>>(),
+                    vec!["x: (1 + 2)", "name: a"]
+                );
             }
-        );
+            other => panic!("Structure literal expected, but found: {:?}", other),
+        }
     }
 
     #[test]
-    fn break_stmt_test() {
-        let input = String::from("break");
+    fn struct_literal_is_not_block_test() {
+        // `{` after identifier in condition opens a block, not a literal
+        let input = String::from("if flag { a = 1; };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2345,12 +4124,15 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        assert_eq!(ast[0], Statements::BreakStatement { line: 0 });
+        assert!(matches!(
+            &ast[0],
+            Statements::IfStatement { then_block, .. } if then_block.len() == 1
+        ));
     }
 
     #[test]
-    fn import_statement() {
-        let input = String::from("import \"std.tpl\"");
+    fn field_assign_stmt_test() {
+        let input = String::from("p.x = 5;");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2363,16 +4145,29 @@ mod tests {
 
         assert_eq!(
             ast[0],
-            Statements::ImportStatement {
-                path: Expressions::Value(Value::String("std.tpl".to_string())),
-                line: 0
+            Statements::FieldAssignStatement {
+                target: Box::new(Expressions::SubElement {
+                    parent: Box::new(Expressions::Value(
+                        Value::Identifier("p".to_string()),
+                        Span::new(0, 0, 1)
+                    )),
+                    child: Box::new(Expressions::Value(
+                        Value::Identifier("x".to_string()),
+                        Span::new(0, 2, 3)
+                    )),
+                    line: 0,
+                    span: Span::new(0, 0, 3)
+                }),
+                value: Box::new(Expressions::Value(Value::Integer(5), Span::new(0, 6, 7))),
+                line: 0,
+                span: Span::new(0, 0, 7)
             }
         );
     }
 
     #[test]
-    fn lambda_expr_test() {
-        let input = String::from("fn<int8> a = int8 (int8 a, int8 b) { return 0 };");
+    fn function_define_with_user_types_test() {
+        let input = String::from("define Point shift(Point* p, int32 dx) { return *p; };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2383,31 +4178,20 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        assert_eq!(
-            ast[0],
-            Statements::AnnotationStatement {
-                identifier: String::from("a"),
-                datatype: String::from("fn<int8>"),
-                value: Some(Box::new(Expressions::Lambda {
-                    arguments: vec![
-                        ("a".to_string(), "int8".to_string()),
-                        ("b".to_string(), "int8".to_string()),
-                    ],
-                    statements: vec![Statements::ReturnStatement {
-                        value: Expressions::Value(Value::Integer(0)),
-                        line: 0
-                    }],
-                    ftype: String::from("int8"),
-                    line: 0
-                })),
-                line: 0
-            }
-        );
+        assert!(matches!(
+            &ast[0],
+            Statements::FunctionDefineStatement { function_type, arguments, .. }
+                if function_type == "Point"
+                    && arguments == &vec![
+                        ("p".to_string(), "Point*".to_string()),
+                        ("dx".to_string(), "int32".to_string()),
+                    ]
+        ));
     }
 
     #[test]
-    fn expressions_enum_test() {
-        let input = String::from("(1, true, \"a\")");
+    fn enum_define_stmt_test() {
+        let input = String::from("enum Shape { Circle(int32), Rect(int32, Point*), Empty };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2416,21 +4200,31 @@ mod tests {
         };
 
         let mut parser = Parser::new(tokens, "test".to_string(), input);
-        let ast = parser.expressions_enum(TokenType::LParen, TokenType::RParen, TokenType::Comma);
+        let ast = parser.parse().unwrap();
 
         assert_eq!(
             ast,
-            vec![
-                Expressions::Value(Value::Integer(1)),
-                Expressions::Value(Value::Boolean(true)),
-                Expressions::Value(Value::String("a".to_string())),
-            ]
+            vec![Statements::EnumDefineStatement {
+                enum_name: String::from("Shape"),
+                variants: vec![
+                    ("Circle".to_string(), vec!["int32".to_string()]),
+                    (
+                        "Rect".to_string(),
+                        vec!["int32".to_string(), "Point*".to_string()]
+                    ),
+                    ("Empty".to_string(), Vec::new()),
+                ],
+                docs: Vec::new(),
+                line: 0,
+                span: Span::new(0, 0, 56)
+            }]
         );
     }
 
     #[test]
-    fn expressions_enum_test_2() {
-        let input = String::from("[1; true; \"a\"]");
+    fn match_stmt_test() {
+        let input =
+            String::from("match s { Circle(r) => { a = r; }, Rect(w, _) => { a = w; } _ => {} };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2439,22 +4233,32 @@ mod tests {
         };
 
         let mut parser = Parser::new(tokens, "test".to_string(), input);
-        let ast =
-            parser.expressions_enum(TokenType::LBrack, TokenType::RBrack, TokenType::Semicolon);
+        let ast = parser.parse().unwrap();
+
+        let Statements::MatchStatement { value, arms, .. } = &ast[0] else {
+            panic!("Match statement expected, but found: {:?}", ast[0]);
+        };
 
+        assert_eq!(expression_shape(value), "s");
         assert_eq!(
-            ast,
+            arms.iter()
+                .map(|(variant, bindings, block)| (
+                    variant.as_str(),
+                    bindings.join(","),
+                    block.len()
+                ))
+                .collect::<Vec<(&str, String, usize)>>(),
             vec![
-                Expressions::Value(Value::Integer(1)),
-                Expressions::Value(Value::Boolean(true)),
-                Expressions::Value(Value::String("a".to_string())),
+                ("Circle", String::from("r"), 1),
+                ("Rect", String::from("w,_"), 1),
+                ("_", String::new(), 0),
             ]
         );
     }
 
     #[test]
-    fn error_test() {
-        let input = String::from("int32 a = ;");
+    fn else_if_chain_test() {
+        let input = String::from("if a { x = 1; } else if b { x = 2; } else { x = 3; };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2463,15 +4267,41 @@ mod tests {
         };
 
         let mut parser = Parser::new(tokens, "test".to_string(), input);
-        let ast = parser.parse();
+        let ast = parser.parse().unwrap();
 
-        assert!(ast.is_err());
+        assert_eq!(ast.len(), 1);
+
+        let Statements::IfStatement {
+            else_block: Some(else_block),
+            span,
+            ..
+        } = &ast[0]
+        else {
+            panic!("If/else statement expected, but found: {:?}", ast[0]);
+        };
+
+        assert_eq!(*span, Span::new(0, 0, 52));
+
+        match else_block.as_slice() {
+            [Statements::IfStatement {
+                condition,
+                else_block: Some(last_block),
+                ..
+            }] => {
+                assert_eq!(expression_shape(condition), "b");
+                assert_eq!(last_block.len(), 1);
+            }
+            other => panic!(
+                "Nested `if` expected in `else` block, but found: {:?}",
+                other
+            ),
+        }
     }
 
     #[test]
-    #[should_panic]
-    fn should_panic_test() {
-        let input = String::from("int0 a;");
+    fn switch_stmt_test() {
+        let input =
+            String::from("switch (x + 1) { case 1, 2 { a = 1; } case 'c' {} default { a = 0; } };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2480,12 +4310,36 @@ mod tests {
         };
 
         let mut parser = Parser::new(tokens, "test".to_string(), input);
-        let _ = parser.parse().unwrap();
+        let ast = parser.parse().unwrap();
+
+        let Statements::SwitchStatement {
+            value,
+            cases,
+            default_block,
+            ..
+        } = &ast[0]
+        else {
+            panic!("Switch statement expected, but found: {:?}", ast[0]);
+        };
+
+        assert_eq!(expression_shape(value), "(x + 1)");
+        assert_eq!(
+            cases
+                .iter()
+                .map(|(values, block)| (values.len(), block.len()))
+                .collect::<Vec<(usize, usize)>>(),
+            vec![(2, 1), (1, 0)]
+        );
+        assert_eq!(
+            cases[1].0[0],
+            Expressions::Value(Value::Char('c'), Span::new(0, 43, 46))
+        );
+        assert_eq!(default_block.as_ref().map(|block| block.len()), Some(1));
     }
 
     #[test]
-    fn array_annotation_test() {
-        let input = String::from("int32[] a;");
+    fn case_outside_switch_test() {
+        let input = String::from("case 1 { a = 1; };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2494,22 +4348,13 @@ mod tests {
         };
 
         let mut parser = Parser::new(tokens, "test".to_string(), input);
-        let ast = parser.parse().unwrap();
 
-        assert_eq!(
-            ast[0],
-            Statements::AnnotationStatement {
-                identifier: String::from("a"),
-                datatype: String::from("int32[auto]"),
-                value: None,
-                line: 0
-            }
-        );
+        assert!(parser.parse().is_err());
     }
 
     #[test]
-    fn array_annotation_with_len_test() {
-        let input = String::from("int32[5] a;");
+    fn for_in_range_stmt_test() {
+        let input = String::from("for i in 0..n - 1 { a = i; }; for j in 1..=10 {};");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2520,20 +4365,55 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
+        assert_eq!(ast.len(), 2);
+
+        let ranges = ast
+            .iter()
+            .map(|statement| match statement {
+                Statements::ForInStatement {
+                    variable,
+                    iterable:
+                        Expressions::Range {
+                            start,
+                            end,
+                            inclusive,
+                            ..
+                        },
+                    ..
+                } => (
+                    variable.clone(),
+                    expression_shape(start),
+                    expression_shape(end),
+                    *inclusive,
+                ),
+                other => panic!("Range-based for expected, but found: {:?}", other),
+            })
+            .collect::<Vec<_>>();
+
         assert_eq!(
-            ast[0],
-            Statements::AnnotationStatement {
-                identifier: String::from("a"),
-                datatype: String::from("int32[5]"),
-                value: None,
-                line: 0
-            }
+            ranges,
+            vec![
+                (
+                    String::from("i"),
+                    String::from("0"),
+                    String::from("(n - 1)"),
+                    false
+                ),
+                (
+                    String::from("j"),
+                    String::from("1"),
+                    String::from("10"),
+                    true
+                ),
+            ]
         );
+
+        assert_eq!(ast[0].span(), Some(Span::new(0, 0, 28)));
     }
 
     #[test]
-    fn array_annotation_with_values_test() {
-        let input = String::from("int32[] a = [1,2,3];");
+    fn for_in_iterable_stmt_test() {
+        let input = String::from("for c in word { print(c); };");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2544,28 +4424,24 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        assert_eq!(
-            ast[0],
-            Statements::AnnotationStatement {
-                identifier: String::from("a"),
-                datatype: String::from("int32[auto]"),
-                value: Some(Box::new(Expressions::Array {
-                    values: vec![
-                        Expressions::Value(Value::Integer(1)),
-                        Expressions::Value(Value::Integer(2)),
-                        Expressions::Value(Value::Integer(3)),
-                    ],
-                    len: 3,
-                    line: 0
-                })),
-                line: 0
+        match &ast[0] {
+            Statements::ForInStatement {
+                variable,
+                iterable,
+                block,
+                ..
+            } => {
+                assert_eq!(variable, "c");
+                assert_eq!(expression_shape(iterable), "word");
+                assert_eq!(block.len(), 1);
             }
-        );
+            other => panic!("Range-based for expected, but found: {:?}", other),
+        }
     }
 
     #[test]
-    fn array_annotation_with_empty_test() {
-        let input = String::from("int32[] a = [];");
+    fn nested_array_annotation_test() {
+        let input = String::from("int32[3][4] m; define void f(int32[][4] rows) {};");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2576,24 +4452,24 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        assert_eq!(
-            ast[0],
-            Statements::AnnotationStatement {
-                identifier: String::from("a"),
-                datatype: String::from("int32[auto]"),
-                value: Some(Box::new(Expressions::Array {
-                    values: vec![],
-                    len: 0,
-                    line: 0
-                })),
-                line: 0
+        match (&ast[0], &ast[1]) {
+            (
+                Statements::AnnotationStatement { datatype, .. },
+                Statements::FunctionDefineStatement { arguments, .. },
+            ) => {
+                assert_eq!(datatype, "int32[3][4]");
+                assert_eq!(
+                    arguments,
+                    &vec![(String::from("rows"), String::from("int32[][4]"))]
+                );
             }
-        );
+            other => panic!("Annotation and function expected, but found: {:?}", other),
+        }
     }
 
     #[test]
-    fn pointer_annotation_test() {
-        let input = String::from("int32* a;");
+    fn list_annotation_test() {
+        let input = String::from("list<int32> xs; list<list<str>> rows = [];");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2604,20 +4480,32 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        assert_eq!(
-            ast[0],
-            Statements::AnnotationStatement {
-                identifier: String::from("a"),
-                datatype: String::from("int32*"),
-                value: None,
-                line: 0
+        match (&ast[0], &ast[1]) {
+            (
+                Statements::AnnotationStatement {
+                    datatype: first,
+                    value: None,
+                    ..
+                },
+                Statements::AnnotationStatement {
+                    datatype: second,
+                    value: Some(_),
+                    span,
+                    ..
+                },
+            ) => {
+                assert_eq!(first, "list<int32>");
+                assert_eq!(second, "list<list<str>>");
+                assert_eq!(*span, Span::new(0, 16, 41));
             }
-        );
+            other => panic!("Two annotations expected, but found: {:?}", other),
+        }
     }
 
     #[test]
-    fn pointer_on_ref_annotation_test() {
-        let input = String::from("int32* a = &b;");
+    fn map_annotation_test() {
+        let input =
+            String::from("map<str, list<int32>> groups; define void f(map<int64, str> names) {};");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2628,23 +4516,26 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        assert_eq!(
-            ast[0],
-            Statements::AnnotationStatement {
-                identifier: String::from("a"),
-                datatype: String::from("int32*"),
-                value: Some(Box::new(Expressions::Reference {
-                    object: Box::new(Expressions::Value(Value::Identifier("b".to_string()))),
-                    line: 0
-                })),
-                line: 0
+        match (&ast[0], &ast[1]) {
+            (
+                Statements::AnnotationStatement { datatype, .. },
+                Statements::FunctionDefineStatement { arguments, .. },
+            ) => {
+                assert_eq!(datatype, "map<str, list<int32>>");
+                assert_eq!(
+                    arguments,
+                    &vec![(String::from("names"), String::from("map<int64, str>"))]
+                );
             }
-        );
+            other => panic!("Annotation and function expected, but found: {:?}", other),
+        }
     }
 
     #[test]
-    fn logical_or_in_condition() {
-        let input = String::from("if 1 > 2 || 2 > 1 {};");
+    fn fn_signature_test() {
+        let input = String::from(
+            "fn<int32(int32, str)> f; fn<void()> g; list<fn<bool(int32)>> checks; define fn<int32(int32)> make(fn<int32> old) {};",
+        );
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2655,12 +4546,42 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        dbg!(&ast);
+        let datatypes = ast[..3]
+            .iter()
+            .map(|statement| match statement {
+                Statements::AnnotationStatement { datatype, .. } => datatype.as_str(),
+                other => panic!("Annotation expected, but found: {:?}", other),
+            })
+            .collect::<Vec<&str>>();
+
+        assert_eq!(
+            datatypes,
+            vec![
+                "fn<int32(int32, str)>",
+                "fn<void()>",
+                "list<fn<bool(int32)>>"
+            ]
+        );
+
+        match &ast[3] {
+            Statements::FunctionDefineStatement {
+                function_type,
+                arguments,
+                ..
+            } => {
+                assert_eq!(function_type, "fn<int32(int32)>");
+                assert_eq!(
+                    arguments,
+                    &vec![(String::from("old"), String::from("fn<int32>"))]
+                );
+            }
+            other => panic!("Function definition expected, but found: {:?}", other),
+        }
     }
 
     #[test]
-    fn bitwise_operation_in_annotation() {
-        let input = String::from("int32 a = 5 & 1");
+    fn nested_slice_test() {
+        let input = String::from("a = m[i][j + 1]; m[1][2] = a;");
         let mut lexer = Lexer::new(input.clone(), "test".to_string());
 
         let tokens = match lexer.tokenize() {
@@ -2671,19 +4592,31 @@ mod tests {
         let mut parser = Parser::new(tokens, "test".to_string(), input);
         let ast = parser.parse().unwrap();
 
-        assert_eq!(
-            ast[0],
-            Statements::AnnotationStatement {
-                identifier: String::from("a"),
-                datatype: String::from("int32"),
-                value: Some(Box::new(Expressions::Bitwise {
-                    operand: String::from("&"),
-                    lhs: Box::new(Expressions::Value(Value::Integer(5))),
-                    rhs: Box::new(Expressions::Value(Value::Integer(1))),
-                    line: 0
-                })),
-                line: 0
-            }
-        );
+        assert_eq!(ast.len(), 2);
+
+        let Statements::AssignStatement { value, .. } = &ast[0] else {
+            panic!("Assignment expected, but found: {:?}", ast[0]);
+        };
+
+        let Expressions::Slice { object, index, .. } = value.as_ref() else {
+            panic!("Slice expected, but found: {:?}", value);
+        };
+
+        assert_eq!(expression_shape(index), "(j + 1)");
+        assert!(matches!(
+            object.as_ref(),
+            Expressions::Slice { object, .. }
+                if matches!(object.as_ref(), Expressions::Value(Value::Identifier(name), _) if name == "m")
+        ));
+
+        let Statements::SliceAssignStatement { object, index, .. } = &ast[1] else {
+            panic!("Slice assignment expected, but found: {:?}", ast[1]);
+        };
+
+        assert_eq!(expression_shape(index), "2");
+        assert!(matches!(
+            object.as_ref(),
+            Expressions::Slice { index, .. } if expression_shape(index) == "1"
+        ));
     }
 }
@@ -4,11 +4,13 @@
 // Project licensed under the BSD-3 LICENSE.
 // Check the `LICENSE` file to more info.
 
-// NOTE: `line` field added for error handling on IR stage
+// NOTE: `line` field added for error handling on IR stage,
+// `span` marks the exact source range of the node
 
 use crate::expressions::Expressions;
+use tpl_lexer::span::Span;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 #[allow(unused)]
 pub enum Statements {
     // Assign
@@ -16,23 +18,33 @@ pub enum Statements {
         identifier: String,
         value: Box<Expressions>,
         line: usize,
+        span: Span,
     },
     BinaryAssignStatement {
         identifier: String,
         operand: String,
         value: Box<Expressions>,
         line: usize,
+        span: Span,
     },
     DerefAssignStatement {
         identifier: String,
         value: Box<Expressions>,
         line: usize,
+        span: Span,
     },
     SliceAssignStatement {
-        identifier: String,
+        object: Box<Expressions>, // array or nested slice `a[1]`
         index: Box<Expressions>,
         value: Box<Expressions>,
         line: usize,
+        span: Span,
+    },
+    FieldAssignStatement {
+        target: Box<Expressions>, // `a.b` subelement
+        value: Box<Expressions>,
+        line: usize,
+        span: Span,
     },
 
     // Annotation
@@ -41,6 +53,7 @@ pub enum Statements {
         datatype: String,
         value: Option<Box<Expressions>>,
         line: usize,
+        span: Span,
     },
 
     // Functions
@@ -49,12 +62,31 @@ pub enum Statements {
         function_type: String,
         arguments: Vec<(String, String)>, // ("a", "int")
         block: Vec<Statements>,
+        docs: Vec<String>, // `///` lines above definition
         line: usize,
+        span: Span,
     },
     FunctionCallStatement {
         function_name: String,
         arguments: Vec<Expressions>,
         line: usize,
+        span: Span,
+    },
+
+    // Structures
+    StructDefineStatement {
+        struct_name: String,
+        fields: Vec<(String, String)>, // ("x", "int32")
+        docs: Vec<String>,
+        line: usize,
+        span: Span,
+    },
+    EnumDefineStatement {
+        enum_name: String,
+        variants: Vec<(String, Vec<String>)>, // ("Rect", ["int32", "int32"])
+        docs: Vec<String>,
+        line: usize,
+        span: Span,
     },
 
     // Constructions
@@ -63,11 +95,13 @@ pub enum Statements {
         then_block: Vec<Statements>,
         else_block: Option<Vec<Statements>>,
         line: usize,
+        span: Span,
     },
     WhileStatement {
         condition: Expressions,
         block: Vec<Statements>,
         line: usize,
+        span: Span,
     },
     ForStatement {
         initializer: Box<Statements>,
@@ -75,24 +109,81 @@ pub enum Statements {
         iterator: Box<Statements>,
         block: Vec<Statements>,
         line: usize,
+        span: Span,
+    },
+    ForInStatement {
+        variable: String,
+        iterable: Expressions, // range, array or string
+        block: Vec<Statements>,
+        line: usize,
+        span: Span,
+    },
+    SwitchStatement {
+        value: Expressions,
+        cases: Vec<(Vec<Expressions>, Vec<Statements>)>, // ([1, 2], block)
+        default_block: Option<Vec<Statements>>,
+        line: usize,
+        span: Span,
+    },
+    MatchStatement {
+        value: Expressions,
+        arms: Vec<(String, Vec<String>, Vec<Statements>)>, // ("Circle", ["r"], block), `_` is default
+        line: usize,
+        span: Span,
     },
 
     // Import
     ImportStatement {
         path: Expressions,
         line: usize,
+        span: Span,
     },
 
     // Etc
     BreakStatement {
         line: usize,
+        span: Span,
+    },
+    ContinueStatement {
+        line: usize,
+        span: Span,
     },
     ReturnStatement {
         value: Expressions,
         line: usize,
+        span: Span,
     },
 
     Expression(Expressions),
     None,
     End,
 }
+
+impl Statements {
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Statements::AssignStatement { span, .. }
+            | Statements::BinaryAssignStatement { span, .. }
+            | Statements::DerefAssignStatement { span, .. }
+            | Statements::SliceAssignStatement { span, .. }
+            | Statements::FieldAssignStatement { span, .. }
+            | Statements::AnnotationStatement { span, .. }
+            | Statements::FunctionDefineStatement { span, .. }
+            | Statements::FunctionCallStatement { span, .. }
+            | Statements::StructDefineStatement { span, .. }
+            | Statements::EnumDefineStatement { span, .. }
+            | Statements::IfStatement { span, .. }
+            | Statements::WhileStatement { span, .. }
+            | Statements::ForStatement { span, .. }
+            | Statements::ForInStatement { span, .. }
+            | Statements::SwitchStatement { span, .. }
+            | Statements::MatchStatement { span, .. }
+            | Statements::ImportStatement { span, .. }
+            | Statements::BreakStatement { span, .. }
+            | Statements::ContinueStatement { span, .. }
+            | Statements::ReturnStatement { span, .. } => Some(*span),
+            Statements::Expression(expression) => expression.span(),
+            Statements::None | Statements::End => None,
+        }
+    }
+}
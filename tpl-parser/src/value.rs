@@ -4,10 +4,11 @@
 // Project licensed under the BSD-3 LICENSE.
 // Check the `LICENSE` file to more info.
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 #[allow(unused)]
 pub enum Value {
     Integer(i64),
+    Float(f64),
     String(String),
     Char(char),
     Boolean(bool),
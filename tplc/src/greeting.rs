@@ -23,9 +23,11 @@ pub fn print_greeting() {
 
 pub fn print_usage() {
     let usage = format!(
-        "| Usage: {}\n| Example: {}",
-        format!("{} [input] [output]", PROJECT_PACKAGE).yellow(),
-        format!("{} example.tpl output", PROJECT_PACKAGE).yellow()
+        "| Usage: {}\n| Example: {}\n| Flags:\n|   {} - {}",
+        format!("{} [input] [output] [flags]", PROJECT_PACKAGE).yellow(),
+        format!("{} example.tpl output", PROJECT_PACKAGE).yellow(),
+        "--no-bounds-checks".yellow(),
+        "disable runtime array and string bounds checks"
     );
 
     println!("{}", usage);
@@ -17,24 +17,39 @@ const OPTIMIZATION_LEVEL: inkwell::OptimizationLevel = inkwell::OptimizationLeve
 const RELOC_MODE: inkwell::targets::RelocMode = inkwell::targets::RelocMode::PIC;
 const CODE_MODEL: inkwell::targets::CodeModel = inkwell::targets::CodeModel::Large;
 
-pub const COMMENTS_START: &str = "//";
-
 struct Config {
     pub input: String,
     pub output: String,
     pub source: String,
+
+    pub bounds_checks: bool,
 }
 
 impl Config {
     fn parse(arguments: Vec<String>) -> Result<Self, String> {
+        // splitting flags and positional arguments
+
+        let mut positional = Vec::new();
+        let mut bounds_checks = true;
+
+        for argument in arguments.into_iter().skip(1) {
+            match argument.as_str() {
+                "--no-bounds-checks" => bounds_checks = false,
+                flag if flag.starts_with("--") => {
+                    return Err(format!("Unknown flag `{}`! See `Usage`.", flag));
+                }
+                _ => positional.push(argument),
+            }
+        }
+
         // checking arguments count
 
-        if arguments.len() < 3 {
+        if positional.len() < 2 {
             return Err(String::from("Not enough arguments! See `Usage`."));
         }
 
         // getting source code
-        let source_file = arguments[1].clone();
+        let source_file = positional[0].clone();
         let source = match std::fs::read_to_string(source_file) {
             Ok(code) => code,
             Err(_) => {
@@ -44,26 +59,14 @@ impl Config {
             }
         };
 
-        // deleting comments
-
-        let formatted_source = source
-            .lines()
-            .map(|line| {
-                if let Some(index) = line.find(COMMENTS_START) {
-                    &line[..index]
-                } else {
-                    line
-                }
-            })
-            .collect::<Vec<&str>>()
-            .join("\n");
-
         // returning config
 
         Ok(Self {
-            input: arguments[1].clone(),
-            output: arguments[2].clone(),
-            source: formatted_source,
+            input: positional[0].clone(),
+            output: positional[1].clone(),
+            source,
+
+            bounds_checks,
         })
     }
 }
@@ -94,7 +97,8 @@ fn main() {
         config.output.as_str(),
         config.input.clone(),
         config.source.clone(),
-    );
+    )
+    .with_bounds_checks(config.bounds_checks);
 
     // creating lexical analyzer and getting tokens
 
@@ -118,7 +122,17 @@ fn main() {
     match ast {
         Ok(stmts) => {
             // compiling statements to module
-            compiler.generate(stmts);
+            if let Err(diagnostics) = compiler.generate(stmts) {
+                // printing all codegen errors in terminal and quitting
+                eprintln!("---- codegen found {} errors! ----", diagnostics.len());
+
+                for diagnostic in diagnostics {
+                    eprintln!("{}\n", diagnostic.format_error());
+                }
+
+                std::process::exit(1);
+            }
+
             let module = compiler.get_module();
 
             // // debug